@@ -0,0 +1,33 @@
+use togui::{Window, Text, ComboBox, Container, Font, TextDirection};
+
+// 用法: cargo run --example bidi_demo -- path/to/font.ttf [字号]
+// 需要包含阿拉伯语和希伯来语字形的字体，例如 DejaVuSans.ttf
+fn main() {
+    let mut args = std::env::args().skip(1);
+    if let Some(path) = args.next() {
+        let size = args.next().and_then(|size| size.parse().ok()).unwrap_or(18.0);
+        match Font::load_ttf(&path, size) {
+            Ok(font) => Font::set_default(font.with_fallback(Font::basic())),
+            Err(e) => println!("Failed to load font {}: {:?}", path, e),
+        }
+    }
+
+    let mut window = Window::new("ToGUI Bidi Demo", 800, 600);
+
+    let mut container = Container::new(0.0, 0.0, 400.0, 600.0);
+    // 从右到左的段落自动靠右对齐
+    container.add_child(Text::new(0.0, 0.0, "שלום עולם! זוהי פסקה בעברית עם המילה English באמצע.").with_wrap(true));
+    container.add_child(Text::new(0.0, 0.0, "مرحبا بالعالم، النص العربي يتصل حروفه.").with_wrap(true));
+    container.add_child(Text::new(0.0, 0.0, "Mixed: abc שלום (123) def"));
+    container.add_child(Text::new(0.0, 0.0, "Forced RTL: (a + b)").with_direction(TextDirection::Rtl));
+
+    // 在输入框中用左右方向键移动光标
+    let cities = ["ירושלים", "תל אביב", "القاهرة", "دبي", "London"];
+    let combo = ComboBox::new(0.0, 0.0, 240.0, 28.0, &cities)
+        .with_editable(true)
+        .on_select(|index, value| println!("Picked {}: {}", index, value));
+    container.add_child(combo);
+
+    window.add_widget(container);
+    window.run();
+}
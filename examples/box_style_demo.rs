@@ -0,0 +1,48 @@
+use togui::{Window, BoxShadow, BoxStyle, Button, Color, Container, CornerRadii, Direction, Padding, Text};
+
+fn main() {
+    let mut window = Window::new("ToGUI Box Style Demo", 800, 600);
+
+    let mut main_container = Container::new(0.0, 0.0, 800.0, 600.0)
+        .with_padding(Padding::all(30.0))
+        .with_spacing(30.0);
+    main_container.with_direction(Direction::Horizontal);
+
+    // 带阴影的圆角卡片
+    let card = BoxStyle::new()
+        .with_background(Color::rgb(250, 250, 250))
+        .with_corner_radius(12.0)
+        .with_shadow(BoxShadow::new(0.0, 4.0, 16.0, Color::rgba(0, 0, 0, 140)));
+    let mut first = Container::new(0.0, 0.0, 220.0, 160.0)
+        .with_padding(Padding::all(16.0))
+        .with_box_style(card);
+    first.add_child(Text::new(0.0, 0.0, "Card with a soft shadow").with_color(Color::rgb(30, 30, 30)));
+    first.add_child(Button::new(0.0, 0.0, 120.0, 36.0, "Open"));
+    main_container.add_child(first);
+
+    // 四个角半径不同、带边框的面板
+    let panel = BoxStyle::new()
+        .with_background(Color::rgb(45, 60, 90))
+        .with_border(3.0, Color::rgb(110, 170, 255))
+        .with_corner_radii(CornerRadii::new(24.0, 4.0, 24.0, 4.0));
+    let mut second = Container::new(0.0, 0.0, 220.0, 160.0)
+        .with_padding(Padding::all(20.0))
+        .with_box_style(panel);
+    second.add_child(Text::new(0.0, 0.0, "Per-corner radii and a border"));
+    main_container.add_child(second);
+
+    // 扩展后没有偏移和模糊的阴影相当于第二层边框，发光效果则只用模糊
+    let glow = BoxStyle::new()
+        .with_background(Color::rgb(40, 40, 40))
+        .with_corner_radius(80.0)
+        .with_shadow(BoxShadow::new(0.0, 0.0, 24.0, Color::rgba(255, 200, 80, 160)).with_spread(4.0))
+        .with_shadow(BoxShadow::new(0.0, 0.0, 0.0, Color::rgb(255, 200, 80)).with_spread(4.0));
+    let mut third = Container::new(0.0, 0.0, 160.0, 160.0)
+        .with_padding(Padding::all(50.0))
+        .with_box_style(glow);
+    third.add_child(Text::new(0.0, 0.0, "Glow"));
+    main_container.add_child(third);
+
+    window.add_root(main_container);
+    window.run();
+}
@@ -0,0 +1,33 @@
+use togui::{Window, Color, Container, Text, Theme, Direction, Padding};
+
+fn main() {
+    let mut window = Window::new("ToGUI Color Demo", 800, 600);
+
+    let mut main_container = Container::new(0.0, 0.0, 800.0, 600.0)
+        .with_padding(Padding::all(20.0))
+        .with_spacing(8.0);
+    main_container.with_direction(Direction::Vertical);
+
+    // 同一个基色的亮暗变体，可用作悬停和按下状态
+    let base = Color::parse("#466eaa").expect("invalid color");
+    let variants = [
+        ("lighten(0.2)", base.lighten(0.2)),
+        ("base", base),
+        ("darken(0.2)", base.darken(0.2)),
+        ("mix(white, 0.5)", base.mix(Color::WHITE, 0.5)),
+        ("hsl(30, 90%, 60%)", Color::parse("hsl(30, 90%, 60%)").expect("invalid color")),
+        ("orange", Color::named("orange").expect("unknown color")),
+    ];
+
+    // 与默认深色主题背景的对比度，WCAG 要求正文至少 4.5:1
+    let background = Theme::dark().palette.background;
+    for (name, color) in variants {
+        let ratio = color.contrast_ratio(background);
+        let verdict = if ratio >= 4.5 { "AA" } else { "fails AA" };
+        let label = format!("{:<18} {}  contrast {:.2}:1 {}", name, color.to_hex(), ratio, verdict);
+        main_container.add_child(Text::new(0.0, 0.0, &label).with_color(color));
+    }
+
+    window.add_root(main_container);
+    window.run();
+}
@@ -0,0 +1,26 @@
+use togui::{Window, ComboBox, Container};
+
+fn main() {
+    let mut window = Window::new("ToGUI ComboBox Demo", 800, 600);
+
+    let fruits = [
+        "Apple", "Apricot", "Banana", "Blueberry", "Cherry", "Grape",
+        "Lemon", "Mango", "Orange", "Peach", "Pear", "Plum", "Strawberry",
+    ];
+
+    let mut container = Container::new(0.0, 0.0, 800.0, 600.0);
+
+    let combo = ComboBox::new(0.0, 0.0, 240.0, 28.0, &fruits)
+        .with_selected(0)
+        .on_select(|index, value| println!("Selected {}: {}", index, value));
+    container.add_child(combo);
+
+    let editable = ComboBox::new(0.0, 0.0, 240.0, 28.0, &fruits)
+        .with_editable(true)
+        .with_max_visible(5)
+        .on_select(|index, value| println!("Picked {}: {}", index, value));
+    container.add_child(editable);
+
+    window.add_widget(container);
+    window.run();
+}
@@ -0,0 +1,49 @@
+use togui::{Window, Color, ConstraintLayout, Button, Text, Strength};
+
+fn main() {
+    let mut window = Window::new("ToGUI Constraint Layout Demo", 800, 600);
+
+    let mut layout = ConstraintLayout::new(0.0, 0.0, 800.0, 600.0);
+    layout.add_child("sidebar", Button::new(0.0, 0.0, 200.0, 50.0, "Sidebar")).expect("duplicate name");
+    layout.add_child("title", Text::new(0.0, 0.0, "Connect to server").with_color(Color::rgb(255, 220, 120))).expect("duplicate name");
+    layout.add_child("label", Text::new(0.0, 0.0, "Address")).expect("duplicate name");
+    layout.add_child("address", Button::new(0.0, 0.0, 200.0, 50.0, "example.com")).expect("duplicate name");
+    layout.add_child("ok", Button::new(0.0, 0.0, 120.0, 50.0, "OK").on_click(|| println!("OK clicked"))).expect("duplicate name");
+    layout.add_child("cancel", Button::new(0.0, 0.0, 120.0, 50.0, "Cancel").on_click(|| println!("Cancel clicked"))).expect("duplicate name");
+
+    let constraints = [
+        // 侧栏占满左侧，宽度是可编辑变量，限制在 120 到窗口宽度的一半之间
+        "sidebar.left == parent.left",
+        "sidebar.top == parent.top",
+        "sidebar.bottom == parent.bottom",
+        "sidebar.width >= 120",
+        "sidebar.width <= parent.width / 2",
+        // 标题和表单位于侧栏右侧
+        "title.left == sidebar.right + 16",
+        "title.top == parent.top + 16",
+        "label.left == title.left",
+        "label.center_y == address.center_y",
+        "label.width >= 120 weak",
+        "address.left == label.right + 8",
+        "address.top == title.bottom + 24",
+        "address.right == parent.right - 16 medium",
+        // 按钮靠右下角，宽度相同
+        "ok.right == parent.right - 16",
+        "ok.bottom == parent.bottom - 16",
+        "cancel.right == ok.left - 8",
+        "cancel.top == ok.top",
+        "cancel.width == ok.width",
+    ];
+    for constraint in constraints {
+        if let Err(err) = layout.add_constraint(constraint) {
+            println!("Failed to add constraint: {:?}", err);
+        }
+    }
+
+    // 交互调整侧栏宽度时只需要建议新的值，求解器增量地更新其余组件
+    let _ = layout.add_edit_variable("sidebar.width", Strength::Strong);
+    let _ = layout.suggest_value("sidebar.width", 180.0);
+
+    window.add_root(layout);
+    window.run();
+}
@@ -0,0 +1,35 @@
+use togui::{Window, Button, Container, Text, Dialog, DialogResult, Popup, Rect, Tooltip};
+
+fn main() {
+    let mut window = Window::new("ToGUI Dialog Demo", 800, 600);
+    let overlays = window.get_overlay_handle();
+
+    let mut container = Container::new(0.0, 0.0, 800.0, 600.0);
+
+    let dialog_overlays = overlays.clone();
+    let open_dialog = Button::new(0.0, 0.0, 200.0, 50.0, "Open Dialog").on_click(move || {
+        let dialog = Dialog::new("Save changes?")
+            .with_content(Text::new(0.0, 0.0, "Your document has unsaved changes."))
+            .with_button("Save")
+            .with_button("Discard")
+            .with_button("Cancel")
+            .on_result(|result| match result {
+                DialogResult::Button(index) => println!("Dialog button {} clicked", index),
+                DialogResult::Cancelled => println!("Dialog cancelled"),
+            });
+        dialog_overlays.show_dialog(dialog);
+    });
+    container.add_child(Tooltip::new(open_dialog, "Opens a modal dialog"));
+
+    // 弹出层锚定在按钮下方
+    let anchor = Rect { x: 10.0, y: 65.0, width: 200.0, height: 50.0 };
+    let open_popup = Button::new(0.0, 0.0, 200.0, 50.0, "Open Popup").on_click(move || {
+        let popup = Popup::new(Button::new(0.0, 0.0, 160.0, 40.0, "Popup content"), anchor)
+            .on_dismiss(|| println!("Popup dismissed"));
+        overlays.show_popup(popup);
+    });
+    container.add_child(open_popup);
+
+    window.add_widget(container);
+    window.run();
+}
@@ -0,0 +1,38 @@
+use togui::{Window, Text, Button, Container, Font};
+
+// 用法: cargo run --example font_demo -- path/to/unifont.hex
+//       cargo run --example font_demo -- path/to/font.bdf
+//       cargo run --example font_demo -- path/to/font.psf
+//       cargo run --example font_demo -- path/to/font.ttf [字号]
+fn main() {
+    let mut args = std::env::args().skip(1);
+    if let Some(path) = args.next() {
+        let size = args.next().and_then(|size| size.parse().ok()).unwrap_or(18.0);
+        let font = if path.ends_with(".hex") {
+            Font::load_hex(&path)
+        } else if path.ends_with(".bdf") {
+            Font::load_bdf(&path)
+        } else if path.ends_with(".psf") || path.ends_with(".psfu") {
+            Font::load_psf(&path)
+        } else {
+            Font::load_ttf(&path, size)
+        };
+        match font {
+            // 内置 ASCII 字体作为后备
+            Ok(font) => Font::set_default(font.with_fallback(Font::basic())),
+            Err(e) => println!("Failed to load font {}: {:?}", path, e),
+        }
+    }
+
+    let mut window = Window::new("ToGUI Font Demo", 800, 600);
+
+    let mut container = Container::new(0.0, 0.0, 800.0, 600.0);
+    container.add_child(Text::new(0.0, 0.0, "Hello, world!"));
+    container.add_child(Text::new(0.0, 0.0, "你好，世界！"));
+    container.add_child(Text::new(0.0, 0.0, "Crème brûlée £5"));
+    container.add_child(Text::new(0.0, 0.0, "안녕하세요"));
+    container.add_child(Button::new(0.0, 0.0, 200.0, 40.0, "确定 OK"));
+
+    window.add_widget(container);
+    window.run();
+}
@@ -0,0 +1,31 @@
+use togui::{Window, Grid, GridCell, Track, Text, Button, ComboBox, Alignment};
+
+fn main() {
+    let mut window = Window::new("ToGUI Grid Demo", 800, 600);
+
+    // 标签列按内容宽度，输入列占满剩余空间
+    let mut grid = Grid::new(0.0, 0.0, 800.0, 600.0)
+        .with_columns(vec![Track::Auto, Track::Fr(1.0), Track::Px(120.0)])
+        .with_rows(vec![Track::Auto, Track::Auto, Track::Auto, Track::Fr(1.0), Track::Auto])
+        .with_column_gap(12.0)
+        .with_row_gap(8.0);
+
+    let label = |row| GridCell::new(row, 0).with_align_y(Alignment::Center);
+    grid.add_child(Text::new(0.0, 0.0, "Name"), label(0));
+    grid.add_child(ComboBox::new(0.0, 0.0, 240.0, 28.0, &["Alice", "Bob"]).with_editable(true), GridCell::new(0, 1).with_span(1, 2));
+    grid.add_child(Text::new(0.0, 0.0, "Fruit"), label(1));
+    grid.add_child(ComboBox::new(0.0, 0.0, 240.0, 28.0, &["Apple", "Banana", "Cherry"]), GridCell::new(1, 1));
+    grid.add_child(Button::new(0.0, 0.0, 120.0, 28.0, "Reset"), GridCell::new(1, 2));
+    grid.add_child(Text::new(0.0, 0.0, "Notes"), label(2));
+    grid.add_child(
+        Text::new(0.0, 0.0, "Labels line up in the first column no matter how long the fields are.").with_wrap(true),
+        GridCell::new(2, 1).with_span(1, 2),
+    );
+
+    // 按钮跨越所有列并靠右对齐
+    let submit = Button::new(0.0, 0.0, 200.0, 40.0, "Submit").on_click(|| println!("Submitted"));
+    grid.add_child(submit, GridCell::new(4, 0).with_span(1, 3).with_align_x(Alignment::End));
+
+    window.add_root(grid);
+    window.run();
+}
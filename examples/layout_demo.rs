@@ -1,6 +1,6 @@
 use togui::{
-    Window, Button, Container, Text,
-    Direction, Padding, Alignment
+    Window, Button, Container, Text, Color,
+    Direction, Padding, Alignment, FlexItem, AlignSelf
 };
 
 fn main() {
@@ -8,19 +8,20 @@ fn main() {
     
     // 创建主容器
     let mut main_container = Container::new(10.0, 10.0, 780.0, 580.0)
-        .with_direction(Direction::Vertical)
         .with_padding(Padding::all(20.0))
         .with_spacing(10.0);
+    main_container.with_direction(Direction::Vertical);
 
     // 添加标题文本
     let title = Text::new(0.0, 0.0, "Layout Demo")
-        .with_color([255, 255, 255, 255]);
+        .with_color(Color::rgb(255, 255, 255));
     main_container.add_child(title);
 
     // 创建水平按钮容器
     let mut button_container = Container::new(0.0, 0.0, 740.0, 50.0)
+        .with_spacing(20.0);
+    button_container
         .with_direction(Direction::Horizontal)
-        .with_spacing(20.0)
         .with_alignment(Alignment::Center);
 
     // 添加三个按钮
@@ -31,18 +32,42 @@ fn main() {
     let button3 = Button::new(0.0, 0.0, 200.0, 50.0, "Button 3")
         .on_click(|| println!("Button 3 clicked!"));
 
+    // 中间的按钮占满剩余宽度，右侧按钮在空间不足时不收缩
     button_container.add_child(button1);
-    button_container.add_child(button2);
-    button_container.add_child(button3);
+    button_container.add_flex_child(button2, FlexItem::new().with_grow(1.0).with_min_width(120.0));
+    button_container.add_flex_child(button3, FlexItem::new().with_shrink(0.0));
 
     // 将按钮容器添加到主容器
     main_container.add_widget(button_container);
 
     // 添加一些说明文本
     let description = Text::new(0.0, 0.0, "This is a demo of the layout system.")
-        .with_color([200, 200, 200, 255]);
+        .with_color(Color::rgb(200, 200, 200));
     main_container.add_child(description);
 
+    // 标签云：超出宽度时换行，每行居中
+    let mut tags = Container::new(0.0, 0.0, 740.0, 0.0)
+        .with_spacing(8.0)
+        .with_wrap(true)
+        .with_line_spacing(6.0)
+        .with_line_alignment(Alignment::Center);
+    tags.with_direction(Direction::Horizontal);
+    for tag in ["rust", "gui", "layout", "flexbox", "grid", "wrap", "tag cloud", "toolbar", "overflow", "winit", "pixels"] {
+        tags.add_child(Button::new(0.0, 0.0, 90.0, 30.0, tag));
+    }
+    main_container.add_child(tags);
+
+    // 拉伸到容器宽度，并占满剩余高度
+    let footer = Button::new(0.0, 0.0, 200.0, 50.0, "Footer")
+        .on_click(|| println!("Footer clicked!"));
+    main_container.add_flex_child(
+        footer,
+        FlexItem::new()
+            .with_grow(1.0)
+            .with_max_height(120.0)
+            .with_align_self(AlignSelf::Stretch),
+    );
+
     window.add_widget(main_container);
     window.run();
 }
\ No newline at end of file
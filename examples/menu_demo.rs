@@ -0,0 +1,46 @@
+use togui::{Window, Button, Container, Menu, MenuItem, MenuBar, ContextMenu};
+
+fn main() {
+    let mut window = Window::new("ToGUI Menu Demo", 800, 600);
+
+    let menu_bar = MenuBar::new(0.0, 0.0, 800.0)
+        .with_menu(
+            Menu::new("&File")
+                .with_item(MenuItem::new("&New").with_accelerator("Ctrl+N").on_activate(|| println!("New")))
+                .with_item(MenuItem::new("&Open...").with_accelerator("Ctrl+O").on_activate(|| println!("Open")))
+                .with_submenu(
+                    Menu::new("Open &Recent")
+                        .with_item(MenuItem::new("demo.ui").on_activate(|| println!("Open demo.ui")))
+                        .with_item(MenuItem::new("layout.ui").on_activate(|| println!("Open layout.ui"))),
+                )
+                .with_separator()
+                .with_item(MenuItem::new("E&xit").on_activate(|| std::process::exit(0))),
+        )
+        .with_menu(
+            Menu::new("&Edit")
+                .with_item(MenuItem::new("&Undo").with_accelerator("Ctrl+Z").with_enabled(false))
+                .with_item(MenuItem::new("Cu&t").with_accelerator("Ctrl+X"))
+                .with_item(MenuItem::new("&Copy").with_accelerator("Ctrl+C"))
+                .with_item(MenuItem::new("&Paste").with_accelerator("Ctrl+V")),
+        )
+        .with_menu(
+            Menu::new("&View")
+                .with_item(
+                    MenuItem::new("Show &Toolbar")
+                        .with_checked(true)
+                        .on_toggle(|checked| println!("Toolbar: {}", checked)),
+                )
+                .with_item(MenuItem::new("Show &Status Bar").with_checked(false)),
+        );
+
+    let mut content = Container::new(0.0, 30.0, 800.0, 570.0);
+    let button = Button::new(0.0, 0.0, 200.0, 50.0, "Right click me");
+    let context_menu = Menu::new("Context")
+        .with_item(MenuItem::new("&Rename").on_activate(|| println!("Rename")))
+        .with_item(MenuItem::new("&Delete").on_activate(|| println!("Delete")));
+    content.add_child(ContextMenu::new(button, context_menu));
+
+    window.add_widget(content);
+    window.add_widget(menu_bar);
+    window.run();
+}
@@ -0,0 +1,42 @@
+use togui::{Window, Color, Container, Button, Text, FlexItem, Breakpoint, Direction, Alignment, Length, Padding};
+
+fn main() {
+    let mut window = Window::new("ToGUI Responsive Demo", 800, 600);
+
+    let mut main_container = Container::new(0.0, 0.0, 800.0, 600.0);
+    main_container.with_direction(Direction::Vertical);
+
+    main_container.add_child(Text::new(0.0, 0.0, "Resize the window to see the layout change").with_color(Color::rgb(255, 220, 120)));
+
+    // 宽窗口中侧栏和内容左右排列，窗口窄于 600 像素时改为上下排列
+    let mut body = Container::new(0.0, 0.0, 0.0, 0.0)
+        .with_spacing(10.0)
+        .with_breakpoint(Breakpoint::below(600.0).with_direction(Direction::Vertical));
+    body.with_direction(Direction::Horizontal);
+
+    // 侧栏占内容区域宽度的 30%
+    body.add_flex_child(
+        Button::new(0.0, 0.0, 200.0, 50.0, "Sidebar"),
+        FlexItem::new().with_width(Length::Percent(30.0)),
+    );
+    body.add_flex_child(
+        Text::new(0.0, 0.0, "The content area takes the remaining space. Its text wraps to the width it is given.").with_wrap(true),
+        FlexItem::new().with_grow(1.0).with_margin(Padding::all(8.0)),
+    );
+    main_container.add_flex_child(body, FlexItem::new().with_grow(1.0));
+
+    // 底部按钮之间留出外边距，窗口很窄时换行并居中
+    let mut buttons = Container::new(0.0, 0.0, 0.0, 0.0)
+        .with_breakpoint(Breakpoint::below(400.0).with_wrap(true).with_alignment(Alignment::Center));
+    buttons.with_direction(Direction::Horizontal);
+    for label in ["Back", "Next", "Finish"] {
+        buttons.add_flex_child(
+            Button::new(0.0, 0.0, 160.0, 40.0, label).on_click(move || println!("{} clicked", label)),
+            FlexItem::new().with_margin(Padding::all(4.0)),
+        );
+    }
+    main_container.add_child(buttons);
+
+    window.add_root(main_container);
+    window.run();
+}
@@ -0,0 +1,38 @@
+use togui::{Window, Color, RichText, TextSpan, SpanStyle, Container};
+
+fn main() {
+    let mut window = Window::new("ToGUI Rich Text Demo", 800, 600);
+
+    let mut container = Container::new(0.0, 0.0, 500.0, 600.0);
+
+    // 使用标记语法
+    container.add_child(
+        RichText::from_markup(
+            0.0,
+            0.0,
+            "Mix [b]bold[/b], [i]italic[/i], [u]underline[/u] and [s]strikethrough[/s] with \
+             [color=#ff8800]colors[/color], [bg=#335577]highlights[/bg] and [size=16]bigger text[/size]. \
+             Visit the [link=https://github.com/yahao333/togui]project page[/link] for more.",
+        )
+        .with_wrap(true)
+        .on_link(|url| println!("Open link: {}", url)),
+    );
+
+    // 直接构造片段
+    let spans = vec![
+        TextSpan::new("Spans can also be built in code: ", SpanStyle::default()),
+        TextSpan::new(
+            "styled",
+            SpanStyle {
+                color: Some(Color::rgb(120, 220, 120)),
+                bold: true,
+                underline: true,
+                ..SpanStyle::default()
+            },
+        ),
+    ];
+    container.add_child(RichText::new(0.0, 0.0, spans).with_wrap(true));
+
+    window.add_widget(container);
+    window.run();
+}
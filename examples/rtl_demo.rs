@@ -0,0 +1,55 @@
+use togui::{Window, Button, ComboBox, Container, FlexItem, Image, LayoutDirection, Menu, MenuItem, MenuBar, Text, Direction, Alignment, Padding};
+
+fn main() {
+    let mut window = Window::new("ToGUI RTL Demo", 800, 600);
+    // 整个窗口从右到左：菜单从右边开始，容器从右向左排列，Start 对齐靠右
+    window.set_layout_direction(LayoutDirection::Rtl);
+
+    let menu_bar = MenuBar::new(0.0, 0.0, 800.0)
+        .with_menu(
+            Menu::new("&File")
+                .with_item(MenuItem::new("&New").with_accelerator("Ctrl+N"))
+                .with_submenu(
+                    Menu::new("Open &Recent")
+                        .with_item(MenuItem::new("demo.ui"))
+                        .with_item(MenuItem::new("layout.ui")),
+                ),
+        )
+        .with_menu(Menu::new("&Edit").with_item(MenuItem::new("&Copy").with_accelerator("Ctrl+C")));
+
+    let mut main_container = Container::new(0.0, 0.0, 800.0, 600.0)
+        .with_padding(Padding { left: 10.0, right: 10.0, top: 34.0, bottom: 10.0 });
+    main_container.with_direction(Direction::Vertical);
+
+    main_container.add_child(Text::new(0.0, 0.0, "مرحبا بالعالم — the first button is on the right"));
+
+    let mut buttons = Container::new(0.0, 0.0, 0.0, 0.0).with_spacing(10.0);
+    buttons.with_direction(Direction::Horizontal);
+    for label in ["First", "Second", "Third"] {
+        buttons.add_child(Button::new(0.0, 0.0, 120.0, 40.0, label).on_click(move || println!("{} clicked", label)));
+    }
+    main_container.add_child(buttons);
+
+    // 有方向的图标随布局方向镜像
+    main_container.add_child(
+        Image::from_file(0.0, 0.0, "examples/assets/logo.ppm")
+            .with_size(64.0, 64.0)
+            .with_directional(true),
+    );
+
+    main_container.add_child(ComboBox::new(0.0, 0.0, 240.0, 28.0, &["Apple", "Banana", "Cherry"]));
+
+    // 子树可以单独设置方向，例如从右到左界面中的数字键盘或代码片段
+    let mut ltr = Container::new(0.0, 0.0, 0.0, 0.0)
+        .with_spacing(10.0)
+        .with_layout_direction(LayoutDirection::Ltr);
+    ltr.with_direction(Direction::Horizontal).with_alignment(Alignment::Start);
+    for label in ["1", "2", "3"] {
+        ltr.add_child(Button::new(0.0, 0.0, 60.0, 40.0, label));
+    }
+    main_container.add_flex_child(ltr, FlexItem::new());
+
+    window.add_root(main_container);
+    window.add_widget(menu_bar);
+    window.run();
+}
@@ -0,0 +1,33 @@
+use togui::{Window, Color, Button, Direction, SplitPane, Splitter, Text};
+
+fn main() {
+    let mut window = Window::new("ToGUI Splitter Demo", 800, 600);
+
+    // 拖动分隔条调整窗格大小，双击分隔条折叠或展开侧栏和控制台，
+    // 比例保存在临时目录中，下次启动时恢复
+    let state_file = std::env::temp_dir().join("togui_splitter_demo.split");
+
+    let mut editor = Splitter::new(0.0, 0.0, 0.0, 0.0)
+        .with_direction(Direction::Vertical)
+        .with_state_file(state_file.with_extension("editor"));
+    editor.add_child(
+        Text::new(0.0, 0.0, "Editor: drag the dividers, double-click them to collapse a pane").with_wrap(true),
+        SplitPane::new().with_ratio(0.7).with_min_size(100.0),
+    );
+    editor.add_child(
+        Text::new(0.0, 0.0, "Console").with_color(Color::rgb(180, 220, 180)),
+        SplitPane::new().with_ratio(0.3).with_min_size(60.0).with_collapsible(true),
+    );
+
+    let mut main = Splitter::new(0.0, 0.0, 800.0, 600.0)
+        .with_state_file(state_file)
+        .on_resize(|ratios| println!("Split ratios: {:?}", ratios));
+    main.add_child(
+        Button::new(0.0, 0.0, 200.0, 50.0, "Sidebar"),
+        SplitPane::new().with_ratio(0.25).with_min_size(120.0).with_max_size(400.0).with_collapsible(true),
+    );
+    main.add_child(editor, SplitPane::new().with_ratio(0.75).with_min_size(200.0));
+
+    window.add_root(main);
+    window.run();
+}
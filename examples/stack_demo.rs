@@ -0,0 +1,38 @@
+use togui::{Window, Color, Stack, StackItem, Anchor, Button, Text, Image};
+
+fn main() {
+    let mut window = Window::new("ToGUI Stack Demo", 800, 600);
+
+    let mut stack = Stack::new(0.0, 0.0, 800.0, 600.0);
+
+    // 铺满整个窗口的背景图
+    stack.add_child(
+        Image::from_file(0.0, 0.0, "examples/assets/logo.ppm"),
+        StackItem::new(Anchor::Center).with_width_percent(100.0).with_height_percent(100.0),
+    );
+
+    // HUD：左上角的状态文本，位于背景之上
+    stack.add_child(
+        Text::new(0.0, 0.0, "HP 100  MP 40").with_color(Color::rgb(255, 220, 120)),
+        StackItem::new(Anchor::TopLeft).with_offset(16.0, 16.0).with_z_index(1),
+    );
+
+    // 图标按钮和它右上角的角标，角标的 z_index 更大，绘制和点击都优先
+    stack.add_child(
+        Button::new(0.0, 0.0, 200.0, 50.0, "Inbox").on_click(|| println!("Inbox clicked")),
+        StackItem::new(Anchor::Top).with_offset(0.0, 40.0).with_z_index(1),
+    );
+    stack.add_child(
+        Text::new(0.0, 0.0, "3").with_color(Color::rgb(255, 80, 80)),
+        StackItem::new(Anchor::Top).with_offset(100.0, 36.0).with_z_index(2),
+    );
+
+    // 右下角的悬浮按钮
+    stack.add_child(
+        Button::new(0.0, 0.0, 200.0, 50.0, "+").on_click(|| println!("Floating action")),
+        StackItem::new(Anchor::BottomRight).with_offset(-24.0, -24.0).with_z_index(3),
+    );
+
+    window.add_root(stack);
+    window.run();
+}
@@ -0,0 +1,27 @@
+use togui::{Window, Text, Button, Container, Alignment};
+
+fn main() {
+    let mut window = Window::new("ToGUI Text Layout Demo", 800, 600);
+
+    let mut container = Container::new(0.0, 0.0, 400.0, 600.0);
+
+    container.add_child(
+        Text::new(0.0, 0.0, "Long paragraphs wrap at word boundaries when they do not fit in the container width.")
+            .with_wrap(true),
+    );
+    container.add_child(
+        Text::new(0.0, 0.0, "Centered text\nwith explicit newlines\nand extra line spacing")
+            .with_align(Alignment::Center)
+            .with_line_spacing(1.5),
+    );
+    container.add_child(
+        Text::new(0.0, 0.0, "Only two lines of this long text are shown, the rest is cut off with an ellipsis.")
+            .with_wrap(true)
+            .with_max_lines(2)
+            .with_ellipsis(true),
+    );
+    container.add_child(Button::new(0.0, 0.0, 120.0, 40.0, "A button label that is far too long"));
+
+    window.add_widget(container);
+    window.run();
+}
@@ -0,0 +1,35 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use togui::{Window, Button, ComboBox, Container, Direction, Menu, MenuBar, MenuItem, Padding, RichText, Text, Theme, Tooltip};
+
+fn main() {
+    let mut window = Window::new("ToGUI Theme Demo", 800, 600);
+    window.set_theme(Theme::light());
+    let themes = window.get_theme_handle();
+
+    let menu_bar = MenuBar::new(0.0, 0.0, 800.0).with_menu(
+        Menu::new("&View")
+            .with_item(MenuItem::new("&Zoom In").with_accelerator("Ctrl+="))
+            .with_item(MenuItem::new("Zoom &Out").with_accelerator("Ctrl+-").with_enabled(false)),
+    );
+
+    let mut main_container = Container::new(0.0, 0.0, 800.0, 600.0)
+        .with_spacing(10.0)
+        .with_padding(Padding { left: 10.0, right: 10.0, top: 34.0, bottom: 10.0 });
+    main_container.with_direction(Direction::Vertical);
+
+    main_container.add_child(Text::new(0.0, 0.0, "Every built-in widget reads its colors from the current theme"));
+    main_container.add_child(RichText::from_markup(0.0, 0.0, "Plain text, [b]bold[/b] and a [link=https://github.com/yahao333/togui]link[/link]"));
+    main_container.add_child(ComboBox::new(0.0, 0.0, 240.0, 28.0, &["Light", "Dark"]));
+
+    // 在浅色和深色主题之间切换，新主题在下一次事件循环时生效
+    let dark = AtomicBool::new(false);
+    let toggle = Button::new(0.0, 0.0, 200.0, 50.0, "Toggle Theme").on_click(move || {
+        let dark = !dark.fetch_xor(true, Ordering::SeqCst);
+        themes.set_theme(if dark { Theme::dark() } else { Theme::light() });
+    });
+    main_container.add_child(Tooltip::new(toggle, "Switches between the light and dark themes"));
+
+    window.add_root(main_container);
+    window.add_widget(menu_bar);
+    window.run();
+}
@@ -1,5 +1,5 @@
 use togui::Window;
-use togui::ui::{UiLoader, parse_ui};
+use togui::ui::{UiLoader, parse_style, parse_ui};
 use togui::debug_log;
 use togui::info_log;
 
@@ -14,11 +14,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     debug_log!("Loading UI file");
     let content = loader.load("examples/assets/demo.ui").unwrap();
     let container = parse_ui(&content).expect("Failed to parse UI");
-    window.add_widget(container);
+    window.add_root(container);
+
+    // 加载样式表，修改 .style 文件后只重新加载样式
+    debug_log!("Loading style file");
+    let style = loader.load_style("examples/assets/demo.style").unwrap();
+    window.set_stylesheet(parse_style(&style).expect("Failed to parse stylesheet"));
 
     // 启动热重载
     debug_log!("Starting hot reload watcher");
-    loader.start_watching();
+    if let Err(e) = loader.start_watching() {
+        println!("Failed to start watcher: {:?}", e);
+    }
 
     info_log!("Running main window");
     window.run();
@@ -0,0 +1,114 @@
+use std::f32::consts::PI;
+use winit::event::WindowEvent;
+use togui::{Window, Color, FillRule, LineCap, LineJoin, Path, Rect, Renderer, StrokeStyle, Widget};
+
+// 用矢量路径自己绘制的组件：折线图、饼图和几个图标
+struct Canvas {
+    rect: Rect,
+}
+
+impl Canvas {
+    fn draw_chart(&self, renderer: &mut Renderer, x: f32, y: f32, width: f32, height: f32) {
+        let values = [0.2, 0.45, 0.35, 0.7, 0.55, 0.9, 0.75];
+        let point = |i: usize| (x + width * i as f32 / (values.len() - 1) as f32, y + height * (1.0 - values[i]));
+
+        // 虚线网格
+        let grid = StrokeStyle::new(1.0).with_dash(&[4.0, 4.0], 0.0);
+        for row in 0..=4 {
+            let line_y = y + height * row as f32 / 4.0;
+            let mut line = Path::new();
+            line.move_to(x, line_y).line_to(x + width, line_y);
+            renderer.stroke_path(&line, Color::rgb(90, 90, 90), &grid);
+        }
+
+        // 折线下方的半透明区域
+        let mut area = Path::new();
+        area.move_to(x, y + height);
+        for i in 0..values.len() {
+            let (px, py) = point(i);
+            area.line_to(px, py);
+        }
+        area.line_to(x + width, y + height).close();
+        renderer.fill_path(&area, Color::rgba(110, 170, 255, 70), FillRule::NonZero);
+
+        let mut line = Path::new();
+        for i in 0..values.len() {
+            let (px, py) = point(i);
+            line.line_to(px, py);
+        }
+        let style = StrokeStyle::new(3.0).with_join(LineJoin::Round).with_cap(LineCap::Round);
+        renderer.stroke_path(&line, Color::rgb(110, 170, 255), &style);
+    }
+
+    fn draw_pie(&self, renderer: &mut Renderer, cx: f32, cy: f32, radius: f32) {
+        let slices = [(0.45, Color::rgb(230, 120, 90)), (0.3, Color::rgb(120, 200, 140)), (0.25, Color::rgb(240, 200, 90))];
+        let mut angle = -PI / 2.0;
+        for (share, color) in slices {
+            let end = angle + share * 2.0 * PI;
+            let mut slice = Path::new();
+            slice.move_to(cx, cy).arc(cx, cy, radius, angle, end).close();
+            renderer.fill_path(&slice, color, FillRule::NonZero);
+            renderer.stroke_path(&slice, Color::rgb(40, 40, 40), &StrokeStyle::new(2.0).with_join(LineJoin::Bevel));
+            angle = end;
+        }
+    }
+
+    fn draw_icons(&self, renderer: &mut Renderer, x: f32, y: f32) {
+        // 自交的五角星：非零规则填满中心，奇偶规则留出中间的五边形
+        for (offset, rule) in [(0.0, FillRule::NonZero), (90.0, FillRule::EvenOdd)] {
+            let (cx, cy) = (x + 35.0 + offset, y + 35.0);
+            let mut star = Path::new();
+            for i in 0..5 {
+                let angle = -PI / 2.0 + i as f32 * 4.0 * PI / 5.0;
+                star.line_to(cx + angle.cos() * 32.0, cy + angle.sin() * 32.0);
+            }
+            star.close();
+            renderer.fill_path(&star, Color::rgb(255, 210, 80), rule);
+        }
+
+        // 用三次贝塞尔曲线画的心形
+        let (cx, top) = (x + 215.0, y + 15.0);
+        let mut heart = Path::new();
+        heart
+            .move_to(cx, top + 12.0)
+            .cubic_to(cx - 6.0, top - 6.0, cx - 36.0, top, cx - 30.0, top + 24.0)
+            .cubic_to(cx - 26.0, top + 40.0, cx - 8.0, top + 48.0, cx, top + 58.0)
+            .cubic_to(cx + 8.0, top + 48.0, cx + 26.0, top + 40.0, cx + 30.0, top + 24.0)
+            .cubic_to(cx + 36.0, top, cx + 6.0, top - 6.0, cx, top + 12.0)
+            .close();
+        renderer.fill_path(&heart, Color::rgb(230, 80, 100), FillRule::NonZero);
+
+        // 二次贝塞尔曲线和三种端点
+        for (i, cap) in [LineCap::Butt, LineCap::Square, LineCap::Round].into_iter().enumerate() {
+            let left = x + 280.0 + i as f32 * 40.0;
+            let mut curve = Path::new();
+            curve.move_to(left, y + 60.0).quad_to(left + 15.0, y, left + 25.0, y + 60.0);
+            renderer.stroke_path(&curve, Color::rgb(200, 200, 200), &StrokeStyle::new(6.0).with_cap(cap));
+        }
+    }
+}
+
+impl Widget for Canvas {
+    fn draw(&self, renderer: &mut Renderer) {
+        let Rect { x, y, .. } = self.rect;
+        self.draw_chart(renderer, x + 30.0, y + 30.0, 420.0, 220.0);
+        self.draw_pie(renderer, x + 600.0, y + 140.0, 100.0);
+        self.draw_icons(renderer, x + 30.0, y + 320.0);
+    }
+
+    fn handle_event(&mut self, _event: &WindowEvent) {}
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+}
+
+fn main() {
+    let mut window = Window::new("ToGUI Vector Demo", 800, 600);
+    window.add_root(Canvas { rect: Rect { x: 0.0, y: 0.0, width: 800.0, height: 600.0 } });
+    window.run();
+}
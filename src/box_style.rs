@@ -0,0 +1,140 @@
+use crate::color::Color;
+use crate::theme::StateStyle;
+
+// 四个角的圆角半径，与 CSS border-radius 的顺序相同：左上、右上、右下、左下
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    pub const fn all(radius: f32) -> Self {
+        Self {
+            top_left: radius,
+            top_right: radius,
+            bottom_right: radius,
+            bottom_left: radius,
+        }
+    }
+
+    pub const fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self { top_left, top_right, bottom_right, bottom_left }
+    }
+
+    // 每个角加上 amount，不小于 0。用于边框内侧和阴影的扩展
+    pub fn grow(self, amount: f32) -> Self {
+        let grow = |radius: f32| (radius + amount).max(0.0);
+        Self::new(grow(self.top_left), grow(self.top_right), grow(self.bottom_right), grow(self.bottom_left))
+    }
+
+    // 与 CSS 相同：同一条边上两个半径之和超过边长时按比例缩小所有半径
+    pub fn fit(self, width: f32, height: f32) -> Self {
+        let ratio = |side: f32, sum: f32| if sum > side && sum > 0.0 { side.max(0.0) / sum } else { 1.0 };
+        let scale = ratio(width, self.top_left + self.top_right)
+            .min(ratio(width, self.bottom_left + self.bottom_right))
+            .min(ratio(height, self.top_left + self.bottom_left))
+            .min(ratio(height, self.top_right + self.bottom_right));
+        let scale = |radius: f32| radius.max(0.0) * scale;
+        Self::new(scale(self.top_left), scale(self.top_right), scale(self.bottom_right), scale(self.bottom_left))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Border {
+    pub width: f32,
+    pub color: Color,
+}
+
+impl Border {
+    pub const fn new(width: f32, color: Color) -> Self {
+        Self { width, color }
+    }
+}
+
+// 画在盒子外面的阴影：offset 为偏移，blur 为模糊半径，spread 向外扩展（负数收缩）阴影的形状
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoxShadow {
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur: f32,
+    pub spread: f32,
+    pub color: Color,
+}
+
+impl BoxShadow {
+    pub const fn new(offset_x: f32, offset_y: f32, blur: f32, color: Color) -> Self {
+        Self { offset_x, offset_y, blur, spread: 0.0, color }
+    }
+
+    pub const fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    // 完全透明的阴影不画，样式表中的 box-shadow: none 即是这样的阴影
+    pub fn is_visible(&self) -> bool {
+        self.color.a > 0
+    }
+}
+
+// 组件内容后面的背景：先画阴影，再填充背景，最后画边框，都带抗锯齿的圆角
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BoxStyle {
+    pub background: Option<Color>,
+    pub border: Option<Border>,
+    pub corner_radii: CornerRadii,
+    // 按顺序绘制，后面的阴影盖住前面的
+    pub shadows: Vec<BoxShadow>,
+}
+
+impl BoxStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    pub fn with_border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some(Border::new(width, color));
+        self
+    }
+
+    pub fn with_corner_radius(mut self, radius: f32) -> Self {
+        self.corner_radii = CornerRadii::all(radius);
+        self
+    }
+
+    pub fn with_corner_radii(mut self, radii: CornerRadii) -> Self {
+        self.corner_radii = radii;
+        self
+    }
+
+    pub fn with_shadow(mut self, shadow: BoxShadow) -> Self {
+        self.shadows.push(shadow);
+        self
+    }
+
+    // 没有任何需要绘制的内容
+    pub fn is_empty(&self) -> bool {
+        self.background.is_none()
+            && self.border.is_none_or(|border| border.width <= 0.0)
+            && !self.shadows.iter().any(BoxShadow::is_visible)
+    }
+}
+
+// 主题中的状态样式：背景和 1 像素的边框，没有圆角和阴影
+impl From<StateStyle> for BoxStyle {
+    fn from(style: StateStyle) -> Self {
+        Self {
+            background: Some(style.background),
+            border: style.border.map(|color| Border::new(1.0, color)),
+            ..Self::default()
+        }
+    }
+}
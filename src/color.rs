@@ -0,0 +1,395 @@
+// 8 位 sRGB 颜色，alpha 为 255 时不透明。
+// 各分量按 sRGB 编码存储，与帧缓冲区的像素格式一致；需要物理上正确的混合时先转换到线性空间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self { a, ..self }
+    }
+
+    pub const fn to_array(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    // #RGB、#RGBA、#RRGGBB、#RRGGBBAA，开头的 # 可以省略
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        // from_str_radix 接受开头的正号，这里只允许十六进制数字
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        match hex.len() {
+            3 => Some(Self::rgb(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+            4 => Some(Self::rgba(digit(0)? * 17, digit(1)? * 17, digit(2)? * 17, digit(3)? * 17)),
+            6 => Some(Self::rgb(byte(0)?, byte(2)?, byte(4)?)),
+            8 => Some(Self::rgba(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+            _ => None,
+        }
+    }
+
+    // 不透明时为 #rrggbb，否则为 #rrggbbaa
+    pub fn to_hex(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    // 解析 .ui、.style 文件和富文本标记中的颜色：
+    // #RRGGBB 等十六进制形式、颜色名、rgb(255, 128, 0)、rgba(255, 128, 0, 0.5)、
+    // hsl(210, 50%, 40%) 和 hsla(210, 50%, 40%, 0.5)
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.starts_with('#') {
+            return Self::from_hex(value);
+        }
+        if let Some((function, arguments)) = value.strip_suffix(')').and_then(|value| value.split_once('(')) {
+            return parse_function(&function.trim().to_ascii_lowercase(), arguments);
+        }
+        Self::named(value)
+    }
+
+    // CSS 中的常用颜色名，不区分大小写
+    pub fn named(name: &str) -> Option<Self> {
+        let color = match name.to_ascii_lowercase().as_str() {
+            "transparent" => Self::TRANSPARENT,
+            "black" => Self::BLACK,
+            "white" => Self::WHITE,
+            "red" => Self::rgb(255, 0, 0),
+            "green" => Self::rgb(0, 128, 0),
+            "lime" => Self::rgb(0, 255, 0),
+            "blue" => Self::rgb(0, 0, 255),
+            "navy" => Self::rgb(0, 0, 128),
+            "yellow" => Self::rgb(255, 255, 0),
+            "orange" => Self::rgb(255, 165, 0),
+            "purple" => Self::rgb(128, 0, 128),
+            "magenta" | "fuchsia" => Self::rgb(255, 0, 255),
+            "cyan" | "aqua" => Self::rgb(0, 255, 255),
+            "teal" => Self::rgb(0, 128, 128),
+            "olive" => Self::rgb(128, 128, 0),
+            "maroon" => Self::rgb(128, 0, 0),
+            "brown" => Self::rgb(165, 42, 42),
+            "pink" => Self::rgb(255, 192, 203),
+            "silver" => Self::rgb(192, 192, 192),
+            "gray" | "grey" => Self::rgb(128, 128, 128),
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    // 色相 0..360 度，饱和度和亮度 0..1
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        from_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    // 返回 (色相, 饱和度, 亮度)，忽略 alpha
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (max, min, hue) = self.hue();
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+        (hue, saturation, lightness)
+    }
+
+    // 色相 0..360 度，饱和度和明度 0..1
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let value = value.clamp(0.0, 1.0);
+        let chroma = value * saturation.clamp(0.0, 1.0);
+        from_chroma(hue, chroma, value - chroma)
+    }
+
+    // 返回 (色相, 饱和度, 明度)，忽略 alpha
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (max, min, hue) = self.hue();
+        let saturation = if max == 0.0 { 0.0 } else { (max - min) / max };
+        (hue, saturation, max)
+    }
+
+    // RGB 分量的最大值、最小值（0..1）和色相
+    fn hue(self) -> (f32, f32, f32) {
+        let [r, g, b] = [self.r, self.g, self.b].map(|channel| channel as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let hue = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        (max, min, hue)
+    }
+
+    // 在 HSL 空间中提高亮度，amount 为 0..1 的绝对值，例如 0.1 表示亮度加 10%
+    pub fn lighten(self, amount: f32) -> Self {
+        let (hue, saturation, lightness) = self.to_hsl();
+        Self::from_hsl(hue, saturation, lightness + amount).with_alpha(self.a)
+    }
+
+    pub fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    // 按 t（0..1）在两种颜色之间线性插值，t 为 0 时是 self，为 1 时是 other
+    pub fn mix(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let lerp = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+        Self::rgba(lerp(self.r, other.r), lerp(self.g, other.g), lerp(self.b, other.b), lerp(self.a, other.a))
+    }
+
+    // 预乘 alpha：颜色分量乘以 alpha，用于图像合成
+    pub fn premultiplied(self) -> Self {
+        let multiply = |channel: u8| ((channel as u32 * self.a as u32 + 127) / 255) as u8;
+        Self::rgba(multiply(self.r), multiply(self.g), multiply(self.b), self.a)
+    }
+
+    // premultiplied 的逆运算，完全透明时颜色分量为 0
+    pub fn unpremultiplied(self) -> Self {
+        if self.a == 0 {
+            return Self::TRANSPARENT;
+        }
+        let divide = |channel: u8| ((channel as u32 * 255 + self.a as u32 / 2) / self.a as u32).min(255) as u8;
+        Self::rgba(divide(self.r), divide(self.g), divide(self.b), self.a)
+    }
+
+    // 线性空间中的 [r, g, b, a]，各分量 0..1，alpha 本身是线性的
+    pub fn to_linear(self) -> [f32; 4] {
+        [
+            srgb_to_linear(self.r),
+            srgb_to_linear(self.g),
+            srgb_to_linear(self.b),
+            self.a as f32 / 255.0,
+        ]
+    }
+
+    pub fn from_linear(linear: [f32; 4]) -> Self {
+        Self::rgba(
+            linear_to_srgb(linear[0]),
+            linear_to_srgb(linear[1]),
+            linear_to_srgb(linear[2]),
+            (linear[3].clamp(0.0, 1.0) * 255.0).round() as u8,
+        )
+    }
+
+    // WCAG 2 定义的相对亮度，0 为黑色，1 为白色
+    pub fn relative_luminance(self) -> f32 {
+        let [r, g, b, _] = self.to_linear();
+        0.2126 * r + 0.7152 * g + 0.0722 * b
+    }
+
+    // WCAG 2 对比度，范围 1..21，与参数顺序无关。
+    // 正文文本至少需要 4.5，大号文本至少需要 3
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let (a, b) = (self.relative_luminance(), other.relative_luminance());
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+}
+
+impl From<[u8; 4]> for Color {
+    fn from(rgba: [u8; 4]) -> Self {
+        Self::rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+    }
+}
+
+impl From<Color> for [u8; 4] {
+    fn from(color: Color) -> Self {
+        color.to_array()
+    }
+}
+
+fn srgb_to_linear(channel: u8) -> f32 {
+    let value = channel as f32 / 255.0;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u8 {
+    let value = value.clamp(0.0, 1.0);
+    let encoded = if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+// 由色度、最小分量和色相得到 RGB，HSL 和 HSV 共用
+fn from_chroma(hue: f32, chroma: f32, min: f32) -> Color {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |value: f32| ((value + min).clamp(0.0, 1.0) * 255.0).round() as u8;
+    Color::rgb(channel(r), channel(g), channel(b))
+}
+
+// rgb()、rgba()、hsl()、hsla() 的参数，用逗号或空格分隔
+fn parse_function(function: &str, arguments: &str) -> Option<Color> {
+    let arguments: Vec<&str> = arguments
+        .split(|c: char| c == ',' || c.is_whitespace() || c == '/')
+        .filter(|argument| !argument.is_empty())
+        .collect();
+    if !matches!(function, "rgb" | "rgba" | "hsl" | "hsla") {
+        return None;
+    }
+    let (channels, alpha) = match arguments.as_slice() {
+        [a, b, c] => ([*a, *b, *c], None),
+        [a, b, c, alpha] => ([*a, *b, *c], Some(*alpha)),
+        _ => return None,
+    };
+    let alpha = match alpha {
+        Some(alpha) => (parse_fraction(alpha)? * 255.0).round() as u8,
+        None => 255,
+    };
+    let color = if function.starts_with("rgb") {
+        let channel = |value: &str| match value.strip_suffix('%') {
+            Some(percent) => finite(percent).map(|percent| (percent.clamp(0.0, 100.0) * 2.55).round() as u8),
+            None => finite(value).map(|value| value.clamp(0.0, 255.0).round() as u8),
+        };
+        Color::rgb(channel(channels[0])?, channel(channels[1])?, channel(channels[2])?)
+    } else {
+        let hue = finite(channels[0].trim_end_matches("deg"))?;
+        Color::from_hsl(hue, parse_fraction(channels[1])?, parse_fraction(channels[2])?)
+    };
+    Some(color.with_alpha(alpha))
+}
+
+// 0.5 或 50%
+fn parse_fraction(value: &str) -> Option<f32> {
+    match value.strip_suffix('%') {
+        Some(percent) => finite(percent).map(|percent| percent / 100.0),
+        None => finite(value),
+    }
+    .map(|value| value.clamp(0.0, 1.0))
+}
+
+// 不接受 inf 和 NaN
+fn finite(value: &str) -> Option<f32> {
+    value.parse::<f32>().ok().filter(|value| value.is_finite())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_forms() {
+        assert_eq!(Color::from_hex("#f80"), Some(Color::rgb(255, 136, 0)));
+        assert_eq!(Color::from_hex("f808"), Some(Color::rgba(255, 136, 0, 136)));
+        assert_eq!(Color::from_hex("#466EAA"), Some(Color::rgb(70, 110, 170)));
+        assert_eq!(Color::from_hex("#466eaa80"), Some(Color::rgba(70, 110, 170, 128)));
+    }
+
+    #[test]
+    fn rejects_malformed_hex() {
+        for value in ["", "#", "#12", "#12345", "#1234567", "#ggg", "#+f+f+f", "+f+f+f", "#-1-1-1", "#é12", "##fff", "#fff "] {
+            assert_eq!(Color::from_hex(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        for color in [Color::rgb(0, 0, 0), Color::rgb(70, 110, 170), Color::rgba(1, 2, 3, 4), Color::TRANSPARENT] {
+            assert_eq!(Color::from_hex(&color.to_hex()), Some(color));
+        }
+        assert_eq!(Color::rgb(255, 136, 0).to_hex(), "#ff8800");
+        assert_eq!(Color::rgba(255, 136, 0, 128).to_hex(), "#ff880080");
+    }
+
+    #[test]
+    fn parses_names_and_functions() {
+        assert_eq!(Color::parse(" Orange "), Some(Color::rgb(255, 165, 0)));
+        assert_eq!(Color::parse("transparent"), Some(Color::TRANSPARENT));
+        assert_eq!(Color::parse("#fff"), Some(Color::WHITE));
+        assert_eq!(Color::parse("rgb(255, 128, 0)"), Some(Color::rgb(255, 128, 0)));
+        assert_eq!(Color::parse("RGB(100% 50% 0%)"), Some(Color::rgb(255, 128, 0)));
+        assert_eq!(Color::parse("rgba(300, -5, 0, 0.5)"), Some(Color::rgba(255, 0, 0, 128)));
+        assert_eq!(Color::parse("rgb(0 0 0 / 25%)"), Some(Color::rgba(0, 0, 0, 64)));
+        assert_eq!(Color::parse("hsl(0, 100%, 50%)"), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(Color::parse("hsl(120deg, 100%, 25%)"), Some(Color::rgb(0, 128, 0)));
+        assert_eq!(Color::parse("hsla(-120, 1, 0.5, 1)"), Some(Color::rgb(0, 0, 255)));
+    }
+
+    #[test]
+    fn rejects_malformed_colors() {
+        for value in [
+            "",
+            "nope",
+            "rgb(1, 2)",
+            "rgb(1, 2, 3, 4, 5)",
+            "rgb(a, b, c)",
+            "rgb(1, 2, 3",
+            "cmyk(1, 2, 3)",
+            "rgb(NaN, 0, 0)",
+            "rgb(inf, 0, 0)",
+            "rgba(0, 0, 0, nan)",
+            "hsl(inf, 50%, 50%)",
+        ] {
+            assert_eq!(Color::parse(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn hsl_and_hsv_round_trip() {
+        for r in (0..=255).step_by(51) {
+            for g in (0..=255).step_by(17) {
+                for b in (0..=255).step_by(85) {
+                    let color = Color::rgb(r, g, b);
+                    let (h, s, l) = color.to_hsl();
+                    assert_eq!(Color::from_hsl(h, s, l), color);
+                    let (h, s, v) = color.to_hsv();
+                    assert_eq!(Color::from_hsv(h, s, v), color);
+                }
+            }
+        }
+        let (hue, saturation, lightness) = Color::rgb(0, 0, 255).to_hsl();
+        assert_eq!((hue, saturation, lightness), (240.0, 1.0, 0.5));
+    }
+
+    #[test]
+    fn lightness_and_contrast() {
+        assert_eq!(Color::rgb(128, 128, 128).lighten(1.0), Color::WHITE);
+        assert_eq!(Color::rgb(128, 128, 128).darken(1.0), Color::BLACK);
+        assert!((Color::BLACK.contrast_ratio(Color::WHITE) - 21.0).abs() < 1e-3);
+        assert_eq!(Color::WHITE.contrast_ratio(Color::BLACK), Color::BLACK.contrast_ratio(Color::WHITE));
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use super::bitmap::{is_hex, BitmapFont, BitmapGlyph};
+use super::FontError;
+
+// 字形宽高、偏移和字体上下行高度的上限，防止异常的文件申请过大的内存或让坐标溢出
+const MAX_GLYPH_SIZE: i32 = 1024;
+
+// 正在解析的字形
+struct PendingGlyph {
+    encoding: Option<char>,
+    advance: Option<u32>,
+    // BBX: 宽、高、相对于原点的 x 偏移和 y 偏移（向上为正）
+    bbx: Option<(u32, u32, i32, i32)>,
+    rows: Vec<Vec<u8>>,
+}
+
+fn numbers<T: std::str::FromStr>(values: &[&str], count: usize, line: usize) -> Result<Vec<T>, FontError> {
+    if values.len() < count {
+        return Err(error(line));
+    }
+    values[..count]
+        .iter()
+        .map(|value| value.parse().map_err(|_| error(line)))
+        .collect()
+}
+
+fn error(line: usize) -> FontError {
+    FontError::ParseError(format!("invalid BDF font line {}", line))
+}
+
+// FONTBOUNDINGBOX 和 BBX 的宽、高、x 偏移、y 偏移，宽高不能为负，各项都不能超过 MAX_GLYPH_SIZE
+fn parse_box(values: &[&str], line: usize) -> Result<(u32, u32, i32, i32), FontError> {
+    let v: Vec<i32> = numbers(values, 4, line)?;
+    if v.iter().any(|value| value.abs() > MAX_GLYPH_SIZE) || v[0] < 0 || v[1] < 0 {
+        return Err(error(line));
+    }
+    Ok((v[0] as u32, v[1] as u32, v[2], v[3]))
+}
+
+fn metric(values: &[&str], line: usize) -> Result<u32, FontError> {
+    let value: u32 = numbers(values, 1, line)?[0];
+    if value > MAX_GLYPH_SIZE as u32 {
+        return Err(error(line));
+    }
+    Ok(value)
+}
+
+// X11 的 BDF 文本格式，支持每个字形独立的 BBX 和 DWIDTH
+pub(super) fn parse(content: &str) -> Result<BitmapFont, FontError> {
+    let mut bounding_box: Option<(u32, u32, i32, i32)> = None;
+    let mut font_ascent: Option<u32> = None;
+    let mut font_descent: Option<u32> = None;
+    let mut default_advance: Option<u32> = None;
+    let mut pending: Option<PendingGlyph> = None;
+    let mut in_bitmap = false;
+    let mut parsed = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if in_bitmap {
+            if line == "ENDCHAR" {
+                in_bitmap = false;
+                parsed.extend(pending.take());
+                continue;
+            }
+            let glyph = pending.as_mut().ok_or_else(|| error(number))?;
+            if !is_hex(line) {
+                return Err(error(number));
+            }
+            let row = (0..line.len() / 2)
+                .map(|i| u8::from_str_radix(&line[i * 2..i * 2 + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| error(number))?;
+            glyph.rows.push(row);
+            continue;
+        }
+
+        let mut parts = line.split_whitespace();
+        let keyword = parts.next().unwrap_or("");
+        let values: Vec<&str> = parts.collect();
+
+        match keyword {
+            "FONTBOUNDINGBOX" => bounding_box = Some(parse_box(&values, number)?),
+            "FONT_ASCENT" => font_ascent = Some(metric(&values, number)?),
+            "FONT_DESCENT" => font_descent = Some(metric(&values, number)?),
+            "STARTCHAR" => {
+                pending = Some(PendingGlyph {
+                    encoding: None,
+                    advance: None,
+                    bbx: None,
+                    rows: Vec::new(),
+                });
+            }
+            "ENCODING" => {
+                // ENCODING -1 表示非标准编码，这类字形没有对应的字符
+                let code: i64 = numbers(&values, 1, number)?[0];
+                if let Some(glyph) = pending.as_mut() {
+                    glyph.encoding = u32::try_from(code).ok().and_then(char::from_u32);
+                }
+            }
+            "DWIDTH" => {
+                let advance = numbers::<i32>(&values, 1, number)?[0].clamp(0, MAX_GLYPH_SIZE) as u32;
+                match pending.as_mut() {
+                    Some(glyph) => glyph.advance = Some(advance),
+                    None => default_advance = Some(advance),
+                }
+            }
+            "BBX" => {
+                let bbx = parse_box(&values, number)?;
+                let glyph = pending.as_mut().ok_or_else(|| error(number))?;
+                glyph.bbx = Some(bbx);
+            }
+            "BITMAP" => {
+                if pending.is_none() {
+                    return Err(error(number));
+                }
+                in_bitmap = true;
+            }
+            _ => {}
+        }
+    }
+
+    let bounding_box = bounding_box
+        .ok_or_else(|| FontError::ParseError("BDF font has no FONTBOUNDINGBOX".to_string()))?;
+    // 没有 FONT_ASCENT/FONT_DESCENT 属性时由字体包围盒推算
+    let ascent = font_ascent.unwrap_or((bounding_box.1 as i32 + bounding_box.3).max(0) as u32);
+    let descent = font_descent.unwrap_or((-bounding_box.3).max(0) as u32);
+
+    let mut glyphs = HashMap::new();
+    for glyph in parsed {
+        let Some(c) = glyph.encoding else {
+            continue;
+        };
+        let (width, height, x_offset, y_offset) = glyph.bbx.unwrap_or(bounding_box);
+        let row_bytes = (width as usize).div_ceil(8);
+        let size = row_bytes
+            .checked_mul(height as usize)
+            .ok_or_else(|| FontError::ParseError("BDF glyph too large".to_string()))?;
+        let mut bits = Vec::with_capacity(size);
+        for row in 0..height as usize {
+            let data = glyph.rows.get(row).map(Vec::as_slice).unwrap_or(&[]);
+            bits.extend((0..row_bytes).map(|i| data.get(i).copied().unwrap_or(0)));
+        }
+
+        glyphs.insert(
+            c,
+            BitmapGlyph {
+                width,
+                height,
+                x_offset,
+                // BBX 的 y 偏移是字形底部相对于基线的位置
+                y_offset: ascent as i32 - (height as i32 + y_offset),
+                advance: glyph.advance.or(default_advance).unwrap_or(width),
+                bits,
+            },
+        );
+    }
+
+    if glyphs.is_empty() {
+        return Err(FontError::ParseError("BDF font contains no glyphs".to_string()));
+    }
+
+    let cell_width = glyphs.get(&'0').map(|glyph| glyph.advance).unwrap_or(bounding_box.0);
+    Ok(BitmapFont::new(glyphs, ascent, descent, cell_width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: &str = "STARTFONT 2.1
+FONTBOUNDINGBOX 8 8 0 -2
+FONT_ASCENT 6
+FONT_DESCENT 2
+STARTCHAR A
+ENCODING 65
+DWIDTH 7 0
+BBX 4 3 1 0
+BITMAP
+F0
+90
+F0
+ENDCHAR
+STARTCHAR B
+ENCODING 66
+BBX 8 2 0 -2
+BITMAP
+ff
+ENDCHAR
+STARTCHAR unmapped
+ENCODING -1
+BITMAP
+ENDCHAR
+ENDFONT
+";
+
+    #[test]
+    fn parses_glyph_boxes_and_metrics() {
+        let font = parse(FONT).unwrap();
+        assert_eq!((font.ascent, font.descent, font.cell_width), (6, 2, 8));
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!((a.width, a.height, a.x_offset, a.y_offset, a.advance), (4, 3, 1, 3, 7));
+        assert_eq!(a.bits, vec![0xF0, 0x90, 0xF0]);
+
+        // 缺少的行补 0，没有 DWIDTH 时前进宽度等于字形宽度
+        let b = font.glyph('B').unwrap();
+        assert_eq!((b.y_offset, b.advance), (6, 8));
+        assert_eq!(b.bits, vec![0xFF, 0x00]);
+    }
+
+    #[test]
+    fn derives_ascent_from_bounding_box() {
+        let content = FONT.replace("FONT_ASCENT 6\nFONT_DESCENT 2\n", "");
+        let font = parse(&content).unwrap();
+        assert_eq!((font.ascent, font.descent), (6, 2));
+    }
+
+    #[test]
+    fn rejects_malformed_fonts() {
+        for content in [
+            FONT.replace("FONTBOUNDINGBOX 8 8 0 -2\n", ""),
+            FONT.replace("BBX 4 3 1 0", "BBX 4 3 1"),
+            FONT.replace("ENCODING 65", "ENCODING A"),
+            FONT.replace("90\n", "9G\n"),
+            FONT.replace("90\n", "0é\n"),
+            "STARTFONT 2.1\nFONTBOUNDINGBOX 8 8 0 -2\nENDFONT\n".to_string(),
+            "BITMAP\n".to_string(),
+            FONT.replace("BBX 4 3 1 0", "BBX 100000 100000 0 0"),
+            FONT.replace("BBX 4 3 1 0", "BBX -4 3 1 0"),
+            FONT.replace("BBX 4 3 1 0", "BBX 4 3 1 2147483647"),
+            FONT.replace("FONTBOUNDINGBOX 8 8 0 -2", "FONTBOUNDINGBOX 8 100000 0 -2"),
+            FONT.replace("FONT_ASCENT 6", "FONT_ASCENT 4294967295"),
+        ] {
+            assert!(parse(&content).is_err(), "{:?}", content);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use super::FontError;
+
+// 单个点阵字形，每行按 MSB 在前的顺序占 ceil(width / 8) 个字节
+pub(crate) struct BitmapGlyph {
+    pub width: u32,
+    pub height: u32,
+    // 相对于笔位置的水平偏移
+    pub x_offset: i32,
+    // 字形顶部相对于字体行顶部的偏移
+    pub y_offset: i32,
+    pub advance: u32,
+    pub bits: Vec<u8>,
+}
+
+impl BitmapGlyph {
+    pub fn row_bytes(&self) -> usize {
+        (self.width as usize).div_ceil(8)
+    }
+}
+
+pub(crate) struct BitmapFont {
+    glyphs: HashMap<char, BitmapGlyph>,
+    pub ascent: u32,
+    pub descent: u32,
+    // 半角字符的宽度，用于计算缺字时的豆腐块宽度
+    pub cell_width: u32,
+}
+
+impl BitmapFont {
+    pub fn new(glyphs: HashMap<char, BitmapGlyph>, ascent: u32, descent: u32, cell_width: u32) -> Self {
+        Self {
+            glyphs,
+            ascent,
+            descent,
+            cell_width,
+        }
+    }
+
+    pub fn glyph(&self, c: char) -> Option<&BitmapGlyph> {
+        self.glyphs.get(&c)
+    }
+
+    // 由内置 8x8 字体表生成，只包含可打印的 ASCII 字符
+    pub fn from_basic(table: &[[u8; 8]; 128]) -> Self {
+        let glyphs = (0x20u8..0x7F)
+            .map(|code| {
+                let glyph = BitmapGlyph {
+                    width: 8,
+                    height: 8,
+                    x_offset: 0,
+                    y_offset: 0,
+                    advance: 8,
+                    bits: table[code as usize].to_vec(),
+                };
+                (code as char, glyph)
+            })
+            .collect();
+
+        Self {
+            glyphs,
+            ascent: 7,
+            descent: 1,
+            cell_width: 8,
+        }
+    }
+
+    // GNU Unifont .hex 格式：每行 "码位:字形数据"，字形高 16 像素，
+    // 32 个十六进制数字为 8 像素宽，64 个为 16 像素宽
+    pub fn parse_hex(content: &str) -> Result<Self, FontError> {
+        const HEIGHT: usize = 16;
+        let mut glyphs = HashMap::new();
+
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || FontError::ParseError(format!("invalid hex font line {}", number + 1));
+
+            let (code, data) = line.split_once(':').ok_or_else(error)?;
+            // from_str_radix 接受开头的正号，这里只允许十六进制数字
+            if !is_hex(code) || !is_hex(data) {
+                return Err(error());
+            }
+            let c = u32::from_str_radix(code, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(error)?;
+            if data.len() % 2 != 0 || data.len() / 2 % HEIGHT != 0 {
+                return Err(error());
+            }
+
+            let bits = (0..data.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&data[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| error())?;
+            let width = (bits.len() / HEIGHT * 8) as u32;
+
+            glyphs.insert(
+                c,
+                BitmapGlyph {
+                    width,
+                    height: HEIGHT as u32,
+                    x_offset: 0,
+                    y_offset: 0,
+                    advance: width,
+                    bits,
+                },
+            );
+        }
+
+        if glyphs.is_empty() {
+            return Err(FontError::ParseError("hex font contains no glyphs".to_string()));
+        }
+
+        Ok(Self {
+            glyphs,
+            ascent: 14,
+            descent: 2,
+            cell_width: 8,
+        })
+    }
+}
+
+pub(super) fn is_hex(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "0041:0000000018242442427E424242420000";
+
+    #[test]
+    fn parses_narrow_and_wide_glyphs() {
+        let wide = format!("4E00:{}", "0000".repeat(7) + "7FFE" + &"0000".repeat(8));
+        let font = BitmapFont::parse_hex(&format!("# comment\n\n{}\n{}\n", A, wide)).unwrap();
+
+        let a = font.glyph('A').unwrap();
+        assert_eq!((a.width, a.height, a.advance), (8, 16, 8));
+        assert_eq!(a.bits[5], 0x24);
+        let wide = font.glyph('一').unwrap();
+        assert_eq!((wide.width, wide.row_bytes()), (16, 2));
+        assert_eq!(&wide.bits[14..16], &[0x7F, 0xFE]);
+        assert!(font.glyph('B').is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        for content in [
+            "0041",
+            "0041:00",
+            "0041:000000001824244242",
+            "ZZZZ:0000000018242442427E424242420000",
+            "+041:0000000018242442427E424242420000",
+            "0041:+0000000018242442427E42424242000",
+            "0041:0é000000018242442427E4242424200",
+            "D800:0000000018242442427E424242420000",
+        ] {
+            assert!(BitmapFont::parse_hex(content).is_err(), "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn rejects_font_without_glyphs() {
+        assert!(BitmapFont::parse_hex("# empty\n").is_err());
+    }
+}
@@ -1,42 +1,426 @@
-// 基础的 8x8 点阵字体系统
+mod bdf;
+mod bitmap;
+mod psf;
+mod shape;
+mod vector;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::text::TextDirection;
+use bitmap::{BitmapFont, BitmapGlyph};
+use vector::{LineMetrics, VectorFace};
+
+pub(crate) use shape::ShapedLine;
+
+#[derive(Debug)]
+pub enum FontError {
+    IoError(io::Error),
+    ParseError(String),
+}
+
+impl From<io::Error> for FontError {
+    fn from(err: io::Error) -> Self {
+        FontError::IoError(err)
+    }
+}
+
+// 全局默认字体，未设置时使用内置的 8x8 ASCII 点阵字体
+static DEFAULT_FONT: RwLock<Option<Font>> = RwLock::new(None);
+static BASIC: OnceLock<Arc<BitmapFont>> = OnceLock::new();
+
+// 合成斜体时每像素高度的水平偏移
+const ITALIC_SLANT: f32 = 0.2;
+
+// 字号上限，过大的字号会让字形尺寸和放大倍数溢出
+pub(crate) const MAX_FONT_SIZE: f32 = 1024.0;
+
+#[derive(Clone)]
+enum FontSource {
+    // 点阵字体和整数放大倍数
+    Bitmap(Arc<BitmapFont>, u32),
+    // 矢量字体和以像素为单位的字号
+    Vector(Arc<VectorFace>, f32),
+}
+
+impl FontSource {
+    fn glyph(&self, c: char) -> Option<Glyph<'_>> {
+        match self {
+            FontSource::Bitmap(bitmap, scale) => bitmap.glyph(c).map(|glyph| Glyph::Bitmap(bitmap, glyph, *scale)),
+            FontSource::Vector(face, size) => face.glyph_index(c).map(|index| Glyph::Vector(face, *size, index)),
+        }
+    }
+
+    fn metrics(&self) -> LineMetrics {
+        match self {
+            FontSource::Bitmap(bitmap, scale) => LineMetrics {
+                ascent: bitmap.ascent.saturating_mul(*scale) as f32,
+                descent: bitmap.descent.saturating_mul(*scale) as f32,
+                line_gap: 0.0,
+            },
+            FontSource::Vector(face, size) => face.line_metrics(*size),
+        }
+    }
+
+    // 半角字符的宽度，用于计算豆腐块的宽度
+    fn cell_width(&self) -> u32 {
+        match self {
+            FontSource::Bitmap(bitmap, scale) => bitmap.cell_width.saturating_mul(*scale),
+            FontSource::Vector(_, size) => (size / 2.0).ceil() as u32,
+        }
+    }
+}
+
+// 在字体链中找到的字形
+enum Glyph<'a> {
+    Bitmap(&'a BitmapFont, &'a BitmapGlyph, u32),
+    Vector(&'a VectorFace, f32, u16),
+}
+
+impl Glyph<'_> {
+    fn advance(&self) -> f32 {
+        match self {
+            Glyph::Bitmap(_, glyph, scale) => glyph.advance.saturating_mul(*scale) as f32,
+            Glyph::Vector(face, size, index) => face.advance(*index, *size),
+        }
+    }
+}
+
+// 点阵字体或 TrueType/OpenType 矢量字体，可以通过 fallback 串联多个字体，缺字时依次查找。
+// 粗体和斜体由绘制时合成，对整个字体链生效
+#[derive(Clone)]
 pub struct Font {
-    glyph_width: u32,
-    glyph_height: u32,
-    glyphs: &'static [[u8; 8]],
+    source: FontSource,
+    fallback: Option<Arc<Font>>,
+    bold: bool,
+    italic: bool,
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        if let Some(font) = DEFAULT_FONT.read().unwrap().as_ref() {
+            return font.clone();
+        }
+        Self::basic()
+    }
 }
 
 impl Font {
-    pub fn default() -> Self {
+    // 内置的 8x8 ASCII 点阵字体
+    pub fn basic() -> Self {
+        let bitmap = BASIC.get_or_init(|| Arc::new(BitmapFont::from_basic(&BASIC_FONT)));
+        Self::from_source(FontSource::Bitmap(bitmap.clone(), 1))
+    }
+
+    // 设置 Font::default() 返回的字体，之后创建的组件都会使用它
+    pub fn set_default(font: Font) {
+        *DEFAULT_FONT.write().unwrap() = Some(font);
+    }
+
+    // 加载 GNU Unifont 的 .hex 格式字体，每行形如 "4E00:0080...."，
+    // 8x16 的半角字形和 16x16 的全角字形
+    pub fn load_hex<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        let content = fs::read_to_string(path)?;
+        Self::from_hex(&content)
+    }
+
+    pub fn from_hex(content: &str) -> Result<Self, FontError> {
+        Ok(Self::from_bitmap(BitmapFont::parse_hex(content)?))
+    }
+
+    // 加载 X11 的 BDF 点阵字体
+    pub fn load_bdf<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        let content = fs::read_to_string(path)?;
+        Self::from_bdf(&content)
+    }
+
+    pub fn from_bdf(content: &str) -> Result<Self, FontError> {
+        Ok(Self::from_bitmap(bdf::parse(content)?))
+    }
+
+    // 加载 Linux 控制台的 PSF1/PSF2 点阵字体
+    pub fn load_psf<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        let bytes = fs::read(path)?;
+        Self::from_psf(&bytes)
+    }
+
+    pub fn from_psf(bytes: &[u8]) -> Result<Self, FontError> {
+        Ok(Self::from_bitmap(psf::parse(bytes)?))
+    }
+
+    fn from_bitmap(bitmap: BitmapFont) -> Self {
+        Self::from_source(FontSource::Bitmap(Arc::new(bitmap), 1))
+    }
+
+    fn from_source(source: FontSource) -> Self {
         Self {
-            glyph_width: 8,
-            glyph_height: 8,
-            glyphs: &BASIC_FONT,
+            source,
+            fallback: None,
+            bold: false,
+            italic: false,
         }
     }
 
-    pub fn render_char(&self, renderer: &mut crate::renderer::Renderer, x: i32, y: i32, c: char, color: [u8; 4]) {
-        if let Some(glyph) = self.get_glyph(c) {
-            for (row, &bits) in glyph.iter().enumerate() {
-                for col in 0..8 {
-                    if (bits >> (7 - col)) & 1 == 1 {
-                        renderer.draw_pixel(
-                            x + col as i32,
-                            y + row as i32,
-                            color
-                        );
-                    }
-                }
+    // 加载 TTF/OTF 字体，size 为以像素为单位的字号
+    pub fn load_ttf<P: AsRef<Path>>(path: P, size: f32) -> Result<Self, FontError> {
+        let bytes = fs::read(path)?;
+        Self::from_ttf_bytes(&bytes, size)
+    }
+
+    pub fn from_ttf_bytes(bytes: &[u8], size: f32) -> Result<Self, FontError> {
+        Ok(Self::from_source(FontSource::Vector(Arc::new(VectorFace::parse(bytes)?), size)))
+    }
+
+    // 本字体缺字时使用的后备字体
+    pub fn with_fallback(mut self, fallback: Font) -> Self {
+        self.fallback = Some(Arc::new(match self.fallback.take() {
+            Some(existing) => (*existing).clone().with_fallback(fallback),
+            None => fallback,
+        }));
+        self
+    }
+
+    // 修改字体链中所有字体的字号，点阵字体按最接近的整数倍放大。
+    // 字号限制在 MAX_FONT_SIZE 以内，NaN 按最小字号处理。
+    // 新字号与原字体共享解析结果和字形缓存
+    pub fn with_size(mut self, size: f32) -> Self {
+        let size = if size.is_nan() { 0.0 } else { size.clamp(0.0, MAX_FONT_SIZE) };
+        match &mut self.source {
+            FontSource::Bitmap(bitmap, scale) => {
+                let native = (bitmap.ascent + bitmap.descent).max(1) as f32;
+                *scale = (size / native).round().clamp(1.0, (MAX_FONT_SIZE / native).max(1.0)) as u32;
             }
+            FontSource::Vector(_, current) => *current = size,
+        }
+        self.fallback = self
+            .fallback
+            .take()
+            .map(|fallback| Arc::new((*fallback).clone().with_size(size)));
+        self
+    }
+
+    // 以像素为单位的字号，点阵字体为字形高度
+    pub fn size(&self) -> f32 {
+        match &self.source {
+            FontSource::Bitmap(bitmap, scale) => (bitmap.ascent + bitmap.descent).saturating_mul(*scale) as f32,
+            FontSource::Vector(_, size) => *size,
+        }
+    }
+
+    // 合成粗体：每个像素向右加粗一像素，前进宽度加一
+    pub fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    // 合成斜体：按到基线的距离水平错切
+    pub fn with_italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    pub fn is_italic(&self) -> bool {
+        self.italic
+    }
+
+    fn fonts(&self) -> impl Iterator<Item = &Font> {
+        std::iter::successors(Some(self), |font| font.fallback.as_deref())
+    }
+
+    fn find_glyph(&self, c: char) -> Option<Glyph<'_>> {
+        self.fonts().find_map(|font| font.source.glyph(c))
+    }
+
+    // 字体链中是否有该字符的字形
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.find_glyph(c).is_some()
+    }
+
+    // 基线到行顶部的距离，取所有字体中的最大值
+    pub fn ascent(&self) -> u32 {
+        self.fonts()
+            .map(|font| font.source.metrics().ascent.ceil() as u32)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn descent(&self) -> u32 {
+        self.fonts()
+            .map(|font| font.source.metrics().descent.ceil() as u32)
+            .max()
+            .unwrap_or(0)
+    }
+
+    // 字体建议的行间距
+    pub fn line_gap(&self) -> u32 {
+        self.source.metrics().line_gap.max(0.0).round() as u32
+    }
+
+    pub fn line_height(&self) -> u32 {
+        self.ascent() + self.descent() + self.line_gap()
+    }
+
+    // 字符的前进宽度，控制字符为 0，缺字时按豆腐块的宽度计算
+    pub fn char_width(&self, c: char) -> u32 {
+        self.advance(c).round() as u32
+    }
+
+    fn advance(&self, c: char) -> f32 {
+        if c.is_control() {
+            return 0.0;
+        }
+        let advance = match self.find_glyph(c) {
+            Some(glyph) => glyph.advance(),
+            None => self.tofu_width(c) as f32,
+        };
+        if self.bold {
+            advance + 1.0
+        } else {
+            advance
         }
     }
 
-    fn get_glyph(&self, c: char) -> Option<&[u8; 8]> {
-        if c.is_ascii() {
-            Some(&self.glyphs[c as usize])
+    // 整形后的文本宽度，包含字距调整和连写
+    pub fn text_width(&self, text: &str) -> u32 {
+        self.shape_line(text, TextDirection::Auto).width.round().max(0.0) as u32
+    }
+
+    fn tofu_width(&self, c: char) -> u32 {
+        let cell = self.source.cell_width();
+        if is_wide(c) {
+            cell.saturating_mul(2)
         } else {
-            None
+            cell
         }
     }
+
+    // 以 (x, y) 为行的左上角绘制字符，返回前进宽度
+    pub fn render_char(&self, renderer: &mut Renderer, x: i32, y: i32, c: char, color: Color) -> u32 {
+        if c.is_control() {
+            return 0;
+        }
+        self.render_glyph(renderer, x, y, c, self.find_glyph(c).as_ref(), color);
+        self.char_width(c)
+    }
+
+    fn render_glyph(&self, renderer: &mut Renderer, x: i32, y: i32, c: char, glyph: Option<&Glyph>, color: Color) {
+        // 不同字体按基线对齐
+        let baseline = y + self.ascent() as i32;
+        let mut plot = |px: i32, py: i32, coverage: u32| {
+            let px = if self.italic {
+                px + ((baseline - py) as f32 * ITALIC_SLANT).round() as i32
+            } else {
+                px
+            };
+            // 按覆盖率与背景混合，实现灰度抗锯齿
+            let alpha = (coverage * color.a as u32 / 255) as u8;
+            let pixel = color.with_alpha(alpha);
+            for dx in 0..=self.bold as i32 {
+                if alpha == 255 {
+                    renderer.draw_pixel(px + dx, py, pixel);
+                } else {
+                    renderer.blend_pixel(px + dx, py, pixel);
+                }
+            }
+        };
+
+        match glyph {
+            Some(Glyph::Bitmap(font, glyph, scale)) => {
+                let scale = *scale as i32;
+                let top = baseline - (font.ascent as i32 - glyph.y_offset) * scale;
+                let left = x + glyph.x_offset * scale;
+                let row_bytes = glyph.row_bytes();
+                for row in 0..glyph.height {
+                    for col in 0..glyph.width {
+                        let byte = glyph.bits[row as usize * row_bytes + col as usize / 8];
+                        if (byte >> (7 - col % 8)) & 1 == 0 {
+                            continue;
+                        }
+                        for sy in 0..scale {
+                            for sx in 0..scale {
+                                plot(left + col as i32 * scale + sx, top + row as i32 * scale + sy, 255);
+                            }
+                        }
+                    }
+                }
+            }
+            Some(Glyph::Vector(face, size, index)) => {
+                let raster = face.rasterize(*index, *size);
+                let top = baseline - raster.ymin - raster.height as i32;
+                let left = x + raster.xmin;
+                for row in 0..raster.height {
+                    for col in 0..raster.width {
+                        let coverage = raster.coverage[(row * raster.width + col) as usize] as u32;
+                        if coverage > 0 {
+                            plot(left + col as i32, top + row as i32, coverage);
+                        }
+                    }
+                }
+            }
+            None => {
+                // 缺字时绘制“豆腐块”，让缺失的字符可见
+                let width = self.tofu_width(c);
+                let height = (self.ascent() + self.descent()).saturating_sub(2);
+                let (left, top) = (x + 1, y + 1);
+                let (w, h) = (width.saturating_sub(2), height);
+                if w > 0 && h > 0 {
+                    renderer.draw_rect(left, top, w, 1, color);
+                    renderer.draw_rect(left, top + h as i32 - 1, w, 1, color);
+                    renderer.draw_rect(left, top, 1, h, color);
+                    renderer.draw_rect(left + w as i32 - 1, top, 1, h, color);
+                }
+            }
+        }
+    }
+
+    // 绘制一行文本，基本方向由第一个强方向字符决定，返回文本宽度
+    pub fn draw_text(&self, renderer: &mut Renderer, x: i32, y: i32, text: &str, color: Color) -> u32 {
+        self.draw_text_with_direction(renderer, x, y, text, color, TextDirection::Auto)
+    }
+
+    // 按指定的基本方向绘制一行文本，阿拉伯语、希伯来语等从右到左的片段按视觉顺序排列
+    pub fn draw_text_with_direction(
+        &self,
+        renderer: &mut Renderer,
+        x: i32,
+        y: i32,
+        text: &str,
+        color: Color,
+        direction: TextDirection,
+    ) -> u32 {
+        let line = self.shape_line(text, direction);
+        self.draw_shaped(renderer, x, y, &line, color);
+        line.width.round().max(0.0) as u32
+    }
+}
+
+// 东亚宽字符（CJK、谚文、全角符号等）占两个字符宽度
+pub fn is_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x1F300..=0x1F64F
+            | 0x1F900..=0x1F9FF
+            | 0x20000..=0x2FFFD
+            | 0x30000..=0x3FFFD
+    )
 }
 
 // 基础的 ASCII 字体数据
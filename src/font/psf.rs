@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use super::bitmap::{BitmapFont, BitmapGlyph};
+use super::FontError;
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF1_MODE512: u8 = 0x01;
+const PSF1_MODEHASTAB: u8 = 0x02;
+const PSF1_SEPARATOR: u16 = 0xFFFF;
+const PSF1_STARTSEQ: u16 = 0xFFFE;
+
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xB5, 0x4A, 0x86];
+const PSF2_HAS_UNICODE_TABLE: u32 = 0x01;
+const PSF2_SEPARATOR: u8 = 0xFF;
+const PSF2_STARTSEQ: u8 = 0xFE;
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, FontError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(truncated)
+}
+
+fn truncated() -> FontError {
+    FontError::ParseError("truncated PSF font".to_string())
+}
+
+// Linux 控制台的 PSF1/PSF2 字体，带 Unicode 映射表时按表建立字符映射，
+// 否则字形序号即为码位
+pub(super) fn parse(bytes: &[u8]) -> Result<BitmapFont, FontError> {
+    let (width, height, glyph_count, glyph_size, glyphs_offset, table) = if bytes.starts_with(&PSF1_MAGIC) {
+        let mode = *bytes.get(2).ok_or_else(truncated)?;
+        let height = *bytes.get(3).ok_or_else(truncated)? as u32;
+        let count = if mode & PSF1_MODE512 != 0 { 512 } else { 256 };
+        (8, height, count, height as usize, 4, mode & PSF1_MODEHASTAB != 0)
+    } else if bytes.starts_with(&PSF2_MAGIC) {
+        let header_size = read_u32(bytes, 8)? as usize;
+        let flags = read_u32(bytes, 12)?;
+        let count = read_u32(bytes, 16)? as usize;
+        let glyph_size = read_u32(bytes, 20)? as usize;
+        let height = read_u32(bytes, 24)?;
+        let width = read_u32(bytes, 28)?;
+        (width, height, count, glyph_size, header_size, flags & PSF2_HAS_UNICODE_TABLE != 0)
+    } else {
+        return Err(FontError::ParseError("not a PSF font".to_string()));
+    };
+
+    let row_bytes = (width as usize).div_ceil(8);
+    if width == 0 || height == 0 || glyph_size < row_bytes * height as usize {
+        return Err(FontError::ParseError("invalid PSF glyph size".to_string()));
+    }
+
+    let table_offset = glyph_count
+        .checked_mul(glyph_size)
+        .and_then(|size| size.checked_add(glyphs_offset))
+        .ok_or_else(truncated)?;
+    let data = bytes.get(glyphs_offset..table_offset).ok_or_else(truncated)?;
+
+    let mapping = if !table {
+        (0..glyph_count)
+            .filter_map(|index| char::from_u32(index as u32).map(|c| (c, index)))
+            .collect()
+    } else if bytes.starts_with(&PSF1_MAGIC) {
+        psf1_table(&bytes[table_offset..], glyph_count)
+    } else {
+        psf2_table(&bytes[table_offset..], glyph_count)
+    };
+
+    let glyphs: HashMap<char, BitmapGlyph> = mapping
+        .into_iter()
+        .map(|(c, index)| {
+            let start = index * glyph_size;
+            let glyph = BitmapGlyph {
+                width,
+                height,
+                x_offset: 0,
+                y_offset: 0,
+                advance: width,
+                bits: data[start..start + row_bytes * height as usize].to_vec(),
+            };
+            (c, glyph)
+        })
+        .collect();
+
+    // PSF 没有记录基线，按常见控制台字体的比例估算下行高度
+    let descent = (height / 5).max(1);
+    Ok(BitmapFont::new(glyphs, height - descent, descent, width))
+}
+
+// PSF1 映射表：每个字形一组小端 u16 码位，0xFFFE 之后是组合序列，0xFFFF 结束
+fn psf1_table(bytes: &[u8], glyph_count: usize) -> Vec<(char, usize)> {
+    let mut mapping = Vec::new();
+    let mut index = 0;
+    let mut in_sequence = false;
+    for chunk in bytes.chunks_exact(2) {
+        if index >= glyph_count {
+            break;
+        }
+        match u16::from_le_bytes([chunk[0], chunk[1]]) {
+            PSF1_SEPARATOR => {
+                index += 1;
+                in_sequence = false;
+            }
+            PSF1_STARTSEQ => in_sequence = true,
+            code if !in_sequence => mapping.extend(char::from_u32(code as u32).map(|c| (c, index))),
+            _ => {}
+        }
+    }
+    mapping
+}
+
+// PSF2 映射表：每个字形一组 UTF-8 字符，0xFE 之后是组合序列，0xFF 结束
+fn psf2_table(bytes: &[u8], glyph_count: usize) -> Vec<(char, usize)> {
+    let mut mapping = Vec::new();
+    for (index, entry) in bytes.split(|&b| b == PSF2_SEPARATOR).take(glyph_count).enumerate() {
+        let singles = entry.split(|&b| b == PSF2_STARTSEQ).next().unwrap_or(&[]);
+        if let Ok(text) = std::str::from_utf8(singles) {
+            mapping.extend(text.chars().map(|c| (c, index)));
+        }
+    }
+    mapping
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn psf1(mode: u8, table: &[u16]) -> Vec<u8> {
+        let mut bytes = vec![PSF1_MAGIC[0], PSF1_MAGIC[1], mode, 8];
+        bytes.extend((0..256u32).flat_map(|index| [index as u8; 8]));
+        bytes.extend(table.iter().flat_map(|code| code.to_le_bytes()));
+        bytes
+    }
+
+    fn psf2(count: u32, glyph_size: u32, table: &[u8]) -> Vec<u8> {
+        let mut bytes = PSF2_MAGIC.to_vec();
+        for value in [0, 32, PSF2_HAS_UNICODE_TABLE, count, glyph_size, 16, 8] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend((0..2u8).flat_map(|index| [index + 1; 16]));
+        bytes.extend(table);
+        bytes
+    }
+
+    #[test]
+    fn maps_psf1_glyph_index_to_code_point() {
+        let font = parse(&psf1(0, &[])).unwrap();
+        let a = font.glyph('A').unwrap();
+        assert_eq!((a.width, a.height), (8, 8));
+        assert_eq!(a.bits, vec![b'A'; 8]);
+        assert_eq!((font.ascent, font.descent), (7, 1));
+    }
+
+    #[test]
+    fn reads_psf1_unicode_table() {
+        // 字形 0 对应 é，组合序列被忽略；其余字形没有映射
+        let mut table = vec![0x00E9, PSF1_STARTSEQ, 0x0065, 0x0301, PSF1_SEPARATOR];
+        table.extend([PSF1_SEPARATOR; 255]);
+        let font = parse(&psf1(PSF1_MODEHASTAB, &table)).unwrap();
+        assert_eq!(font.glyph('é').unwrap().bits, vec![0; 8]);
+        assert!(font.glyph('e').is_none());
+        assert!(font.glyph('A').is_none());
+    }
+
+    #[test]
+    fn reads_psf2_unicode_table() {
+        let table = [b'a', PSF2_SEPARATOR, b'b', b'c', PSF2_STARTSEQ, b'x', b'y', PSF2_SEPARATOR];
+        let font = parse(&psf2(2, 16, &table)).unwrap();
+        assert_eq!(font.glyph('a').unwrap().bits, vec![1; 16]);
+        assert_eq!(font.glyph('c').unwrap().bits, vec![2; 16]);
+        assert!(font.glyph('x').is_none());
+        assert_eq!(font.glyph('b').unwrap().height, 16);
+    }
+
+    #[test]
+    fn rejects_malformed_fonts() {
+        let mut truncated = psf1(0, &[]);
+        truncated.truncate(100);
+        for bytes in [
+            b"not a font".to_vec(),
+            vec![PSF1_MAGIC[0], PSF1_MAGIC[1]],
+            truncated,
+            psf2(3, 16, &[]),
+            psf2(2, 8, &[]),
+            psf2(u32::MAX, u32::MAX, &[]),
+        ] {
+            assert!(parse(&bytes).is_err(), "{:?}", &bytes[..bytes.len().min(8)]);
+        }
+    }
+}
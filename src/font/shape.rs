@@ -0,0 +1,279 @@
+use unicode_bidi::{BidiInfo, Level};
+use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
+use super::{Font, FontSource, Glyph};
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::text::TextDirection;
+
+// 整形后字形的来源
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum GlyphKind {
+    // 矢量字体整形得到的字形编号，以及该字体在字体链中的位置
+    Indexed(usize, u16),
+    // 逐字符排版的字符，绘制时在字体链中查找
+    Char(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ShapedGlyph {
+    pub kind: GlyphKind,
+    // 字形所属字符簇在整行文本中的字节位置，同一字符簇的字形相同
+    pub cluster: usize,
+    // 笔位置，相对于行的左边缘
+    pub x: f32,
+    pub advance: f32,
+    pub x_offset: f32,
+    // 向上为正
+    pub y_offset: f32,
+    pub rtl: bool,
+}
+
+// 整形后的一行文本，字形按视觉顺序从左到右排列
+#[derive(Debug, Clone)]
+pub(crate) struct ShapedLine {
+    pub glyphs: Vec<ShapedGlyph>,
+    pub width: f32,
+    // 行的基本方向是否为从右到左
+    pub rtl: bool,
+}
+
+impl Font {
+    // 对一行文本执行双向算法 (UAX #9)，再按方向一致的片段逐段整形
+    pub(crate) fn shape_line(&self, text: &str, direction: TextDirection) -> ShapedLine {
+        let mut line = ShapedLine {
+            glyphs: Vec::new(),
+            width: 0.0,
+            rtl: direction == TextDirection::Rtl,
+        };
+        // 纯 ASCII 文本只有从左到右的字符
+        if text.is_ascii() && direction != TextDirection::Rtl {
+            self.shape_run(text, 0, false, &mut line);
+            return line;
+        }
+
+        let level = match direction {
+            TextDirection::Auto => None,
+            TextDirection::Ltr => Some(Level::ltr()),
+            TextDirection::Rtl => Some(Level::rtl()),
+        };
+        let bidi = BidiInfo::new(text, level);
+        line.rtl = bidi.paragraphs.first().is_some_and(|paragraph| paragraph.level.is_rtl());
+        for paragraph in &bidi.paragraphs {
+            let (levels, runs) = bidi.visual_runs(paragraph, paragraph.range.clone());
+            for run in runs {
+                let rtl = levels[run.start].is_rtl();
+                self.shape_run(&text[run.clone()], run.start, rtl, &mut line);
+            }
+        }
+        line
+    }
+
+    // 方向一致的一段文本，按覆盖字符的字体再分段
+    fn shape_run(&self, text: &str, offset: usize, rtl: bool, line: &mut ShapedLine) {
+        // (字体在字体链中的位置, 起点, 终点)，控制字符和缺字的字符没有字体
+        let mut segments: Vec<(Option<usize>, usize, usize)> = Vec::new();
+        for (index, c) in text.char_indices() {
+            let end = index + c.len_utf8();
+            let font = if c.is_control() {
+                None
+            } else {
+                self.fonts().position(|font| font.source.glyph(c).is_some())
+            };
+            match segments.last_mut() {
+                // 组合字符和格式字符（如 ZWJ）跟随前一个字符，保证整形时不被拆开
+                Some(last) if last.0 == font || (!c.is_control() && (is_mark(c) || is_format(c))) => last.2 = end,
+                _ => segments.push((font, index, end)),
+            }
+        }
+        if rtl {
+            segments.reverse();
+        }
+
+        for (font, start, end) in segments {
+            let source = font.and_then(|index| self.fonts().nth(index)).map(|font| &font.source);
+            if let (Some(index), Some(FontSource::Vector(face, size))) = (font, source) {
+                if let Some(glyphs) = face.shape(&text[start..end], *size, rtl) {
+                    for glyph in glyphs {
+                        let advance = glyph.advance + self.bold_extra(glyph.advance);
+                        line.glyphs.push(ShapedGlyph {
+                            kind: GlyphKind::Indexed(index, glyph.id),
+                            cluster: offset + start + glyph.cluster,
+                            x: line.width,
+                            advance,
+                            x_offset: glyph.x_offset,
+                            y_offset: glyph.y_offset,
+                            rtl,
+                        });
+                        line.width += advance;
+                    }
+                    continue;
+                }
+            }
+            self.push_chars(&text[start..end], offset + start, rtl, line);
+        }
+    }
+
+    // 点阵字体没有整形表，逐字符排列。从右到左的片段倒序排列并镜像括号等字符，
+    // 组合字符不占宽度，绘制在基字符的位置上
+    fn push_chars(&self, text: &str, offset: usize, rtl: bool, line: &mut ShapedLine) {
+        let mut clusters: Vec<(usize, Vec<char>)> = Vec::new();
+        for (index, c) in text.char_indices() {
+            if c.is_control() || is_format(c) {
+                continue;
+            }
+            match clusters.last_mut() {
+                Some(cluster) if is_mark(c) => cluster.1.push(c),
+                _ => clusters.push((offset + index, vec![c])),
+            }
+        }
+        if rtl {
+            clusters.reverse();
+        }
+
+        for (cluster, chars) in clusters {
+            let x = line.width;
+            for (index, &c) in chars.iter().enumerate() {
+                let c = match unicode_bidi_mirroring::get_mirrored(c) {
+                    Some(mirrored) if rtl => mirrored,
+                    _ => c,
+                };
+                let advance = if index == 0 { self.advance(c) } else { 0.0 };
+                line.glyphs.push(ShapedGlyph {
+                    kind: GlyphKind::Char(c),
+                    cluster,
+                    x,
+                    advance,
+                    x_offset: 0.0,
+                    y_offset: 0.0,
+                    rtl,
+                });
+                line.width += advance;
+            }
+        }
+    }
+
+    // 合成粗体时每个字形多占一个像素
+    fn bold_extra(&self, advance: f32) -> f32 {
+        if self.bold && advance > 0.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    // 以 (x, y) 为行的左上角绘制整形后的文本
+    pub(crate) fn draw_shaped(&self, renderer: &mut Renderer, x: i32, y: i32, line: &ShapedLine, color: Color) {
+        for glyph in &line.glyphs {
+            let glyph_x = x + (glyph.x + glyph.x_offset).round() as i32;
+            let glyph_y = y - glyph.y_offset.round() as i32;
+            match glyph.kind {
+                GlyphKind::Indexed(font, id) => {
+                    if let Some(FontSource::Vector(face, size)) = self.fonts().nth(font).map(|font| &font.source) {
+                        let found = Glyph::Vector(face, *size, id);
+                        self.render_glyph(renderer, glyph_x, glyph_y, ' ', Some(&found), color);
+                    }
+                }
+                GlyphKind::Char(c) => {
+                    self.render_glyph(renderer, glyph_x, glyph_y, c, self.find_glyph(c).as_ref(), color);
+                }
+            }
+        }
+    }
+}
+
+// 不占宽度的组合字符，例如重音符号和希伯来语元音符号
+fn is_mark(c: char) -> bool {
+    matches!(c.general_category(), GeneralCategory::NonspacingMark | GeneralCategory::EnclosingMark)
+}
+
+// 不可见的格式字符，例如零宽连接符和方向标记
+fn is_format(c: char) -> bool {
+    c.general_category() == GeneralCategory::Format
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{TextLayout, TextOptions};
+
+    fn clusters(line: &ShapedLine) -> Vec<usize> {
+        line.glyphs.iter().map(|glyph| glyph.cluster).collect()
+    }
+
+    #[test]
+    fn orders_mixed_runs_visually() {
+        let font = Font::basic();
+        // א 和 ב 各占两个字节
+        let line = font.shape_line("ab אב", TextDirection::Auto);
+        assert!(!line.rtl);
+        assert_eq!(clusters(&line), [0, 1, 2, 5, 3]);
+        assert!(line.glyphs[3].rtl && !line.glyphs[0].rtl);
+
+        // 从右到左的段落中，左到右的片段整体保持原顺序，中间的空格跟随段落方向
+        let line = font.shape_line("אב ab", TextDirection::Auto);
+        assert!(line.rtl);
+        assert_eq!(clusters(&line), [5, 6, 4, 2, 0]);
+
+        let line = font.shape_line("ab", TextDirection::Rtl);
+        assert!(line.rtl);
+        assert_eq!(clusters(&line), [0, 1]);
+    }
+
+    #[test]
+    fn glyphs_are_laid_out_left_to_right() {
+        let font = Font::basic();
+        let line = font.shape_line("a אב b", TextDirection::Auto);
+        let mut x = 0.0;
+        for glyph in &line.glyphs {
+            assert_eq!(glyph.x, x);
+            x += glyph.advance;
+        }
+        assert_eq!(line.width, x);
+    }
+
+    #[test]
+    fn mirrors_brackets_and_keeps_marks_with_base() {
+        let font = Font::basic();
+        let line = font.shape_line("(א)", TextDirection::Rtl);
+        let chars: Vec<char> = line
+            .glyphs
+            .iter()
+            .filter_map(|glyph| match glyph.kind {
+                GlyphKind::Char(c) => Some(c),
+                GlyphKind::Indexed(..) => None,
+            })
+            .collect();
+        assert_eq!(chars, ['(', 'א', ')']);
+        assert_eq!(clusters(&line), [3, 1, 0]);
+
+        let line = font.shape_line("e\u{301}x", TextDirection::Ltr);
+        assert_eq!(clusters(&line), [0, 0, 3]);
+        assert_eq!(line.glyphs[1].advance, 0.0);
+        assert_eq!(line.glyphs[1].x, line.glyphs[0].x);
+    }
+
+    #[test]
+    fn caret_stops_follow_visual_order() {
+        let font = Font::basic();
+        let layout = TextLayout::new(&font, "ab אב", &TextOptions::default());
+        let mut index = 0;
+        let mut visited = vec![index];
+        for _ in 0..5 {
+            index = layout.move_caret_right(index);
+            visited.push(index);
+        }
+        assert_eq!(visited, [0, 1, 2, 5, 3, 3]);
+        // 从右到左字符的光标停在字符的右边缘
+        assert_eq!(layout.caret_position(3).0, layout.width());
+        assert!(layout.caret_position(5).0 < layout.caret_position(3).0);
+        assert_eq!(layout.move_caret_left(3), 5);
+
+        // 从右到左的行中，行首在右边缘，向左移动是逻辑上的向前
+        let layout = TextLayout::new(&font, "אב", &TextOptions::default());
+        assert_eq!(layout.caret_position(0).0, layout.width());
+        assert_eq!(layout.caret_position(4).0, 0.0);
+        assert_eq!(layout.move_caret_left(0), 2);
+        assert_eq!(layout.move_caret_left(2), 4);
+        assert_eq!(layout.move_caret_right(4), 2);
+    }
+}
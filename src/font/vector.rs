@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use fontdue::FontSettings;
+use rustybuzz::{Direction, UnicodeBuffer};
+use super::FontError;
+
+// 光栅化后的字形，coverage 为每个像素的灰度覆盖率
+pub(crate) struct RasterGlyph {
+    pub width: u32,
+    pub height: u32,
+    // 位图左边缘相对于笔位置的偏移
+    pub xmin: i32,
+    // 位图下边缘相对于基线的偏移，向上为正
+    pub ymin: i32,
+    pub coverage: Vec<u8>,
+}
+
+// 以像素为单位的行度量
+#[derive(Clone, Copy)]
+pub(crate) struct LineMetrics {
+    pub ascent: f32,
+    pub descent: f32,
+    pub line_gap: f32,
+}
+
+// 整形结果中的一个字形，长度单位为像素
+pub(crate) struct FaceGlyph {
+    pub id: u16,
+    // 字形对应的字符簇在输入文本中的字节位置
+    pub cluster: usize,
+    pub advance: f32,
+    pub x_offset: f32,
+    // 向上为正
+    pub y_offset: f32,
+}
+
+// TrueType/OpenType 字体，同一字体的不同字号共享字形缓存。
+// 光栅化使用 fontdue，整形使用 rustybuzz，两者的字形编号一致
+pub(crate) struct VectorFace {
+    data: Vec<u8>,
+    font: fontdue::Font,
+    cache: Mutex<HashMap<(u16, u32), Arc<RasterGlyph>>>,
+}
+
+impl VectorFace {
+    pub fn parse(bytes: &[u8]) -> Result<Self, FontError> {
+        let font = fontdue::Font::from_bytes(bytes, FontSettings::default())
+            .map_err(|e| FontError::ParseError(e.to_string()))?;
+        Ok(Self {
+            data: bytes.to_vec(),
+            font,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    // 字体中没有该字符时返回 None
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        match self.font.lookup_glyph_index(c) {
+            0 => None,
+            index => Some(index),
+        }
+    }
+
+    pub fn line_metrics(&self, size: f32) -> LineMetrics {
+        match self.font.horizontal_line_metrics(size) {
+            Some(metrics) => LineMetrics {
+                ascent: metrics.ascent,
+                descent: -metrics.descent,
+                line_gap: metrics.line_gap,
+            },
+            // 缺少 hhea 表时按常见比例估算
+            None => LineMetrics {
+                ascent: size * 0.8,
+                descent: size * 0.2,
+                line_gap: 0.0,
+            },
+        }
+    }
+
+    pub fn advance(&self, index: u16, size: f32) -> f32 {
+        self.font.metrics_indexed(index, size).advance_width
+    }
+
+    // 整形同一方向的一段文本，处理连写、组合字符和字距调整，结果按视觉顺序排列。
+    // 字体无法被整形引擎解析时返回 None
+    pub fn shape(&self, text: &str, size: f32, rtl: bool) -> Option<Vec<FaceGlyph>> {
+        // Face 借用字体数据，每次整形时创建，开销与整形本身相比很小
+        let face = rustybuzz::Face::from_slice(&self.data, 0)?;
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.set_direction(if rtl { Direction::RightToLeft } else { Direction::LeftToRight });
+        buffer.guess_segment_properties();
+
+        let output = rustybuzz::shape(&face, &[], buffer);
+        let scale = size / face.units_per_em() as f32;
+        let glyphs = output
+            .glyph_infos()
+            .iter()
+            .zip(output.glyph_positions())
+            .map(|(info, position)| FaceGlyph {
+                id: info.glyph_id as u16,
+                cluster: info.cluster as usize,
+                advance: position.x_advance as f32 * scale,
+                x_offset: position.x_offset as f32 * scale,
+                y_offset: position.y_offset as f32 * scale,
+            })
+            .collect();
+        Some(glyphs)
+    }
+
+    pub fn rasterize(&self, index: u16, size: f32) -> Arc<RasterGlyph> {
+        let key = (index, size.to_bits());
+        if let Some(glyph) = self.cache.lock().unwrap().get(&key) {
+            return glyph.clone();
+        }
+
+        let (metrics, coverage) = self.font.rasterize_indexed(index, size);
+        let glyph = Arc::new(RasterGlyph {
+            width: metrics.width as u32,
+            height: metrics.height as u32,
+            xmin: metrics.xmin,
+            ymin: metrics.ymin,
+            coverage,
+        });
+        self.cache.lock().unwrap().insert(key, glyph.clone());
+        glyph
+    }
+}
@@ -0,0 +1,242 @@
+use super::{buffer_size, ImageData, ImageError};
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, ImageError> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(truncated)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, ImageError> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(truncated)
+}
+
+fn truncated() -> ImageError {
+    ImageError::DecodeError("truncated BMP file".to_string())
+}
+
+// 按位掩码取出颜色分量并扩展到 8 位
+fn extract(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let bits = mask.count_ones();
+    let component = (value & mask) >> mask.trailing_zeros();
+    ((component as u64 * 255) / ((1u64 << bits) - 1)) as u8
+}
+
+// 支持未压缩的 1/4/8 位调色板、16/24/32 位以及位域格式
+pub(super) fn decode(bytes: &[u8]) -> Result<ImageData, ImageError> {
+    let pixel_offset = read_u32(bytes, 10)? as usize;
+    let header_size = read_u32(bytes, 14)? as usize;
+
+    let (width, height, bpp, compression, colors_used) = if header_size == 12 {
+        (
+            read_u16(bytes, 18)? as i32,
+            read_u16(bytes, 20)? as i16 as i32,
+            read_u16(bytes, 24)?,
+            BI_RGB,
+            0,
+        )
+    } else {
+        (
+            read_u32(bytes, 18)? as i32,
+            read_u32(bytes, 22)? as i32,
+            read_u16(bytes, 28)?,
+            read_u32(bytes, 30)?,
+            read_u32(bytes, 46)?,
+        )
+    };
+
+    if width <= 0 || height == 0 {
+        return Err(ImageError::DecodeError("invalid BMP dimensions".to_string()));
+    }
+    if !matches!(bpp, 1 | 4 | 8 | 16 | 24 | 32) {
+        return Err(ImageError::Unsupported(format!("BMP with {} bits per pixel", bpp)));
+    }
+    if !matches!(compression, BI_RGB | BI_BITFIELDS | BI_ALPHABITFIELDS) {
+        return Err(ImageError::Unsupported(format!("BMP compression {}", compression)));
+    }
+
+    // 高度为正表示自下而上存储
+    let bottom_up = height > 0;
+    let width = width as u32;
+    let height = height.unsigned_abs();
+
+    // 位域掩码，没有指定时使用默认值
+    let (red_mask, green_mask, blue_mask, alpha_mask) = if compression == BI_RGB {
+        match bpp {
+            16 => (0x7C00, 0x03E0, 0x001F, 0),
+            _ => (0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0),
+        }
+    } else {
+        let masks_offset = 14 + 40;
+        let alpha_mask = if compression == BI_ALPHABITFIELDS || header_size >= 56 {
+            read_u32(bytes, masks_offset + 12)?
+        } else {
+            0
+        };
+        (
+            read_u32(bytes, masks_offset)?,
+            read_u32(bytes, masks_offset + 4)?,
+            read_u32(bytes, masks_offset + 8)?,
+            alpha_mask,
+        )
+    };
+
+    // 调色板
+    let mut palette = Vec::new();
+    if bpp <= 8 {
+        let entry_size = if header_size == 12 { 3 } else { 4 };
+        let count = if colors_used == 0 { 1usize << bpp } else { colors_used as usize };
+        let start = 14 + header_size;
+        for i in 0..count {
+            let entry = bytes
+                .get(start + i * entry_size..start + i * entry_size + 3)
+                .ok_or_else(truncated)?;
+            palette.push([entry[2], entry[1], entry[0], 255]);
+        }
+    }
+
+    let stride = (bpp as usize)
+        .checked_mul(width as usize)
+        .map(|bits| bits.div_ceil(32) * 4)
+        .ok_or_else(|| ImageError::DecodeError("BMP row too large".to_string()))?;
+    // 先确认像素数据完整，再按文件头中的尺寸分配内存
+    let data_size = stride.checked_mul(height as usize).ok_or_else(truncated)?;
+    if data_size > bytes.len().saturating_sub(pixel_offset) {
+        return Err(truncated());
+    }
+    let mut pixels = Vec::with_capacity(buffer_size(width, height, 4)?);
+
+    for y in 0..height {
+        let row_index = if bottom_up { height - 1 - y } else { y } as usize;
+        let row_start = pixel_offset + row_index * stride;
+        let row = bytes.get(row_start..row_start + stride).ok_or_else(truncated)?;
+
+        for x in 0..width as usize {
+            let pixel = match bpp {
+                1 | 4 | 8 => {
+                    let bits = bpp as usize;
+                    let byte = row[x * bits / 8];
+                    let shift = 8 - bits - (x * bits % 8);
+                    let index = ((byte >> shift) & ((1u16 << bits) - 1) as u8) as usize;
+                    *palette.get(index).ok_or_else(|| ImageError::DecodeError("BMP palette index out of range".to_string()))?
+                }
+                16 => {
+                    let value = u16::from_le_bytes([row[x * 2], row[x * 2 + 1]]) as u32;
+                    let alpha = if alpha_mask == 0 { 255 } else { extract(value, alpha_mask) };
+                    [extract(value, red_mask), extract(value, green_mask), extract(value, blue_mask), alpha]
+                }
+                24 => [row[x * 3 + 2], row[x * 3 + 1], row[x * 3], 255],
+                32 => {
+                    let value = u32::from_le_bytes([row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]]);
+                    let alpha = if alpha_mask == 0 { 255 } else { extract(value, alpha_mask) };
+                    [extract(value, red_mask), extract(value, green_mask), extract(value, blue_mask), alpha]
+                }
+                _ => return Err(ImageError::Unsupported(format!("BMP with {} bits per pixel", bpp))),
+            };
+            pixels.extend_from_slice(&pixel);
+        }
+    }
+
+    ImageData::new(width, height, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 组装 BMP 文件：40 字节的 BITMAPINFOHEADER，后面依次是 extra（位域掩码）、调色板和像素数据
+    fn bmp(width: i32, height: i32, bpp: u16, compression: u32, extra: &[u8], palette: &[[u8; 4]], data: &[u8]) -> Vec<u8> {
+        let pixel_offset = 14 + 40 + extra.len() + palette.len() * 4;
+        let mut bytes = b"BM".to_vec();
+        bytes.extend(((pixel_offset + data.len()) as u32).to_le_bytes());
+        bytes.extend([0; 4]);
+        bytes.extend((pixel_offset as u32).to_le_bytes());
+        bytes.extend(40u32.to_le_bytes());
+        bytes.extend(width.to_le_bytes());
+        bytes.extend(height.to_le_bytes());
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(bpp.to_le_bytes());
+        bytes.extend(compression.to_le_bytes());
+        bytes.extend([0; 12]);
+        bytes.extend((palette.len() as u32).to_le_bytes());
+        bytes.extend([0; 4]);
+        bytes.extend(extra);
+        for entry in palette {
+            bytes.extend(entry);
+        }
+        bytes.extend(data);
+        bytes
+    }
+
+    fn pixels(image: &ImageData) -> Vec<[u8; 4]> {
+        image.pixels().chunks_exact(4).map(|p| [p[0], p[1], p[2], p[3]]).collect()
+    }
+
+    #[test]
+    fn decodes_bottom_up_24_bit_with_row_padding() {
+        // 每行 2 个像素 6 字节，补齐到 8 字节；第一行数据是图片的最下面一行
+        let data = [0, 0, 255, 0, 255, 0, 0, 0, 255, 0, 0, 255, 255, 255, 0, 0];
+        let image = decode(&bmp(2, 2, 24, BI_RGB, &[], &[], &data)).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(
+            pixels(&image),
+            [[0, 0, 255, 255], [255, 255, 255, 255], [255, 0, 0, 255], [0, 255, 0, 255]]
+        );
+    }
+
+    #[test]
+    fn decodes_top_down_palette_images() {
+        let palette = [[0, 0, 0, 0], [255, 128, 0, 0]];
+        // 1 位：0b1010_0000，第 1、3 个像素使用调色板的第 1 项
+        let image = decode(&bmp(3, -1, 1, BI_RGB, &[], &palette, &[0b1010_0000, 0, 0, 0])).unwrap();
+        assert_eq!(pixels(&image), [[0, 128, 255, 255], [0, 0, 0, 255], [0, 128, 255, 255]]);
+        // 4 位：每个字节两个像素，高半字节在前
+        let image = decode(&bmp(2, -1, 4, BI_RGB, &[], &palette, &[0x10, 0, 0, 0])).unwrap();
+        assert_eq!(pixels(&image), [[0, 128, 255, 255], [0, 0, 0, 255]]);
+    }
+
+    #[test]
+    fn decodes_bitfields_with_alpha() {
+        let masks: Vec<u8> = [0x0000_FF00u32, 0x00FF_0000, 0xFF00_0000, 0x0000_00FF]
+            .iter()
+            .flat_map(|mask| mask.to_le_bytes())
+            .collect();
+        let data = 0x1122_3380u32.to_le_bytes();
+        let image = decode(&bmp(1, 1, 32, BI_ALPHABITFIELDS, &masks, &[], &data)).unwrap();
+        assert_eq!(pixels(&image), [[0x33, 0x22, 0x11, 0x80]]);
+
+        // 16 位 RGB565
+        let masks: Vec<u8> = [0xF800u32, 0x07E0, 0x001F].iter().flat_map(|mask| mask.to_le_bytes()).collect();
+        let image = decode(&bmp(1, 1, 16, BI_BITFIELDS, &masks, &[], &[0x1F, 0xF8, 0, 0])).unwrap();
+        assert_eq!(pixels(&image), [[255, 0, 255, 255]]);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        let valid = bmp(1, 1, 24, BI_RGB, &[], &[], &[0; 4]);
+        assert!(decode(&valid).is_ok());
+        for bytes in [
+            valid[..20].to_vec(),
+            valid[..valid.len() - 1].to_vec(),
+            bmp(0, 1, 24, BI_RGB, &[], &[], &[0; 4]),
+            bmp(1, 0, 24, BI_RGB, &[], &[], &[0; 4]),
+            bmp(-1, 1, 24, BI_RGB, &[], &[], &[0; 4]),
+            bmp(1, 1, 3, BI_RGB, &[], &[[0; 4]; 8], &[0; 4]),
+            bmp(1, 1, 8, 1, &[], &[[0; 4]], &[0; 4]),
+            bmp(1, 1, 8, BI_RGB, &[], &[[0; 4]], &[5, 0, 0, 0]),
+            bmp(i32::MAX, i32::MAX, 32, BI_RGB, &[], &[], &[0; 4]),
+        ] {
+            assert!(decode(&bytes).is_err());
+        }
+    }
+}
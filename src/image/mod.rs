@@ -0,0 +1,224 @@
+mod bmp;
+mod ppm;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum ImageError {
+    IoError(io::Error),
+    DecodeError(String),
+    Unsupported(String),
+}
+
+impl From<io::Error> for ImageError {
+    fn from(err: io::Error) -> Self {
+        ImageError::IoError(err)
+    }
+}
+
+impl From<png::DecodingError> for ImageError {
+    fn from(err: png::DecodingError) -> Self {
+        ImageError::DecodeError(err.to_string())
+    }
+}
+
+// 缩放时的采样方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleFilter {
+    Nearest,
+    Bilinear,
+}
+
+// 图片在目标区域中的适配方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFit {
+    // 保持比例，完整显示在区域内
+    Contain,
+    // 保持比例，铺满区域，超出部分裁掉
+    Cover,
+    // 拉伸铺满区域
+    Fill,
+    // 原始大小，居中显示
+    None,
+}
+
+// RGBA 格式的位图，每个像素 4 个字节，未预乘 alpha
+#[derive(Debug, Clone)]
+pub struct ImageData {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl ImageData {
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Result<Self, ImageError> {
+        let size = buffer_size(width, height, 4)?;
+        if pixels.len() != size {
+            return Err(ImageError::DecodeError(format!(
+                "expected {} bytes for {}x{} RGBA image, got {}",
+                size,
+                width,
+                height,
+                pixels.len()
+            )));
+        }
+        Ok(Self { width, height, pixels })
+    }
+
+    // 根据文件内容识别格式
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
+        let bytes = fs::read(path)?;
+        Self::decode(&bytes)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ImageError> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            decode_png(bytes)
+        } else if bytes.starts_with(b"BM") {
+            bmp::decode(bytes)
+        } else if bytes.len() >= 2 && bytes[0] == b'P' && (b'1'..=b'6').contains(&bytes[1]) {
+            ppm::decode(bytes)
+        } else {
+            Err(ImageError::Unsupported("unknown image format".to_string()))
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let idx = (y as usize * self.width as usize + x as usize) * 4;
+        [self.pixels[idx], self.pixels[idx + 1], self.pixels[idx + 2], self.pixels[idx + 3]]
+    }
+
+    // 在图片坐标 (u, v) 处采样，坐标以像素为单位，空图片返回透明色
+    pub fn sample(&self, u: f32, v: f32, filter: ScaleFilter) -> [u8; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0; 4];
+        }
+        let max_x = self.width as f32 - 1.0;
+        let max_y = self.height as f32 - 1.0;
+
+        match filter {
+            ScaleFilter::Nearest => self.pixel(u.clamp(0.0, max_x) as u32, v.clamp(0.0, max_y) as u32),
+            ScaleFilter::Bilinear => {
+                // 像素中心位于 +0.5 处
+                let u = (u - 0.5).clamp(0.0, max_x);
+                let v = (v - 0.5).clamp(0.0, max_y);
+                let x0 = u.floor() as u32;
+                let y0 = v.floor() as u32;
+                let x1 = (x0 + 1).min(self.width - 1);
+                let y1 = (y0 + 1).min(self.height - 1);
+                let fx = u - x0 as f32;
+                let fy = v - y0 as f32;
+
+                let p00 = self.pixel(x0, y0);
+                let p10 = self.pixel(x1, y0);
+                let p01 = self.pixel(x0, y1);
+                let p11 = self.pixel(x1, y1);
+
+                // 按 alpha 加权插值，避免透明像素的颜色渗到边缘
+                let weights = [
+                    (1.0 - fx) * (1.0 - fy) * p00[3] as f32,
+                    fx * (1.0 - fy) * p10[3] as f32,
+                    (1.0 - fx) * fy * p01[3] as f32,
+                    fx * fy * p11[3] as f32,
+                ];
+                let alpha = weights.iter().sum::<f32>();
+                let mut result = [0u8; 4];
+                if alpha > 0.0 {
+                    for c in 0..3 {
+                        let value = p00[c] as f32 * weights[0]
+                            + p10[c] as f32 * weights[1]
+                            + p01[c] as f32 * weights[2]
+                            + p11[c] as f32 * weights[3];
+                        result[c] = (value / alpha).round() as u8;
+                    }
+                }
+                result[3] = alpha.round().min(255.0) as u8;
+                result
+            }
+        }
+    }
+}
+
+// width × height × channels 的字节数，文件头中的尺寸过大导致溢出时返回错误
+pub(super) fn buffer_size(width: u32, height: u32, channels: usize) -> Result<usize, ImageError> {
+    (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|count| count.checked_mul(channels))
+        .ok_or_else(|| ImageError::DecodeError(format!("image too large: {}x{}", width, height)))
+}
+
+fn decode_png(bytes: &[u8]) -> Result<ImageData, ImageError> {
+    let mut decoder = png::Decoder::new(bytes);
+    // 展开调色板和低位深，16 位降为 8 位
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    let data = &buffer[..info.buffer_size()];
+
+    let mut pixels = Vec::with_capacity(buffer_size(info.width, info.height, 4)?);
+    match info.color_type {
+        png::ColorType::Rgba => pixels.extend_from_slice(data),
+        png::ColorType::Rgb => {
+            for rgb in data.chunks_exact(3) {
+                pixels.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+            }
+        }
+        png::ColorType::GrayscaleAlpha => {
+            for ga in data.chunks_exact(2) {
+                pixels.extend_from_slice(&[ga[0], ga[0], ga[0], ga[1]]);
+            }
+        }
+        png::ColorType::Grayscale => {
+            for &g in data {
+                pixels.extend_from_slice(&[g, g, g, 255]);
+            }
+        }
+        png::ColorType::Indexed => {
+            return Err(ImageError::Unsupported("indexed PNG was not expanded".to_string()));
+        }
+    }
+
+    ImageData::new(info.width, info.height, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_empty_image_as_transparent() {
+        let image = ImageData::new(0, 0, Vec::new()).unwrap();
+        assert_eq!(image.sample(0.0, 0.0, ScaleFilter::Nearest), [0; 4]);
+        assert_eq!(image.sample(3.0, 1.0, ScaleFilter::Bilinear), [0; 4]);
+    }
+
+    #[test]
+    fn samples_with_clamping_and_interpolation() {
+        let image = ImageData::new(2, 1, vec![0, 0, 0, 255, 200, 100, 50, 255]).unwrap();
+        assert_eq!(image.sample(-5.0, 9.0, ScaleFilter::Nearest), [0, 0, 0, 255]);
+        assert_eq!(image.sample(1.5, 0.5, ScaleFilter::Nearest), [200, 100, 50, 255]);
+        assert_eq!(image.sample(1.0, 0.5, ScaleFilter::Bilinear), [100, 50, 25, 255]);
+    }
+
+    #[test]
+    fn rejects_mismatched_buffers_and_unknown_formats() {
+        assert!(ImageData::new(2, 2, vec![0; 15]).is_err());
+        assert!(ImageData::new(u32::MAX, u32::MAX, Vec::new()).is_err());
+        assert!(matches!(ImageData::decode(b"GIF89a"), Err(ImageError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,193 @@
+use super::{buffer_size, ImageData, ImageError};
+
+// Netpbm 头部和 ASCII 数据的分词器，跳过空白和 '#' 注释
+struct Tokens<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn skip_whitespace(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b == b'#' {
+                while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next_number(&mut self) -> Result<u32, ImageError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| ImageError::DecodeError("invalid number in PPM file".to_string()))
+    }
+
+    // P1 格式中的像素可以不用空白分隔
+    fn next_bit(&mut self) -> Result<u32, ImageError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(0)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(1)
+            }
+            _ => Err(ImageError::DecodeError("invalid bit in PBM file".to_string())),
+        }
+    }
+}
+
+// 支持 PBM/PGM/PPM 的 ASCII (P1-P3) 和二进制 (P4-P6) 格式
+pub(super) fn decode(bytes: &[u8]) -> Result<ImageData, ImageError> {
+    let kind = bytes[1];
+    let mut tokens = Tokens { bytes, pos: 2 };
+    let width = tokens.next_number()?;
+    let height = tokens.next_number()?;
+    let max_value = if kind == b'1' || kind == b'4' { 1 } else { tokens.next_number()? };
+
+    if width == 0 || height == 0 || max_value == 0 || max_value > 65535 {
+        return Err(ImageError::DecodeError("invalid PPM header".to_string()));
+    }
+
+    let channels = match kind {
+        b'3' | b'6' => 3,
+        _ => 1,
+    };
+    let sample_count = buffer_size(width, height, channels)?;
+    // 二进制数据前只有一个空白字符；ASCII 格式中每个样本至少占一个字节
+    let start = tokens.pos + 1;
+    let sample_size = if max_value > 255 { 2 } else { 1 };
+    let stride = (width as usize).div_ceil(8);
+    let data_size = match kind {
+        b'4' => stride.checked_mul(height as usize),
+        b'5' | b'6' => sample_count.checked_mul(sample_size),
+        _ => Some(sample_count),
+    };
+    if data_size.is_none_or(|size| size > bytes.len().saturating_sub(tokens.pos)) {
+        return Err(ImageError::DecodeError("truncated PPM file".to_string()));
+    }
+    let mut samples = Vec::with_capacity(sample_count);
+
+    match kind {
+        b'1' => {
+            for _ in 0..sample_count {
+                samples.push(tokens.next_bit()?);
+            }
+        }
+        b'2' | b'3' => {
+            for _ in 0..sample_count {
+                samples.push(tokens.next_number()?);
+            }
+        }
+        b'4' => {
+            let data = bytes
+                .get(start..start + stride * height as usize)
+                .ok_or_else(|| ImageError::DecodeError("truncated PBM file".to_string()))?;
+            for y in 0..height as usize {
+                for x in 0..width as usize {
+                    samples.push(((data[y * stride + x / 8] >> (7 - x % 8)) & 1) as u32);
+                }
+            }
+        }
+        _ => {
+            let data = bytes
+                .get(start..start + sample_count * sample_size)
+                .ok_or_else(|| ImageError::DecodeError("truncated PPM file".to_string()))?;
+            for chunk in data.chunks_exact(sample_size) {
+                samples.push(match chunk {
+                    [value] => *value as u32,
+                    _ => u16::from_be_bytes([chunk[0], chunk[1]]) as u32,
+                });
+            }
+        }
+    }
+
+    let scale = |value: u32| ((value.min(max_value) * 255 + max_value / 2) / max_value) as u8;
+    let mut pixels = Vec::with_capacity(buffer_size(width, height, 4)?);
+    for pixel in samples.chunks_exact(channels) {
+        let rgb = match (kind, pixel) {
+            // PBM 中 1 表示黑色
+            (b'1' | b'4', [bit]) => {
+                let value = if *bit == 1 { 0 } else { 255 };
+                [value, value, value]
+            }
+            (_, [gray]) => [scale(*gray); 3],
+            (_, rgb) => [scale(rgb[0]), scale(rgb[1]), scale(rgb[2])],
+        };
+        pixels.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+    }
+
+    ImageData::new(width, height, pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels(image: &ImageData) -> Vec<[u8; 4]> {
+        image.pixels().chunks_exact(4).map(|p| [p[0], p[1], p[2], p[3]]).collect()
+    }
+
+    #[test]
+    fn decodes_ascii_formats() {
+        let image = decode(b"P3\n# comment\n2 1\n# max\n255\n255 0 0  0 0 255\n").unwrap();
+        assert_eq!(pixels(&image), [[255, 0, 0, 255], [0, 0, 255, 255]]);
+        // 最大值不是 255 时按比例扩展
+        let image = decode(b"P2 2 1 15 0 15").unwrap();
+        assert_eq!(pixels(&image), [[0, 0, 0, 255], [255, 255, 255, 255]]);
+        // P1 中的像素可以不用空白分隔，1 表示黑色
+        let image = decode(b"P1\n3 1\n101").unwrap();
+        assert_eq!(pixels(&image), [[0, 0, 0, 255], [255, 255, 255, 255], [0, 0, 0, 255]]);
+    }
+
+    #[test]
+    fn decodes_binary_formats() {
+        let mut bytes = b"P6\n1 2\n255\n".to_vec();
+        bytes.extend([10, 20, 30, 40, 50, 60]);
+        assert_eq!(pixels(&decode(&bytes).unwrap()), [[10, 20, 30, 255], [40, 50, 60, 255]]);
+
+        // 16 位样本按大端存储
+        let mut bytes = b"P5 1 1 65535\n".to_vec();
+        bytes.extend([0x80, 0x00]);
+        assert_eq!(pixels(&decode(&bytes).unwrap()), [[128, 128, 128, 255]]);
+
+        // P4 每行按字节对齐
+        let mut bytes = b"P4 3 2\n".to_vec();
+        bytes.extend([0b0100_0000, 0b1010_0000]);
+        let black = [0, 0, 0, 255];
+        let white = [255, 255, 255, 255];
+        assert_eq!(pixels(&decode(&bytes).unwrap()), [white, black, white, black, white, black]);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        for bytes in [
+            &b"P3 0 1 255"[..],
+            b"P3 1 1 0 0 0 0",
+            b"P3 1 1 70000 0 0 0",
+            b"P3 1 1 255 0 0",
+            b"P3 x 1 255 0 0 0",
+            b"P2 2 2 255 1 2 3 ",
+            b"P1 2 1 12",
+            b"P6 1 1 255\n\x01\x02",
+            b"P4 9 1\n\x00",
+            b"P6 4294967295 4294967295 255\n",
+            b"P3",
+        ] {
+            assert!(decode(bytes).is_err(), "{:?}", String::from_utf8_lossy(bytes));
+        }
+    }
+}
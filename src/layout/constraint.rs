@@ -0,0 +1,279 @@
+// 约束布局中组件的属性，right、bottom 和中心点由位置和尺寸推导
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attribute {
+    Left,
+    Top,
+    Width,
+    Height,
+    Right,
+    Bottom,
+    CenterX,
+    CenterY,
+}
+
+impl Attribute {
+    pub fn parse(value: &str) -> Option<Attribute> {
+        match value {
+            "left" | "x" => Some(Attribute::Left),
+            "top" | "y" => Some(Attribute::Top),
+            "width" => Some(Attribute::Width),
+            "height" => Some(Attribute::Height),
+            "right" => Some(Attribute::Right),
+            "bottom" => Some(Attribute::Bottom),
+            "center_x" | "centerx" => Some(Attribute::CenterX),
+            "center_y" | "centery" => Some(Attribute::CenterY),
+            _ => None,
+        }
+    }
+}
+
+// 约束的强度，无法同时满足时优先满足强度高的约束，required 必须满足
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strength {
+    Required,
+    Strong,
+    Medium,
+    Weak,
+}
+
+impl Strength {
+    pub fn parse(value: &str) -> Option<Strength> {
+        match value {
+            "required" => Some(Strength::Required),
+            "strong" => Some(Strength::Strong),
+            "medium" => Some(Strength::Medium),
+            "weak" => Some(Strength::Weak),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn solver_strength(self) -> kasuari::Strength {
+        match self {
+            Strength::Required => kasuari::Strength::REQUIRED,
+            Strength::Strong => kasuari::Strength::STRONG,
+            Strength::Medium => kasuari::Strength::MEDIUM,
+            Strength::Weak => kasuari::Strength::WEAK,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    Equal,
+    LessOrEqual,
+    GreaterOrEqual,
+}
+
+// 表达式中的一项：系数 × 组件名.属性
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintTerm {
+    pub widget: String,
+    pub attribute: Attribute,
+    pub coefficient: f32,
+}
+
+// 线性表达式：各项之和加上常数
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConstraintExpr {
+    pub terms: Vec<ConstraintTerm>,
+    pub constant: f32,
+}
+
+// 一条线性约束，例如 "ok.right == parent.right - 8" 或 "label.width >= 120 weak"。
+// 组件名 parent 表示约束布局容器本身，强度写在末尾，省略时为 required
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutConstraint {
+    pub left: ConstraintExpr,
+    pub relation: Relation,
+    pub right: ConstraintExpr,
+    pub strength: Strength,
+}
+
+impl LayoutConstraint {
+    pub fn parse(value: &str) -> Option<LayoutConstraint> {
+        let mut value = value.trim();
+        let mut strength = Strength::Required;
+        if let Some((rest, last)) = value.rsplit_once(char::is_whitespace) {
+            if let Some(parsed) = Strength::parse(last) {
+                strength = parsed;
+                value = rest;
+            }
+        }
+
+        let (relation, index) = [("==", Relation::Equal), ("<=", Relation::LessOrEqual), (">=", Relation::GreaterOrEqual)]
+            .into_iter()
+            .find_map(|(operator, relation)| value.find(operator).map(|index| (relation, index)))?;
+        let (left, right) = (&value[..index], &value[index + 2..]);
+        Some(LayoutConstraint {
+            left: parse_expr(left)?,
+            relation,
+            right: parse_expr(right)?,
+            strength,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Number(f32),
+    Reference(&'a str, Attribute),
+    Operator(char),
+}
+
+// 不支持括号
+fn is_operator(c: char) -> bool {
+    matches!(c, '+' | '-' | '*' | '/')
+}
+
+fn tokenize(value: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = value.trim_start();
+    while let Some(c) = rest.chars().next() {
+        if is_operator(c) {
+            tokens.push(Token::Operator(c));
+            rest = rest[1..].trim_start();
+            continue;
+        }
+        let end = rest
+            .find(|c: char| c.is_whitespace() || is_operator(c))
+            .unwrap_or(rest.len());
+        let word = &rest[..end];
+        let token = if c.is_ascii_digit() || c == '.' {
+            Token::Number(word.parse().ok().filter(|number: &f32| number.is_finite())?)
+        } else {
+            // 名字为空的子组件不能被引用
+            let (widget, attribute) = word.split_once('.').filter(|(widget, _)| !widget.is_empty())?;
+            Token::Reference(widget, Attribute::parse(attribute)?)
+        };
+        tokens.push(token);
+        rest = rest[end..].trim_start();
+    }
+    Some(tokens)
+}
+
+// 表达式为若干项的和或差，每项是数字和至多一个组件属性的乘积，可以除以数字
+fn parse_expr(value: &str) -> Option<ConstraintExpr> {
+    let tokens = tokenize(value)?;
+    let mut expr = ConstraintExpr::default();
+    let mut tokens = tokens.into_iter().peekable();
+    let mut sign = 1.0;
+    loop {
+        // 一项开头的正负号
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = tokens.peek() {
+            if *operator == '-' {
+                sign = -sign;
+            }
+            tokens.next();
+        }
+
+        let mut coefficient = sign;
+        let mut reference = None;
+        let mut expect_factor = true;
+        let mut divide = false;
+        while let Some(token) = tokens.peek() {
+            match (token, expect_factor) {
+                (Token::Number(number), true) => {
+                    coefficient = if divide { coefficient / number } else { coefficient * number };
+                }
+                (Token::Reference(widget, attribute), true) if reference.is_none() && !divide => {
+                    reference = Some((widget.to_string(), *attribute));
+                }
+                (Token::Operator(operator @ ('*' | '/')), false) => divide = *operator == '/',
+                (Token::Operator('+' | '-'), false) => break,
+                _ => return None,
+            }
+            expect_factor = !expect_factor;
+            tokens.next();
+        }
+        if expect_factor || !coefficient.is_finite() {
+            return None;
+        }
+
+        match reference {
+            Some((widget, attribute)) => expr.terms.push(ConstraintTerm { widget, attribute, coefficient }),
+            None => expr.constant += coefficient,
+        }
+        if tokens.peek().is_none() {
+            return Some(expr);
+        }
+        sign = 1.0;
+    }
+}
+
+#[derive(Debug)]
+pub enum ConstraintError {
+    InvalidSyntax(String),
+    UnknownWidget(String),
+    // 子组件的名字与已有的子组件重复或是 parent
+    DuplicateWidget(String),
+    // required 约束与已有的约束冲突
+    Unsatisfiable(String),
+    // 编辑变量重复添加、强度为 required 或不存在
+    InvalidEdit(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(widget: &str, attribute: Attribute, coefficient: f32) -> ConstraintTerm {
+        ConstraintTerm { widget: widget.to_string(), attribute, coefficient }
+    }
+
+    #[test]
+    fn parses_relation_and_strength() {
+        let constraint = LayoutConstraint::parse("ok.right == parent.right - 8").unwrap();
+        assert_eq!(constraint.relation, Relation::Equal);
+        assert_eq!(constraint.strength, Strength::Required);
+        assert_eq!(constraint.left.terms, vec![term("ok", Attribute::Right, 1.0)]);
+        assert_eq!(constraint.right.terms, vec![term("parent", Attribute::Right, 1.0)]);
+        assert_eq!(constraint.right.constant, -8.0);
+
+        let constraint = LayoutConstraint::parse("  label.width >= 120 weak ").unwrap();
+        assert_eq!((constraint.relation, constraint.strength), (Relation::GreaterOrEqual, Strength::Weak));
+        assert_eq!(constraint.right.constant, 120.0);
+        assert_eq!(LayoutConstraint::parse("a.x<=b.x").unwrap().relation, Relation::LessOrEqual);
+    }
+
+    #[test]
+    fn parses_coefficients_and_signs() {
+        let expr = parse_expr("2 * a.width / 4 - -b.center_x + 3 - 0.5").unwrap();
+        assert_eq!(expr.terms, vec![term("a", Attribute::Width, 0.5), term("b", Attribute::CenterX, 1.0)]);
+        assert_eq!(expr.constant, 2.5);
+
+        let expr = parse_expr("-parent.width/2").unwrap();
+        assert_eq!(expr.terms, vec![term("parent", Attribute::Width, -0.5)]);
+        assert_eq!(parse_expr("a.x").unwrap().constant, 0.0);
+        assert_eq!(parse_expr("a.y").unwrap().terms[0].attribute, Attribute::Top);
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for value in [
+            "",
+            "a.x +",
+            "* a.x",
+            "a.x b.x",
+            "2 3",
+            "a.x * b.x",
+            "120 / a.x",
+            "a.x / 0",
+            "a.depth",
+            "a",
+            ".left",
+            "(a.x)",
+            "1e+5",
+            "1e99",
+            "a.x + 1e39",
+        ] {
+            assert_eq!(parse_expr(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_constraints() {
+        for value in ["a.x", "a.x = b.x", "== 5", "a.x ==", "a.x == b.x == 3", "a.x == 5 weakest", "a.x => 5"] {
+            assert_eq!(LayoutConstraint::parse(value), None, "{:?}", value);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use super::{Alignment, Length, Padding};
+
+// 子组件在交叉轴上的对齐方式，Auto 沿用容器的对齐方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlignSelf {
+    Auto,
+    Start,
+    Center,
+    End,
+    // 拉伸到容器内容区域的宽度（垂直布局）或高度（水平布局），受最小、最大尺寸限制
+    Stretch,
+}
+
+impl From<Alignment> for AlignSelf {
+    fn from(alignment: Alignment) -> Self {
+        match alignment {
+            Alignment::Start => AlignSelf::Start,
+            Alignment::Center => AlignSelf::Center,
+            Alignment::End => AlignSelf::End,
+        }
+    }
+}
+
+// 子组件的弹性布局参数，语义与 CSS flexbox 相同：
+// 先按 basis 确定主轴尺寸，剩余空间按 grow 的比例分配，
+// 空间不足时按 shrink 与 basis 的乘积的比例收缩
+#[derive(Debug, Clone, Copy)]
+pub struct FlexItem {
+    pub grow: f32,
+    pub shrink: f32,
+    // 主轴方向的初始尺寸，None 表示使用组件的首选尺寸
+    pub basis: Option<f32>,
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+    pub align_self: AlignSelf,
+    // 组件外侧留出的空白，不计入组件的尺寸和最小、最大尺寸
+    pub margin: Padding,
+    // 固定或按容器内容区域百分比计算的尺寸，None 表示使用组件的首选尺寸
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        Self {
+            grow: 0.0,
+            shrink: 1.0,
+            basis: None,
+            min_width: 0.0,
+            max_width: f32::INFINITY,
+            min_height: 0.0,
+            max_height: f32::INFINITY,
+            align_self: AlignSelf::Auto,
+            margin: Padding::all(0.0),
+            width: None,
+            height: None,
+        }
+    }
+}
+
+impl FlexItem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow.max(0.0);
+        self
+    }
+
+    pub fn with_shrink(mut self, shrink: f32) -> Self {
+        self.shrink = shrink.max(0.0);
+        self
+    }
+
+    pub fn with_basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis.max(0.0));
+        self
+    }
+
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+
+    pub fn with_min_height(mut self, min_height: f32) -> Self {
+        self.min_height = min_height;
+        self
+    }
+
+    pub fn with_max_height(mut self, max_height: f32) -> Self {
+        self.max_height = max_height;
+        self
+    }
+
+    pub fn with_align_self(mut self, align_self: AlignSelf) -> Self {
+        self.align_self = align_self;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: Padding) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_width(mut self, width: Length) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_height(mut self, height: Length) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    // 与 CSS 一样，最小尺寸大于最大尺寸时以最小尺寸为准
+    pub fn clamp_width(&self, width: f32) -> f32 {
+        width.min(self.max_width).max(self.min_width)
+    }
+
+    pub fn clamp_height(&self, height: f32) -> f32 {
+        height.min(self.max_height).max(self.min_height)
+    }
+}
+
+// 主轴上参与分配的一项
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FlexLine {
+    pub basis: f32,
+    pub grow: f32,
+    pub shrink: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl FlexLine {
+    fn clamp(&self, size: f32) -> f32 {
+        size.min(self.max).max(self.min)
+    }
+
+    // 不伸缩时的尺寸
+    pub fn clamp_basis(&self) -> f32 {
+        self.clamp(self.basis)
+    }
+}
+
+// 按 CSS Flexible Box 的“解析弹性长度”算法分配主轴尺寸：
+// 每轮按比例分配剩余空间，违反最小或最大尺寸的项被冻结在边界上，其余项重新分配
+pub(crate) fn resolve_flex(available: f32, items: &[FlexLine]) -> Vec<f32> {
+    let hypothetical: f32 = items.iter().map(|item| item.clamp(item.basis)).sum();
+    let growing = hypothetical < available;
+
+    let mut sizes: Vec<f32> = items.iter().map(|item| item.clamp(item.basis)).collect();
+    // 不能伸缩的项直接冻结在假设尺寸上
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .map(|item| {
+            let factor = if growing { item.grow } else { item.shrink };
+            factor == 0.0
+                || (growing && item.basis > item.clamp(item.basis))
+                || (!growing && item.basis < item.clamp(item.basis))
+        })
+        .collect();
+
+    let free_space = |sizes: &[f32], frozen: &[bool]| {
+        available
+            - items
+                .iter()
+                .zip(sizes.iter().zip(frozen))
+                .map(|(item, (&size, &frozen))| if frozen { size } else { item.basis })
+                .sum::<f32>()
+    };
+    let initial_free = free_space(&sizes, &frozen);
+
+    while frozen.iter().any(|frozen| !frozen) {
+        let mut free = free_space(&sizes, &frozen);
+        // 伸缩系数之和小于 1 时只分配相应比例的空间
+        let factor_sum: f32 = items
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, &frozen)| !frozen)
+            .map(|(item, _)| if growing { item.grow } else { item.shrink })
+            .sum();
+        if factor_sum < 1.0 {
+            let limited = initial_free * factor_sum;
+            if limited.abs() < free.abs() {
+                free = limited;
+            }
+        }
+
+        if growing {
+            for (index, item) in items.iter().enumerate().filter(|(index, _)| !frozen[*index]) {
+                sizes[index] = item.basis + free * item.grow / factor_sum;
+            }
+        } else {
+            let scaled_sum: f32 = items
+                .iter()
+                .zip(&frozen)
+                .filter(|(_, &frozen)| !frozen)
+                .map(|(item, _)| item.shrink * item.basis)
+                .sum();
+            for (index, item) in items.iter().enumerate().filter(|(index, _)| !frozen[*index]) {
+                sizes[index] = if scaled_sum > 0.0 {
+                    item.basis + free * item.shrink * item.basis / scaled_sum
+                } else {
+                    item.basis
+                };
+            }
+        }
+
+        // 限制在最小、最大尺寸内，按总违反量决定冻结哪些项
+        let mut violation = 0.0;
+        let mut clamped = vec![0.0; items.len()];
+        for (index, item) in items.iter().enumerate().filter(|(index, _)| !frozen[*index]) {
+            let size = item.clamp(sizes[index]);
+            clamped[index] = size - sizes[index];
+            violation += clamped[index];
+            sizes[index] = size;
+        }
+        for index in 0..items.len() {
+            if frozen[index] {
+                continue;
+            }
+            frozen[index] = if violation == 0.0 {
+                true
+            } else if violation > 0.0 {
+                clamped[index] > 0.0
+            } else {
+                clamped[index] < 0.0
+            };
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(basis: f32, grow: f32, shrink: f32) -> FlexLine {
+        FlexLine { basis, grow, shrink, min: 0.0, max: f32::INFINITY }
+    }
+
+    fn assert_sizes(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual_size, expected_size) in actual.iter().zip(expected) {
+            assert!((actual_size - expected_size).abs() < 1e-3, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn grows_in_proportion_to_grow_factors() {
+        let sizes = resolve_flex(300.0, &[line(50.0, 1.0, 1.0), line(50.0, 2.0, 1.0)]);
+        assert_sizes(sizes, &[50.0 + 200.0 / 3.0, 50.0 + 400.0 / 3.0]);
+    }
+
+    #[test]
+    fn freezes_item_at_max_and_redistributes() {
+        let capped = FlexLine { max: 50.0, ..line(0.0, 1.0, 1.0) };
+        let sizes = resolve_flex(300.0, &[capped, line(0.0, 1.0, 1.0)]);
+        assert_sizes(sizes, &[50.0, 250.0]);
+    }
+
+    #[test]
+    fn shrinks_in_proportion_to_shrink_times_basis() {
+        let sizes = resolve_flex(150.0, &[line(200.0, 0.0, 1.0), line(100.0, 0.0, 1.0)]);
+        assert_sizes(sizes, &[100.0, 50.0]);
+    }
+
+    #[test]
+    fn freezes_item_at_min_while_shrinking() {
+        let floored = FlexLine { min: 80.0, ..line(100.0, 0.0, 1.0) };
+        let sizes = resolve_flex(100.0, &[floored, line(100.0, 0.0, 1.0)]);
+        assert_sizes(sizes, &[80.0, 20.0]);
+    }
+
+    #[test]
+    fn distributes_only_part_of_free_space_when_factors_sum_below_one() {
+        let sizes = resolve_flex(200.0, &[line(0.0, 0.5, 1.0), line(0.0, 0.0, 1.0)]);
+        assert_sizes(sizes, &[100.0, 0.0]);
+    }
+
+    #[test]
+    fn inflexible_items_keep_clamped_basis() {
+        // 最小尺寸大于最大尺寸时以最小尺寸为准
+        let conflicting = FlexLine { min: 40.0, max: 30.0, ..line(10.0, 0.0, 0.0) };
+        let sizes = resolve_flex(500.0, &[conflicting, line(60.0, 0.0, 0.0)]);
+        assert_sizes(sizes, &[40.0, 60.0]);
+        let sizes = resolve_flex(50.0, &[line(100.0, 1.0, 0.0)]);
+        assert_sizes(sizes, &[100.0]);
+    }
+
+    #[test]
+    fn handles_empty_and_zero_basis_items() {
+        assert!(resolve_flex(100.0, &[]).is_empty());
+        let sizes = resolve_flex(0.0, &[line(0.0, 0.0, 1.0), line(0.0, 0.0, 1.0)]);
+        assert_sizes(sizes, &[0.0, 0.0]);
+    }
+}
@@ -0,0 +1,214 @@
+use super::{Alignment, Padding};
+
+// 网格的行或列轨道
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Track {
+    // 固定的像素尺寸
+    Px(f32),
+    // 按轨道中子组件的首选尺寸
+    Auto,
+    // 按比例分配固定和自动轨道之外的剩余空间
+    Fr(f32),
+}
+
+impl Track {
+    // 解析 "120"、"120px"、"auto"、"1fr" 这样的轨道定义
+    pub fn parse(value: &str) -> Option<Track> {
+        let value = value.trim();
+        if value == "auto" {
+            return Some(Track::Auto);
+        }
+        if let Some(fr) = value.strip_suffix("fr") {
+            let fr: f32 = if fr.is_empty() { 1.0 } else { fr.parse().ok()? };
+            return (fr > 0.0 && fr.is_finite()).then_some(Track::Fr(fr));
+        }
+        let px: f32 = value.strip_suffix("px").unwrap_or(value).parse().ok()?;
+        (px >= 0.0 && px.is_finite()).then_some(Track::Px(px))
+    }
+}
+
+// 子组件在网格中的位置，行列从 0 开始。
+// 对齐方式为 None 时填满单元格，否则按首选尺寸在单元格内对齐
+#[derive(Debug, Clone, Copy)]
+pub struct GridCell {
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+    pub align_x: Option<Alignment>,
+    pub align_y: Option<Alignment>,
+    // 子组件与单元格边缘之间的空白
+    pub margin: Padding,
+}
+
+impl GridCell {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+            align_x: None,
+            align_y: None,
+            margin: Padding::all(0.0),
+        }
+    }
+
+    pub fn with_span(mut self, row_span: usize, column_span: usize) -> Self {
+        self.row_span = row_span.max(1);
+        self.column_span = column_span.max(1);
+        self
+    }
+
+    pub fn with_align_x(mut self, align: Alignment) -> Self {
+        self.align_x = Some(align);
+        self
+    }
+
+    pub fn with_align_y(mut self, align: Alignment) -> Self {
+        self.align_y = Some(align);
+        self
+    }
+
+    pub fn with_margin(mut self, margin: Padding) -> Self {
+        self.margin = margin;
+        self
+    }
+}
+
+// 子组件在某个方向上占据的轨道范围和首选尺寸
+pub(crate) struct TrackItem {
+    pub start: usize,
+    pub span: usize,
+    pub size: f32,
+}
+
+// 计算各轨道的尺寸。available 为 None 时表示测量首选尺寸，
+// 此时比例轨道按内容计算，并保持彼此之间的比例
+pub(crate) fn resolve_tracks(tracks: &[Track], items: &[TrackItem], gap: f32, available: Option<f32>) -> Vec<f32> {
+    let mut sizes: Vec<f32> = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Px(px) => *px,
+            _ => 0.0,
+        })
+        .collect();
+    // 按内容确定尺寸的轨道
+    let sized_by_content = |track: &Track| match track {
+        Track::Px(_) => false,
+        Track::Auto => true,
+        Track::Fr(_) => available.is_none(),
+    };
+
+    // 先处理只占一个轨道的子组件，再把跨轨道的子组件放不下的部分平均分给其中按内容确定尺寸的轨道
+    for item in items.iter().filter(|item| item.span == 1) {
+        if sized_by_content(&tracks[item.start]) {
+            sizes[item.start] = sizes[item.start].max(item.size);
+        }
+    }
+    for item in items.iter().filter(|item| item.span > 1) {
+        let range = item.start..item.start + item.span;
+        let spanned: f32 = sizes[range.clone()].iter().sum::<f32>() + gap * (item.span - 1) as f32;
+        let flexible: Vec<usize> = range.filter(|&index| sized_by_content(&tracks[index])).collect();
+        if item.size > spanned && !flexible.is_empty() {
+            let extra = (item.size - spanned) / flexible.len() as f32;
+            for index in flexible {
+                sizes[index] += extra;
+            }
+        }
+    }
+
+    let fr_sum: f32 = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fr(fr) => *fr,
+            _ => 0.0,
+        })
+        .sum();
+    if fr_sum == 0.0 {
+        return sizes;
+    }
+    let unit = match available {
+        Some(available) => {
+            let fixed: f32 = tracks
+                .iter()
+                .zip(&sizes)
+                .filter(|(track, _)| !matches!(track, Track::Fr(_)))
+                .map(|(_, size)| size)
+                .sum();
+            let gaps = gap * tracks.len().saturating_sub(1) as f32;
+            (available - fixed - gaps).max(0.0) / fr_sum
+        }
+        // 取能容纳所有比例轨道内容的最小单位
+        None => tracks
+            .iter()
+            .zip(&sizes)
+            .filter_map(|(track, size)| match track {
+                Track::Fr(fr) => Some(size / fr),
+                _ => None,
+            })
+            .fold(0.0, f32::max),
+    };
+    for (track, size) in tracks.iter().zip(&mut sizes) {
+        if let Track::Fr(fr) = track {
+            *size = unit * fr;
+        }
+    }
+    sizes
+}
+
+// 每个轨道的起点，相对于网格内容区域
+pub(crate) fn track_offsets(sizes: &[f32], gap: f32) -> Vec<f32> {
+    let mut offsets = Vec::with_capacity(sizes.len());
+    let mut offset = 0.0;
+    for size in sizes {
+        offsets.push(offset);
+        offset += size + gap;
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_track_definitions() {
+        assert_eq!(Track::parse("120"), Some(Track::Px(120.0)));
+        assert_eq!(Track::parse(" 12.5px "), Some(Track::Px(12.5)));
+        assert_eq!(Track::parse("0"), Some(Track::Px(0.0)));
+        assert_eq!(Track::parse("auto"), Some(Track::Auto));
+        assert_eq!(Track::parse("fr"), Some(Track::Fr(1.0)));
+        assert_eq!(Track::parse("2.5fr"), Some(Track::Fr(2.5)));
+    }
+
+    #[test]
+    fn rejects_invalid_tracks() {
+        for value in ["", "px", "-1", "-1px", "0fr", "-2fr", "abc", "1 fr", "10em", "AUTO", "inf", "NaN", "infpx", "inffr"] {
+            assert_eq!(Track::parse(value), None, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn splits_remaining_space_between_fr_tracks() {
+        let tracks = [Track::Px(100.0), Track::Auto, Track::Fr(1.0), Track::Fr(3.0)];
+        let items = [TrackItem { start: 1, span: 1, size: 40.0 }];
+        let sizes = resolve_tracks(&tracks, &items, 10.0, Some(400.0));
+        assert_eq!(sizes, vec![100.0, 40.0, 57.5, 172.5]);
+        assert_eq!(track_offsets(&sizes, 10.0), vec![0.0, 110.0, 160.0, 227.5]);
+    }
+
+    #[test]
+    fn spreads_spanning_item_over_content_sized_tracks() {
+        let tracks = [Track::Px(20.0), Track::Auto, Track::Auto];
+        let items = [TrackItem { start: 0, span: 3, size: 100.0 }];
+        assert_eq!(resolve_tracks(&tracks, &items, 10.0, None), vec![20.0, 30.0, 30.0]);
+    }
+
+    #[test]
+    fn keeps_fr_ratio_when_measuring() {
+        let tracks = [Track::Fr(1.0), Track::Fr(2.0)];
+        let items = [TrackItem { start: 0, span: 1, size: 50.0 }, TrackItem { start: 1, span: 1, size: 60.0 }];
+        assert_eq!(resolve_tracks(&tracks, &items, 0.0, None), vec![50.0, 100.0]);
+    }
+}
@@ -1,4 +1,20 @@
-#[derive(Debug, Clone, Copy)]
+mod constraint;
+mod flex;
+mod grid;
+mod responsive;
+mod split;
+mod stack;
+
+pub use constraint::{Attribute, ConstraintError, ConstraintExpr, ConstraintTerm, LayoutConstraint, Relation, Strength};
+pub use flex::{AlignSelf, FlexItem};
+pub(crate) use flex::{resolve_flex, FlexLine};
+pub use grid::{GridCell, Track};
+pub use responsive::Breakpoint;
+pub use split::SplitPane;
+pub(crate) use grid::{resolve_tracks, track_offsets, TrackItem};
+pub use stack::{Anchor, StackItem};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -6,7 +22,80 @@ pub struct Rect {
     pub height: f32,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Rect {
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        x >= self.x
+            && x <= self.x + self.width
+            && y >= self.y
+            && y <= self.y + self.height
+    }
+
+    // 在 bounds 中水平镜像，用于从右到左的布局
+    pub fn mirror(&self, bounds: Rect) -> Rect {
+        Rect {
+            x: bounds.x * 2.0 + bounds.width - self.x - self.width,
+            ..*self
+        }
+    }
+
+    // 向内收缩，例如去掉内边距或外边距，尺寸不小于 0
+    pub fn inset(&self, padding: Padding) -> Rect {
+        Rect {
+            x: self.x + padding.left,
+            y: self.y + padding.top,
+            width: (self.width - padding.horizontal()).max(0.0),
+            height: (self.height - padding.vertical()).max(0.0),
+        }
+    }
+}
+
+// 测量组件时的尺寸范围，最大值为无穷大表示不限制
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constraints {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+
+impl Constraints {
+    pub fn unbounded() -> Self {
+        Self {
+            min_width: 0.0,
+            max_width: f32::INFINITY,
+            min_height: 0.0,
+            max_height: f32::INFINITY,
+        }
+    }
+
+    // 不超过给定的尺寸
+    pub fn loose(max_width: f32, max_height: f32) -> Self {
+        Self {
+            max_width,
+            max_height,
+            ..Self::unbounded()
+        }
+    }
+
+    // 宽度固定、高度不限，用于询问组件在某个宽度下的高度
+    pub fn tight_width(width: f32) -> Self {
+        Self {
+            min_width: width,
+            max_width: width,
+            ..Self::unbounded()
+        }
+    }
+
+    pub fn clamp_width(&self, width: f32) -> f32 {
+        width.min(self.max_width).max(self.min_width)
+    }
+
+    pub fn clamp_height(&self, height: f32) -> f32 {
+        height.min(self.max_height).max(self.min_height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Padding {
     pub left: f32,
     pub right: f32,
@@ -23,6 +112,44 @@ impl Padding {
             bottom: value,
         }
     }
+
+    // 左右之和
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    // 上下之和
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+// 组件的宽度或高度：固定的像素，或父容器内容区域的百分比
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Px(f32),
+    Percent(f32),
+}
+
+impl Length {
+    // 解析 "120"、"120px"、"50%" 这样的尺寸
+    pub fn parse(value: &str) -> Option<Length> {
+        let value = value.trim();
+        if let Some(percent) = value.strip_suffix('%') {
+            let percent: f32 = percent.parse().ok()?;
+            return (percent >= 0.0 && percent.is_finite()).then_some(Length::Percent(percent));
+        }
+        let px: f32 = value.strip_suffix("px").unwrap_or(value).parse().ok()?;
+        (px >= 0.0 && px.is_finite()).then_some(Length::Px(px))
+    }
+
+    // 按参照尺寸换算成像素，参照尺寸未知时（例如测量首选尺寸）百分比没有确定的值
+    pub fn resolve(&self, base: Option<f32>) -> Option<f32> {
+        match self {
+            Length::Px(px) => Some(*px),
+            Length::Percent(percent) => base.map(|base| base * percent / 100.0),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -36,4 +163,82 @@ pub enum Alignment {
 pub enum Direction {
     Horizontal,
     Vertical,
-}
\ No newline at end of file
+}
+
+// 界面的布局方向。从右到左时水平排列从右边开始，Start 和 End 对齐互换，
+// 有方向的图标和箭头也随之镜像
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl LayoutDirection {
+    pub fn parse(value: &str) -> Option<LayoutDirection> {
+        match value {
+            "ltr" => Some(LayoutDirection::Ltr),
+            "rtl" => Some(LayoutDirection::Rtl),
+            _ => None,
+        }
+    }
+
+    pub fn is_rtl(&self) -> bool {
+        *self == LayoutDirection::Rtl
+    }
+}
+
+// 弹出层相对锚点的位置
+#[derive(Debug, Clone, Copy)]
+pub enum Placement {
+    Below,
+    // 在锚点下方、与锚点右边缘对齐，用于从右到左的布局
+    BelowEnd,
+    Right,
+    Left,
+}
+
+// 计算弹出层的位置：超出边界时翻转到锚点的另一侧，最后再限制在边界内
+pub fn place_popup(anchor: Rect, width: f32, height: f32, bounds: Rect, placement: Placement) -> Rect {
+    let right = bounds.x + bounds.width;
+    let bottom = bounds.y + bounds.height;
+
+    let (mut x, mut y) = match placement {
+        Placement::Below => (anchor.x, anchor.y + anchor.height),
+        Placement::BelowEnd => (anchor.x + anchor.width - width, anchor.y + anchor.height),
+        Placement::Right => (anchor.x + anchor.width, anchor.y),
+        Placement::Left => (anchor.x - width, anchor.y),
+    };
+
+    match placement {
+        Placement::Below | Placement::BelowEnd => {
+            if y + height > bottom && anchor.y - height >= bounds.y {
+                y = anchor.y - height;
+            }
+            if x + width > right {
+                x = anchor.x + anchor.width - width;
+            }
+            if x < bounds.x {
+                x = anchor.x;
+            }
+        }
+        Placement::Right | Placement::Left => {
+            if matches!(placement, Placement::Right) && x + width > right && anchor.x - width >= bounds.x {
+                x = anchor.x - width;
+            }
+            if matches!(placement, Placement::Left) && x < bounds.x && anchor.x + anchor.width + width <= right {
+                x = anchor.x + anchor.width;
+            }
+            if y + height > bottom {
+                y = anchor.y + anchor.height - height;
+            }
+        }
+    }
+
+    Rect {
+        x: x.min(right - width).max(bounds.x),
+        y: y.min(bottom - height).max(bounds.y),
+        width,
+        height,
+    }
+}
@@ -0,0 +1,49 @@
+use super::{Alignment, Direction};
+
+// 容器宽度小于 max_width 时生效的布局参数，未设置的参数沿用容器本身的设置。
+// 多个断点同时生效时 max_width 较小的优先，例如：
+//   Breakpoint::below(600.0).with_direction(Direction::Vertical)
+#[derive(Debug, Clone, Copy)]
+pub struct Breakpoint {
+    pub max_width: f32,
+    pub direction: Option<Direction>,
+    pub alignment: Option<Alignment>,
+    pub spacing: Option<f32>,
+    pub wrap: Option<bool>,
+}
+
+impl Breakpoint {
+    pub fn below(max_width: f32) -> Self {
+        Self {
+            max_width,
+            direction: None,
+            alignment: None,
+            spacing: None,
+            wrap: None,
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = Some(spacing);
+        self
+    }
+
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    pub fn matches(&self, width: f32) -> bool {
+        width < self.max_width
+    }
+}
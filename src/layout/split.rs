@@ -0,0 +1,52 @@
+// 分割容器中一个窗格的参数。ratio 是初始时所占的比例，按所有窗格的 ratio 之和归一化，
+// 拖动分隔条或窗口缩放时窗格的尺寸保持在最小、最大尺寸之间
+#[derive(Debug, Clone, Copy)]
+pub struct SplitPane {
+    pub ratio: f32,
+    pub min_size: f32,
+    pub max_size: f32,
+    // 双击相邻的分隔条时折叠，再次双击展开
+    pub collapsible: bool,
+}
+
+impl Default for SplitPane {
+    fn default() -> Self {
+        Self {
+            ratio: 1.0,
+            min_size: 0.0,
+            max_size: f32::INFINITY,
+            collapsible: false,
+        }
+    }
+}
+
+impl SplitPane {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio.max(0.0);
+        self
+    }
+
+    pub fn with_min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size.max(0.0);
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: f32) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn with_collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    // 与 FlexItem 一样，最小尺寸大于最大尺寸时以最小尺寸为准
+    pub fn clamp(&self, size: f32) -> f32 {
+        size.min(self.max_size).max(self.min_size)
+    }
+}
@@ -0,0 +1,128 @@
+use super::{Padding, Rect};
+
+// 子组件在层叠容器中的锚点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // 锚点在水平、垂直方向上的比例位置，0 为起点，1 为终点
+    fn factors(self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+
+    // 解析 "top-left"、"center"、"bottom-right" 这样的名称
+    pub fn parse(value: &str) -> Option<Anchor> {
+        match value {
+            "top-left" => Some(Anchor::TopLeft),
+            "top" => Some(Anchor::Top),
+            "top-right" => Some(Anchor::TopRight),
+            "left" => Some(Anchor::Left),
+            "center" => Some(Anchor::Center),
+            "right" => Some(Anchor::Right),
+            "bottom-left" => Some(Anchor::BottomLeft),
+            "bottom" => Some(Anchor::Bottom),
+            "bottom-right" => Some(Anchor::BottomRight),
+            _ => None,
+        }
+    }
+}
+
+// 子组件在层叠容器中的位置参数。子组件的锚点与容器的同名锚点重合，再按偏移移动，
+// 例如 BottomRight 使子组件的右下角对齐容器的右下角
+#[derive(Debug, Clone, Copy)]
+pub struct StackItem {
+    pub anchor: Anchor,
+    // 向右、向下为正
+    pub offset_x: f32,
+    pub offset_y: f32,
+    // 相对于容器内容区域的百分比尺寸，None 表示使用首选尺寸
+    pub width_percent: Option<f32>,
+    pub height_percent: Option<f32>,
+    // 数值大的在上层，相同时后添加的在上层
+    pub z_index: i32,
+    // 与容器边缘之间的空白，百分比尺寸也相对于去掉外边距后的区域
+    pub margin: Padding,
+}
+
+impl Default for StackItem {
+    fn default() -> Self {
+        Self {
+            anchor: Anchor::TopLeft,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            width_percent: None,
+            height_percent: None,
+            z_index: 0,
+            margin: Padding::all(0.0),
+        }
+    }
+}
+
+impl StackItem {
+    pub fn new(anchor: Anchor) -> Self {
+        Self {
+            anchor,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_offset(mut self, x: f32, y: f32) -> Self {
+        self.offset_x = x;
+        self.offset_y = y;
+        self
+    }
+
+    pub fn with_width_percent(mut self, percent: f32) -> Self {
+        self.width_percent = Some(percent.max(0.0));
+        self
+    }
+
+    pub fn with_height_percent(mut self, percent: f32) -> Self {
+        self.height_percent = Some(percent.max(0.0));
+        self
+    }
+
+    pub fn with_z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+
+    pub fn with_margin(mut self, margin: Padding) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    // 子组件在容器内容区域 bounds 中的位置，size 为子组件的首选尺寸
+    pub(crate) fn place(&self, bounds: Rect, size: (f32, f32)) -> Rect {
+        let bounds = bounds.inset(self.margin);
+        let width = self.width_percent.map_or(size.0, |percent| bounds.width * percent / 100.0);
+        let height = self.height_percent.map_or(size.1, |percent| bounds.height * percent / 100.0);
+        let (fx, fy) = self.anchor.factors();
+        Rect {
+            x: bounds.x + (bounds.width - width) * fx + self.offset_x,
+            y: bounds.y + (bounds.height - height) * fy + self.offset_y,
+            width,
+            height,
+        }
+    }
+}
@@ -2,16 +2,41 @@ mod window;
 mod renderer;
 mod widgets;
 mod font;
+mod text;
 mod layout;
+mod image;
+mod overlay;
+mod theme;
+mod color;
+mod box_style;
+mod path;
 
 pub mod ui;
 
 pub use window::Window;
+pub use renderer::Renderer;
 pub use widgets::Widget;
 pub use widgets::button::Button;
 pub use widgets::text::Text;
 pub use widgets::container::Container;
-pub use layout::{Rect, Padding, Alignment, Direction};
+pub use widgets::grid::Grid;
+pub use widgets::stack::Stack;
+pub use widgets::constraint_layout::ConstraintLayout;
+pub use widgets::splitter::Splitter;
+pub use widgets::menu::{Menu, MenuItem, MenuBar, ContextMenu};
+pub use widgets::tooltip::Tooltip;
+pub use widgets::combo_box::ComboBox;
+pub use widgets::image::Image;
+pub use widgets::rich_text::RichText;
+pub use layout::{Rect, Constraints, Padding, Alignment, AlignSelf, Anchor, Attribute, Breakpoint, ConstraintError, ConstraintExpr, ConstraintTerm, Direction, FlexItem, GridCell, LayoutConstraint, LayoutDirection, Length, Placement, Relation, SplitPane, StackItem, Strength, Track};
+pub use image::{ImageData, ImageError, ImageFit, ScaleFilter};
+pub use font::{Font, FontError};
+pub use text::{TextLayout, TextLine, TextOptions, TextDirection, TextSpan, SpanStyle, parse_markup};
+pub use overlay::{Dialog, DialogResult, Popup, OverlayHandle};
+pub use color::Color;
+pub use box_style::{Border, BoxShadow, BoxStyle, CornerRadii};
+pub use path::{FillRule, LineCap, LineJoin, Path, StrokeStyle};
+pub use theme::{FontSizes, Palette, Radii, Spacing, StateStyle, Theme, ThemeHandle, WidgetState, WidgetStyle};
 
 
 #[macro_export]
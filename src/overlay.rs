@@ -0,0 +1,427 @@
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
+use winit::event_loop::EventLoopProxy;
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::widgets::Widget;
+use crate::font::Font;
+use crate::layout::{place_popup, Placement, Rect};
+use crate::window::CustomEvent;
+use crate::Button;
+
+const TITLE_HEIGHT: f32 = 28.0;
+const DIALOG_PADDING: f32 = 16.0;
+const MIN_DIALOG_WIDTH: f32 = 240.0;
+const BUTTON_WIDTH: f32 = 90.0;
+const BUTTON_HEIGHT: f32 = 30.0;
+const BUTTON_SPACING: f32 = 8.0;
+
+// 叠加层对事件的处理结果
+pub(crate) enum OverlayResponse {
+    Ignored,
+    Consumed,
+    Close,
+}
+
+// 窗口叠加层：位于所有组件之上，按打开顺序堆叠。
+// 叠加层经 OverlayHandle 的命令队列从其他线程交给事件循环，所以要求 Send，对话框和弹出层的内容也一样
+pub(crate) trait Overlay: Send {
+    // 模态叠加层会阻止事件传递给下层
+    fn is_modal(&self) -> bool;
+    fn layout(&mut self, bounds: Rect);
+    fn draw(&self, renderer: &mut Renderer);
+    fn handle_event(&mut self, event: &WindowEvent) -> OverlayResponse;
+    // 被外部关闭时调用
+    fn dismiss(&mut self);
+    fn next_wakeup(&self) -> Option<Instant> {
+        None
+    }
+}
+
+fn is_key_pressed(event: &WindowEvent, key: VirtualKeyCode) -> bool {
+    matches!(
+        event,
+        WindowEvent::KeyboardInput {
+            input: KeyboardInput {
+                state: ElementState::Pressed,
+                virtual_keycode: Some(k),
+                ..
+            },
+            ..
+        } if *k == key
+    )
+}
+
+fn draw_outline(renderer: &mut Renderer, rect: Rect, color: Color) {
+    let (x, y) = (rect.x as i32, rect.y as i32);
+    let (width, height) = (rect.width as u32, rect.height as u32);
+    renderer.draw_rect(x, y, width, 1, color);
+    renderer.draw_rect(x, y + height as i32 - 1, width, 1, color);
+    renderer.draw_rect(x, y, 1, height, color);
+    renderer.draw_rect(x + width as i32 - 1, y, 1, height, color);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DialogResult {
+    // 点击的按钮下标
+    Button(usize),
+    // Escape 或被外部关闭
+    Cancelled,
+}
+
+// 模态对话框：半透明遮罩覆盖窗口，键盘焦点限制在对话框按钮之间
+pub struct Dialog {
+    rect: Rect,
+    title: String,
+    content: Option<Box<dyn Widget + Send>>,
+    buttons: Vec<Button>,
+    focused: usize,
+    clicked: Arc<Mutex<Option<usize>>>,
+    modifiers: ModifiersState,
+    bounds: Rect,
+    on_result: Option<Arc<dyn Fn(DialogResult) + Send + Sync>>,
+}
+
+impl Dialog {
+    pub fn new(title: &str) -> Self {
+        Self {
+            rect: Rect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 },
+            title: title.to_string(),
+            content: None,
+            buttons: Vec::new(),
+            focused: 0,
+            clicked: Arc::new(Mutex::new(None)),
+            modifiers: ModifiersState::empty(),
+            bounds: Rect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 },
+            on_result: None,
+        }
+    }
+
+    pub fn with_content<W: Widget + Send + 'static>(mut self, content: W) -> Self {
+        self.content = Some(Box::new(content));
+        self
+    }
+
+    pub fn with_button(mut self, label: &str) -> Self {
+        let index = self.buttons.len();
+        let clicked = self.clicked.clone();
+        let button = Button::new(0.0, 0.0, BUTTON_WIDTH, BUTTON_HEIGHT, label)
+            .on_click(move || *clicked.lock().unwrap() = Some(index));
+        self.buttons.push(button);
+        self
+    }
+
+    pub fn on_result<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(DialogResult) + Send + Sync + 'static,
+    {
+        self.on_result = Some(Arc::new(callback));
+        self
+    }
+
+    fn finish(&mut self, result: DialogResult) -> OverlayResponse {
+        if let Some(callback) = self.on_result.take() {
+            callback(result);
+        }
+        OverlayResponse::Close
+    }
+}
+
+impl Overlay for Dialog {
+    fn is_modal(&self) -> bool {
+        true
+    }
+
+    fn layout(&mut self, bounds: Rect) {
+        self.bounds = bounds;
+
+        let (content_width, content_height) = self
+            .content
+            .as_ref()
+            .map(|content| content.preferred_size())
+            .unwrap_or((0.0, 0.0));
+        let buttons_width = self.buttons.len() as f32 * (BUTTON_WIDTH + BUTTON_SPACING);
+        let title_width = Font::default().text_width(&self.title) as f32;
+        let button_row = if self.buttons.is_empty() { 0.0 } else { BUTTON_HEIGHT + DIALOG_PADDING };
+
+        let width = MIN_DIALOG_WIDTH
+            .max(content_width)
+            .max(buttons_width)
+            .max(title_width)
+            + DIALOG_PADDING * 2.0;
+        let height = TITLE_HEIGHT + DIALOG_PADDING * 2.0 + content_height + button_row;
+
+        // 居中显示
+        self.rect = Rect {
+            x: bounds.x + (bounds.width - width) / 2.0,
+            y: bounds.y + (bounds.height - height) / 2.0,
+            width,
+            height,
+        };
+
+        if let Some(content) = &mut self.content {
+            content.set_rect(Rect {
+                x: self.rect.x + DIALOG_PADDING,
+                y: self.rect.y + TITLE_HEIGHT + DIALOG_PADDING,
+                width: width - DIALOG_PADDING * 2.0,
+                height: content_height,
+            });
+        }
+
+        // 按钮右对齐
+        let mut x = self.rect.x + width - DIALOG_PADDING - BUTTON_WIDTH;
+        let y = self.rect.y + height - DIALOG_PADDING - BUTTON_HEIGHT;
+        for button in self.buttons.iter_mut().rev() {
+            button.set_rect(Rect { x, y, width: BUTTON_WIDTH, height: BUTTON_HEIGHT });
+            x -= BUTTON_WIDTH + BUTTON_SPACING;
+        }
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        let palette = renderer.theme().palette;
+        // 遮罩
+        renderer.blend_rect(
+            self.bounds.x as i32,
+            self.bounds.y as i32,
+            self.bounds.width as u32,
+            self.bounds.height as u32,
+            palette.backdrop,
+        );
+
+        let rect = self.rect;
+        renderer.draw_rect(rect.x as i32, rect.y as i32, rect.width as u32, rect.height as u32, palette.surface);
+        renderer.draw_rect(rect.x as i32, rect.y as i32, rect.width as u32, TITLE_HEIGHT as u32, palette.surface_variant);
+        draw_outline(renderer, rect, palette.border);
+
+        let font = Font::default();
+        let text_y = (rect.y + (TITLE_HEIGHT - font.line_height() as f32) / 2.0) as i32;
+        font.draw_text(renderer, (rect.x + DIALOG_PADDING) as i32, text_y, &self.title, palette.text);
+
+        if let Some(content) = &self.content {
+            content.draw(renderer);
+        }
+        for button in &self.buttons {
+            button.draw(renderer);
+        }
+        if let Some(button) = self.buttons.get(self.focused) {
+            let focus = button.get_rect();
+            draw_outline(
+                renderer,
+                Rect {
+                    x: focus.x - 2.0,
+                    y: focus.y - 2.0,
+                    width: focus.width + 4.0,
+                    height: focus.height + 4.0,
+                },
+                palette.accent,
+            );
+        }
+        if let Some(content) = &self.content {
+            content.draw_overlay(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) -> OverlayResponse {
+        if let WindowEvent::ModifiersChanged(modifiers) = event {
+            self.modifiers = *modifiers;
+        }
+
+        if is_key_pressed(event, VirtualKeyCode::Escape) {
+            return self.finish(DialogResult::Cancelled);
+        }
+        if is_key_pressed(event, VirtualKeyCode::Tab) && !self.buttons.is_empty() {
+            // 焦点只在对话框按钮之间循环
+            let count = self.buttons.len();
+            self.focused = if self.modifiers.shift() {
+                (self.focused + count - 1) % count
+            } else {
+                (self.focused + 1) % count
+            };
+            return OverlayResponse::Consumed;
+        }
+        if is_key_pressed(event, VirtualKeyCode::Return) && !self.buttons.is_empty() {
+            return self.finish(DialogResult::Button(self.focused));
+        }
+
+        if let Some(content) = &mut self.content {
+            if !content.handle_overlay_event(event) {
+                content.handle_event(event);
+            }
+        }
+        for button in &mut self.buttons {
+            button.handle_event(event);
+        }
+
+        let clicked = self.clicked.lock().unwrap().take();
+        match clicked {
+            Some(index) => self.finish(DialogResult::Button(index)),
+            None => OverlayResponse::Consumed,
+        }
+    }
+
+    fn dismiss(&mut self) {
+        self.finish(DialogResult::Cancelled);
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        self.content.as_ref().and_then(|content| content.next_wakeup())
+    }
+}
+
+// 非模态弹出层：锚定在某个组件的矩形区域旁，超出窗口边界时自动翻转
+pub struct Popup {
+    rect: Rect,
+    anchor: Rect,
+    placement: Placement,
+    content: Box<dyn Widget + Send>,
+    dismiss_on_click_outside: bool,
+    cursor: (f32, f32),
+    on_dismiss: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl Popup {
+    pub fn new<W: Widget + Send + 'static>(content: W, anchor: Rect) -> Self {
+        Self {
+            rect: anchor,
+            anchor,
+            placement: Placement::Below,
+            content: Box::new(content),
+            dismiss_on_click_outside: true,
+            cursor: (0.0, 0.0),
+            on_dismiss: None,
+        }
+    }
+
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_dismiss_on_click_outside(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_click_outside = dismiss;
+        self
+    }
+
+    pub fn on_dismiss<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_dismiss = Some(Arc::new(callback));
+        self
+    }
+
+    fn forward(&mut self, event: &WindowEvent) {
+        if !self.content.handle_overlay_event(event) {
+            self.content.handle_event(event);
+        }
+    }
+}
+
+impl Overlay for Popup {
+    fn is_modal(&self) -> bool {
+        false
+    }
+
+    fn layout(&mut self, bounds: Rect) {
+        let (width, height) = self.content.preferred_size();
+        self.rect = place_popup(self.anchor, width, height, bounds, self.placement);
+        self.content.set_rect(self.rect);
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        self.content.draw(renderer);
+        self.content.draw_overlay(renderer);
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) -> OverlayResponse {
+        if is_key_pressed(event, VirtualKeyCode::Escape) {
+            self.dismiss();
+            return OverlayResponse::Close;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+                self.forward(event);
+                if self.rect.contains(self.cursor.0, self.cursor.1) {
+                    OverlayResponse::Consumed
+                } else {
+                    OverlayResponse::Ignored
+                }
+            }
+            WindowEvent::MouseInput { state, .. } => {
+                let inside = self.rect.contains(self.cursor.0, self.cursor.1);
+                if !inside && *state == ElementState::Pressed && self.dismiss_on_click_outside {
+                    // 点击外部关闭
+                    self.dismiss();
+                    return OverlayResponse::Close;
+                }
+                self.forward(event);
+                if inside {
+                    OverlayResponse::Consumed
+                } else {
+                    OverlayResponse::Ignored
+                }
+            }
+            _ => {
+                self.forward(event);
+                OverlayResponse::Ignored
+            }
+        }
+    }
+
+    fn dismiss(&mut self) {
+        if let Some(callback) = self.on_dismiss.take() {
+            callback();
+        }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        self.content.next_wakeup()
+    }
+}
+
+pub(crate) enum OverlayCommand {
+    Show(Box<dyn Overlay>),
+    CloseTop,
+    CloseAll,
+}
+
+// 可以在回调中使用的叠加层句柄，命令在下一次事件循环时执行
+#[derive(Clone)]
+pub struct OverlayHandle {
+    commands: Arc<Mutex<Vec<OverlayCommand>>>,
+    proxy: Arc<Mutex<EventLoopProxy<CustomEvent>>>,
+}
+
+impl OverlayHandle {
+    pub(crate) fn new(commands: Arc<Mutex<Vec<OverlayCommand>>>, proxy: EventLoopProxy<CustomEvent>) -> Self {
+        Self {
+            commands,
+            proxy: Arc::new(Mutex::new(proxy)),
+        }
+    }
+
+    pub fn show_dialog(&self, dialog: Dialog) {
+        self.push(OverlayCommand::Show(Box::new(dialog)));
+    }
+
+    pub fn show_popup(&self, popup: Popup) {
+        self.push(OverlayCommand::Show(Box::new(popup)));
+    }
+
+    pub fn close_top(&self) {
+        self.push(OverlayCommand::CloseTop);
+    }
+
+    pub fn close_all(&self) {
+        self.push(OverlayCommand::CloseAll);
+    }
+
+    fn push(&self, command: OverlayCommand) {
+        self.commands.lock().unwrap().push(command);
+        // 唤醒事件循环
+        let _ = self.proxy.lock().unwrap().send_event(CustomEvent::OverlayChanged);
+    }
+}
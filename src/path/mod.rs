@@ -0,0 +1,277 @@
+mod raster;
+mod stroke;
+
+use std::f32::consts::{FRAC_PI_2, PI};
+use glam::Vec2;
+
+pub(crate) use raster::rasterize;
+pub(crate) use stroke::stroke_polygons;
+
+// 曲线展开为折线时允许偏离曲线的最大距离，单位为像素
+const TOLERANCE: f32 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PathCommand {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+// 矢量路径，由一个或多个子路径组成，坐标单位为像素，y 轴向下。
+// 圆弧在添加时转换为三次贝塞尔曲线，绘制时所有曲线按 TOLERANCE 展开为折线
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    // 当前子路径的起点和当前点，用于 close 和 arc
+    start: Vec2,
+    current: Option<Vec2>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    // 开始新的子路径
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        let point = Vec2::new(x, y);
+        self.commands.push(PathCommand::MoveTo(point));
+        self.start = point;
+        self.current = Some(point);
+        self
+    }
+
+    // 没有当前点时相当于 move_to，下面的曲线命令也一样
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        let point = Vec2::new(x, y);
+        if self.ensure_start(point) {
+            self.commands.push(PathCommand::LineTo(point));
+            self.current = Some(point);
+        }
+        self
+    }
+
+    // 二次贝塞尔曲线，(cx, cy) 为控制点
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) -> &mut Self {
+        let control = Vec2::new(cx, cy);
+        let point = Vec2::new(x, y);
+        self.ensure_start(control);
+        self.commands.push(PathCommand::QuadTo(control, point));
+        self.current = Some(point);
+        self
+    }
+
+    // 三次贝塞尔曲线，(c1x, c1y) 和 (c2x, c2y) 为两个控制点
+    pub fn cubic_to(&mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> &mut Self {
+        let first = Vec2::new(c1x, c1y);
+        let point = Vec2::new(x, y);
+        self.ensure_start(first);
+        self.commands.push(PathCommand::CubicTo(first, Vec2::new(c2x, c2y), point));
+        self.current = Some(point);
+        self
+    }
+
+    // 圆心为 (cx, cy) 的圆弧，角度为弧度，0 指向 x 轴正方向，角度增大时在屏幕上顺时针转动；
+    // end_angle 小于 start_angle 时逆时针画。与 HTML canvas 相同，已有当前点时先画一条直线连到圆弧起点
+    pub fn arc(&mut self, cx: f32, cy: f32, radius: f32, start_angle: f32, end_angle: f32) -> &mut Self {
+        let center = Vec2::new(cx, cy);
+        let radius = radius.abs();
+        let sweep = (end_angle - start_angle).clamp(-2.0 * PI, 2.0 * PI);
+        let start = center + Vec2::from_angle(start_angle) * radius;
+        match self.current {
+            Some(current) if current.distance(start) > f32::EPSILON => {
+                self.line_to(start.x, start.y);
+            }
+            Some(_) => {}
+            None => {
+                self.move_to(start.x, start.y);
+            }
+        }
+
+        // 每段不超过 90 度，控制点到端点的距离为 4/3 * tan(θ/4) * 半径
+        let segments = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / segments as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan();
+        let mut angle = start_angle;
+        for _ in 0..segments {
+            let (from, to) = (Vec2::from_angle(angle), Vec2::from_angle(angle + step));
+            let first = center + (from + from.perp() * k) * radius;
+            let second = center + (to - to.perp() * k) * radius;
+            let end = center + to * radius;
+            self.cubic_to(first.x, first.y, second.x, second.y, end.x, end.y);
+            angle += step;
+        }
+        self
+    }
+
+    // 用直线回到子路径的起点并闭合，描边时首尾按连接方式相连
+    pub fn close(&mut self) -> &mut Self {
+        if self.current.is_some() {
+            self.commands.push(PathCommand::Close);
+            self.current = Some(self.start);
+        }
+        self
+    }
+
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32) -> &mut Self {
+        self.move_to(x, y)
+            .line_to(x + width, y)
+            .line_to(x + width, y + height)
+            .line_to(x, y + height)
+            .close()
+    }
+
+    pub fn circle(&mut self, cx: f32, cy: f32, radius: f32) -> &mut Self {
+        self.move_to(cx + radius, cy).arc(cx, cy, radius, 0.0, 2.0 * PI).close()
+    }
+
+    fn ensure_start(&mut self, point: Vec2) -> bool {
+        if self.current.is_none() {
+            self.move_to(point.x, point.y);
+            return false;
+        }
+        true
+    }
+
+    // 把路径展开为折线，返回每个子路径的点和是否闭合
+    pub(crate) fn flatten(&self) -> Vec<(Vec<Vec2>, bool)> {
+        let mut subpaths = Vec::new();
+        let mut points: Vec<Vec2> = Vec::new();
+        for command in &self.commands {
+            let last = points.last().copied().unwrap_or(Vec2::ZERO);
+            match *command {
+                PathCommand::MoveTo(point) => {
+                    if points.len() > 1 {
+                        subpaths.push((std::mem::take(&mut points), false));
+                    }
+                    points.clear();
+                    points.push(point);
+                }
+                PathCommand::LineTo(point) => points.push(point),
+                PathCommand::QuadTo(control, point) => {
+                    // 均匀细分后弦与曲线的最大距离为 |p0 - 2p1 + p2| / (4n²)
+                    let deviation = (last - 2.0 * control + point).length();
+                    let segments = subdivisions(deviation / 4.0);
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        points.push(last * mt * mt + control * 2.0 * mt * t + point * t * t);
+                    }
+                }
+                PathCommand::CubicTo(first, second, point) => {
+                    // 二阶导数的最大值不超过 6 * max(|p0 - 2p1 + p2|, |p1 - 2p2 + p3|)
+                    let deviation = (last - 2.0 * first + second).length().max((first - 2.0 * second + point).length());
+                    let segments = subdivisions(deviation * 0.75);
+                    for i in 1..=segments {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        points.push(
+                            last * mt * mt * mt + first * 3.0 * mt * mt * t + second * 3.0 * mt * t * t + point * t * t * t,
+                        );
+                    }
+                }
+                PathCommand::Close => {
+                    if !points.is_empty() {
+                        let start = points[0];
+                        subpaths.push((std::mem::take(&mut points), true));
+                        points.push(start);
+                    }
+                }
+            }
+        }
+        if points.len() > 1 {
+            subpaths.push((points, false));
+        }
+        subpaths
+    }
+}
+
+// 误差为 error / n² 时满足 TOLERANCE 所需的段数
+fn subdivisions(error: f32) -> usize {
+    ((error / TOLERANCE).sqrt().ceil() as usize).clamp(1, 256)
+}
+
+// 填充时判断点是否在路径内部的规则
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FillRule {
+    // 环绕数不为 0 的区域在内部，自交的路径整体被填充
+    #[default]
+    NonZero,
+    // 穿过边界奇数次的区域在内部，可以用内外两个子路径画出镂空
+    EvenOdd,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineCap {
+    // 在端点处截断
+    #[default]
+    Butt,
+    // 半圆
+    Round,
+    // 向外延长半个线宽
+    Square,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineJoin {
+    // 尖角，超过 limit（尖角长度与线宽之比）时改为 Bevel
+    Miter(f32),
+    Round,
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Miter(4.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    // 交替的实线和空白长度，奇数个时重复一次，为空时画实线
+    pub dash: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> Self {
+        Self { width, ..Self::default() }
+    }
+
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn with_dash(mut self, dash: &[f32], offset: f32) -> Self {
+        self.dash = dash.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+}
@@ -0,0 +1,197 @@
+use glam::Vec2;
+use super::FillRule;
+
+// 每行像素取的子扫描线数量，决定接近水平的边缘的抗锯齿级数；水平方向按跨度精确计算覆盖率
+const SUBSAMPLES: usize = 16;
+
+struct Edge {
+    top: f32,
+    bottom: f32,
+    // top 处的 x 坐标和每向下一个单位 x 的变化
+    x: f32,
+    slope: f32,
+    // 向下的边为 1，向上的边为 -1
+    winding: i32,
+}
+
+// 扫描线抗锯齿光栅化：多边形隐式闭合，把 clip 区域内每一行的覆盖率（0..1）交给 emit，
+// emit 的参数为 y、这一行第一个像素的 x 和从那里开始的覆盖率
+pub(crate) fn rasterize(
+    polygons: &[Vec<Vec2>],
+    rule: FillRule,
+    clip_width: u32,
+    clip_height: u32,
+    mut emit: impl FnMut(i32, i32, &[f32]),
+) {
+    let mut edges = Vec::new();
+    let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+    for polygon in polygons {
+        for (i, &from) in polygon.iter().enumerate() {
+            let to = polygon[(i + 1) % polygon.len()];
+            if !from.is_finite() || !to.is_finite() {
+                continue;
+            }
+            min = min.min(from);
+            max = max.max(from);
+            if from.y == to.y {
+                continue;
+            }
+            let (upper, lower, winding) = if from.y < to.y { (from, to, 1) } else { (to, from, -1) };
+            edges.push(Edge {
+                top: upper.y,
+                bottom: lower.y,
+                x: upper.x,
+                slope: (lower.x - upper.x) / (lower.y - upper.y),
+                winding,
+            });
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+    edges.sort_by(|a, b| a.top.total_cmp(&b.top));
+
+    let x0 = (min.x.floor() as i32).max(0);
+    let x1 = (max.x.ceil() as i32).min(clip_width as i32);
+    let y0 = (min.y.floor() as i32).max(0);
+    let y1 = (max.y.ceil() as i32).min(clip_height as i32);
+    if x0 >= x1 || y0 >= y1 {
+        return;
+    }
+
+    let mut coverage = vec![0.0f32; (x1 - x0) as usize];
+    let mut active: Vec<&Edge> = Vec::new();
+    let mut next_edge = 0;
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let weight = 1.0 / SUBSAMPLES as f32;
+    for y in y0..y1 {
+        coverage.fill(0.0);
+        let mut touched = false;
+        for sample in 0..SUBSAMPLES {
+            let sample_y = y as f32 + (sample as f32 + 0.5) * weight;
+            while next_edge < edges.len() && edges[next_edge].top <= sample_y {
+                active.push(&edges[next_edge]);
+                next_edge += 1;
+            }
+            active.retain(|edge| edge.bottom > sample_y);
+
+            crossings.clear();
+            crossings.extend(
+                active
+                    .iter()
+                    .map(|edge| (edge.x + (sample_y - edge.top) * edge.slope, edge.winding)),
+            );
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // 从左到右累计环绕数，按填充规则找出内部的跨度
+            let mut winding = 0;
+            let mut span_start = 0.0;
+            for &(x, direction) in &crossings {
+                let was_inside = inside(winding, rule);
+                winding += direction;
+                match (was_inside, inside(winding, rule)) {
+                    (false, true) => span_start = x,
+                    (true, false) => {
+                        add_span(&mut coverage, span_start - x0 as f32, x - x0 as f32, weight);
+                        touched = true;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if touched {
+            emit(y, x0, &coverage);
+        }
+    }
+}
+
+fn inside(winding: i32, rule: FillRule) -> bool {
+    match rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+// 把 [start, end) 按像素分配到 coverage 中，两端的像素按被覆盖的长度计算
+fn add_span(coverage: &mut [f32], start: f32, end: f32, weight: f32) {
+    let start = start.clamp(0.0, coverage.len() as f32);
+    let end = end.clamp(0.0, coverage.len() as f32);
+    if end <= start {
+        return;
+    }
+    let (first, last) = (start.floor() as usize, end.floor() as usize);
+    if first == last {
+        coverage[first] += (end - start) * weight;
+        return;
+    }
+    coverage[first] += (first as f32 + 1.0 - start) * weight;
+    for value in &mut coverage[first + 1..last] {
+        *value += weight;
+    }
+    if last < coverage.len() {
+        coverage[last] += (end - last as f32) * weight;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 光栅化到 size x size 的网格，按 [y][x] 取覆盖率
+    fn coverage_grid(polygons: &[Vec<Vec2>], rule: FillRule, size: u32) -> Vec<Vec<f32>> {
+        let mut grid = vec![vec![0.0; size as usize]; size as usize];
+        rasterize(polygons, rule, size, size, |y, x0, row| {
+            for (offset, value) in row.iter().enumerate() {
+                grid[y as usize][x0 as usize + offset] = *value;
+            }
+        });
+        grid
+    }
+
+    fn square(x: f32, y: f32, size: f32, clockwise: bool) -> Vec<Vec2> {
+        let points = vec![Vec2::new(x, y), Vec2::new(x + size, y), Vec2::new(x + size, y + size), Vec2::new(x, y + size)];
+        if clockwise { points } else { points.into_iter().rev().collect() }
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 0.01, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn fills_pixels_with_partial_edges() {
+        let grid = coverage_grid(&[square(1.5, 1.0, 3.0, true)], FillRule::NonZero, 8);
+        assert_close(grid[2][2], 1.0);
+        assert_close(grid[2][1], 0.5);
+        assert_close(grid[2][4], 0.5);
+        assert_close(grid[0][2], 0.0);
+        assert_close(grid[4][2], 0.0);
+        // 对角线把像素分成两半
+        let triangle = vec![Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(0.0, 4.0)];
+        let grid = coverage_grid(&[triangle], FillRule::NonZero, 8);
+        assert_close(grid[1][2], 0.5);
+        assert_close(grid[0][0], 1.0);
+    }
+
+    #[test]
+    fn nonzero_and_even_odd_differ_for_nested_shapes() {
+        let same_direction = [square(0.0, 0.0, 8.0, true), square(2.0, 2.0, 4.0, true)];
+        assert_close(coverage_grid(&same_direction, FillRule::NonZero, 8)[4][4], 1.0);
+        assert_close(coverage_grid(&same_direction, FillRule::EvenOdd, 8)[4][4], 0.0);
+        assert_close(coverage_grid(&same_direction, FillRule::EvenOdd, 8)[1][1], 1.0);
+
+        // 方向相反的内部子路径在两种规则下都是镂空
+        let opposite = [square(0.0, 0.0, 8.0, true), square(2.0, 2.0, 4.0, false)];
+        assert_close(coverage_grid(&opposite, FillRule::NonZero, 8)[4][4], 0.0);
+        assert_close(coverage_grid(&opposite, FillRule::EvenOdd, 8)[4][4], 0.0);
+    }
+
+    #[test]
+    fn clips_to_target_and_skips_invalid_points() {
+        let grid = coverage_grid(&[square(-4.0, -4.0, 20.0, true)], FillRule::NonZero, 4);
+        assert!(grid.iter().flatten().all(|&value| (value - 1.0).abs() < 0.01));
+
+        let invalid = vec![Vec2::new(f32::NAN, 0.0), Vec2::new(f32::INFINITY, 4.0)];
+        assert!(coverage_grid(&[invalid], FillRule::NonZero, 4).iter().flatten().all(|&value| value == 0.0));
+        assert!(coverage_grid(&[square(1.0, 1.0, 0.0, true)], FillRule::NonZero, 4).iter().flatten().all(|&value| value == 0.0));
+    }
+}
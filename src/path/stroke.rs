@@ -0,0 +1,290 @@
+use std::f32::consts::PI;
+use glam::Vec2;
+use super::{LineCap, LineJoin, StrokeStyle};
+
+// 把折线的描边转换为一组多边形：每条线段一个矩形，再加上连接处和端点的形状。
+// 所有多边形都调整为同一方向，按非零规则填充时重叠部分不会抵消
+pub(crate) fn stroke_polygons(subpaths: &[(Vec<Vec2>, bool)], style: &StrokeStyle) -> Vec<Vec<Vec2>> {
+    let half = style.width / 2.0;
+    let mut polygons = Vec::new();
+    if half <= 0.0 {
+        return polygons;
+    }
+    for (points, closed) in subpaths {
+        let points = dedup(points);
+        if style.dash.iter().any(|length| *length > 0.0) && style.dash.iter().all(|length| *length >= 0.0) {
+            for dash in split_dashes(&points, *closed, &style.dash, style.dash_offset) {
+                stroke_polyline(&dedup(&dash), false, half, style, &mut polygons);
+            }
+        } else {
+            stroke_polyline(&points, *closed, half, style, &mut polygons);
+        }
+    }
+    for polygon in &mut polygons {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    polygons
+}
+
+fn stroke_polyline(points: &[Vec2], closed: bool, half: f32, style: &StrokeStyle, polygons: &mut Vec<Vec<Vec2>>) {
+    // 闭合时最后一个点与起点重合，去掉后首尾相连
+    let points = match points {
+        [rest @ .., last] if closed && rest.len() > 1 && last.distance(rest[0]) < f32::EPSILON => rest,
+        _ => points,
+    };
+    match points {
+        [] => return,
+        // 长度为 0 的线段只画端点
+        [point] => {
+            if !closed {
+                push_cap(*point, Vec2::X, half, style.cap, polygons);
+                push_cap(*point, -Vec2::X, half, style.cap, polygons);
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let segment_count = if closed { points.len() } else { points.len() - 1 };
+    for i in 0..segment_count {
+        let (from, to) = (points[i], points[(i + 1) % points.len()]);
+        let normal = (to - from).normalize().perp() * half;
+        polygons.push(vec![from + normal, to + normal, to - normal, from - normal]);
+    }
+
+    let joints = if closed { 0..points.len() } else { 1..points.len() - 1 };
+    for i in joints {
+        let previous = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        push_join(points[i], (points[i] - previous).normalize(), (next - points[i]).normalize(), half, style.join, polygons);
+    }
+
+    if !closed {
+        let last = points.len() - 1;
+        push_cap(points[0], (points[0] - points[1]).normalize(), half, style.cap, polygons);
+        push_cap(points[last], (points[last] - points[last - 1]).normalize(), half, style.cap, polygons);
+    }
+}
+
+// incoming 和 outgoing 为连接点前后线段的单位方向，只在转弯的外侧补上形状
+fn push_join(point: Vec2, incoming: Vec2, outgoing: Vec2, half: f32, join: LineJoin, polygons: &mut Vec<Vec<Vec2>>) {
+    let cross = incoming.perp_dot(outgoing);
+    if cross.abs() < 1e-6 && incoming.dot(outgoing) > 0.0 {
+        return;
+    }
+    // 向法线一侧转弯时外侧在相反的一侧
+    let side = if cross > 0.0 { -1.0 } else { 1.0 };
+    let first = point + incoming.perp() * half * side;
+    let second = point + outgoing.perp() * half * side;
+    match join {
+        LineJoin::Round => polygons.push(circle(point, half)),
+        LineJoin::Miter(limit) => {
+            // 尖角长度与线宽之比为 1 / cos(θ/2)，θ 为两条线段法线的夹角
+            let bisector = (incoming.perp() + outgoing.perp()).normalize_or_zero() * side;
+            let cos_half = bisector.dot(incoming.perp() * side);
+            if cos_half > 1e-6 && 1.0 / cos_half <= limit {
+                polygons.push(vec![point, first, point + bisector * half / cos_half, second]);
+            } else {
+                polygons.push(vec![point, first, second]);
+            }
+        }
+        LineJoin::Bevel => polygons.push(vec![point, first, second]),
+    }
+}
+
+// direction 为从线段指向端点外侧的单位方向
+fn push_cap(point: Vec2, direction: Vec2, half: f32, cap: LineCap, polygons: &mut Vec<Vec<Vec2>>) {
+    let normal = direction.perp() * half;
+    let extension = direction * half;
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Round => polygons.push(circle(point, half)),
+        LineCap::Square => polygons.push(vec![
+            point + normal,
+            point + normal + extension,
+            point - normal + extension,
+            point - normal,
+        ]),
+    }
+}
+
+// 按半径选择边数，使多边形与圆的距离不超过容差
+fn circle(center: Vec2, radius: f32) -> Vec<Vec2> {
+    let segments = ((PI / (1.0 - super::TOLERANCE / radius).clamp(-1.0, 1.0).acos()).ceil() as usize).clamp(8, 256);
+    (0..segments)
+        .map(|i| center + Vec2::from_angle(i as f32 / segments as f32 * 2.0 * PI) * radius)
+        .collect()
+}
+
+// 按虚线模式把折线切成若干段实线，dash_offset 为从模式的哪个位置开始
+fn split_dashes(points: &[Vec2], closed: bool, pattern: &[f32], offset: f32) -> Vec<Vec<Vec2>> {
+    let mut pattern = pattern.to_vec();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_within(..);
+    }
+    let period: f32 = pattern.iter().sum();
+
+    let mut points = points.to_vec();
+    if closed && points.len() > 1 && points.last() != points.first() {
+        points.push(points[0]);
+    }
+
+    // 找到 offset 在模式中的位置
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut skip = offset.rem_euclid(period);
+    while skip > 0.0 {
+        if skip < remaining {
+            remaining -= skip;
+            break;
+        }
+        skip -= remaining;
+        index = (index + 1) % pattern.len();
+        remaining = pattern[index];
+    }
+
+    let mut dashes = Vec::new();
+    let mut current: Vec<Vec2> = Vec::new();
+    if index % 2 == 0 {
+        current.push(points[0]);
+    }
+    for segment in points.windows(2) {
+        let (mut from, to) = (segment[0], segment[1]);
+        let mut length = from.distance(to);
+        while length > 0.0 {
+            if remaining > length {
+                remaining -= length;
+                if index % 2 == 0 {
+                    current.push(to);
+                }
+                break;
+            }
+            from = from.lerp(to, remaining / length);
+            length -= remaining;
+            if index % 2 == 0 {
+                current.push(from);
+                dashes.push(std::mem::take(&mut current));
+            } else {
+                current.push(from);
+            }
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+    }
+    if index % 2 == 0 && current.len() > 1 {
+        dashes.push(current);
+    }
+    dashes
+}
+
+fn dedup(points: &[Vec2]) -> Vec<Vec2> {
+    let mut result: Vec<Vec2> = Vec::with_capacity(points.len());
+    for point in points {
+        if result.last().is_none_or(|last| last.distance(*point) > 1e-4) {
+            result.push(*point);
+        }
+    }
+    result
+}
+
+fn signed_area(polygon: &[Vec2]) -> f32 {
+    let mut area = 0.0;
+    for (i, point) in polygon.iter().enumerate() {
+        area += point.perp_dot(polygon[(i + 1) % polygon.len()]);
+    }
+    area / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::path::raster::rasterize;
+    use crate::path::FillRule;
+
+    // 按非零规则光栅化描边，按 [y][x] 取覆盖率
+    fn stroke(points: &[(f32, f32)], closed: bool, style: &StrokeStyle) -> Vec<Vec<f32>> {
+        let points = points.iter().map(|&(x, y)| Vec2::new(x, y)).collect();
+        let mut grid = vec![vec![0.0; 16]; 16];
+        rasterize(&stroke_polygons(&[(points, closed)], style), FillRule::NonZero, 16, 16, |y, x0, row| {
+            for (offset, value) in row.iter().enumerate() {
+                grid[y as usize][x0 as usize + offset] = *value;
+            }
+        });
+        grid
+    }
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 0.02, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn caps_extend_line_ends() {
+        let line = [(4.0, 8.0), (12.0, 8.0)];
+        let butt = stroke(&line, false, &StrokeStyle::new(2.0));
+        assert_close(butt[7][4], 1.0);
+        assert_close(butt[8][11], 1.0);
+        assert_close(butt[7][3], 0.0);
+        assert_close(butt[6][4], 0.0);
+
+        let square = stroke(&line, false, &StrokeStyle::new(2.0).with_cap(LineCap::Square));
+        assert_close(square[7][3], 1.0);
+        assert_close(square[8][12], 1.0);
+        assert_close(square[7][2], 0.0);
+
+        // 半圆端点覆盖端点外像素的四分之一圆，圆按多边形近似
+        let round = stroke(&line, false, &StrokeStyle::new(2.0).with_cap(LineCap::Round));
+        assert_close(round[7][3], signed_area(&circle(Vec2::ZERO, 1.0)) / 4.0);
+    }
+
+    #[test]
+    fn joins_fill_outer_corner() {
+        // 在 (12, 4) 处向下转弯，外侧的角落为 [12, 13] x [3, 4]
+        let corner = [(4.0, 4.0), (12.0, 4.0), (12.0, 12.0)];
+        let miter = stroke(&corner, false, &StrokeStyle::new(2.0).with_join(LineJoin::Miter(4.0)));
+        assert_close(miter[3][12], 1.0);
+        let bevel = stroke(&corner, false, &StrokeStyle::new(2.0).with_join(LineJoin::Bevel));
+        assert_close(bevel[3][12], 0.5);
+        let round = stroke(&corner, false, &StrokeStyle::new(2.0).with_join(LineJoin::Round));
+        assert_close(round[3][12], signed_area(&circle(Vec2::ZERO, 1.0)) / 4.0);
+        // 直角的尖角比为 √2，超过限制时改为斜角
+        let limited = stroke(&corner, false, &StrokeStyle::new(2.0).with_join(LineJoin::Miter(1.2)));
+        assert_close(limited[3][12], 0.5);
+        // 内侧不受连接方式影响
+        assert_close(bevel[5][11], 1.0);
+    }
+
+    #[test]
+    fn closed_paths_join_at_start() {
+        let square = [(4.0, 4.0), (12.0, 4.0), (12.0, 12.0), (4.0, 12.0), (4.0, 4.0)];
+        let grid = stroke(&square, true, &StrokeStyle::new(2.0));
+        assert_close(grid[3][3], 1.0);
+        assert_close(grid[8][8], 0.0);
+        let open = stroke(&square, false, &StrokeStyle::new(2.0));
+        assert_close(open[3][3], 0.0);
+    }
+
+    #[test]
+    fn polygons_share_orientation() {
+        let points = vec![Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(0.0, 10.0), Vec2::new(10.0, 10.0)];
+        let style = StrokeStyle::new(3.0).with_join(LineJoin::Round).with_cap(LineCap::Round);
+        let polygons = stroke_polygons(&[(points, false)], &style);
+        assert!(!polygons.is_empty());
+        assert!(polygons.iter().all(|polygon| signed_area(polygon) >= 0.0));
+        assert!(stroke_polygons(&[(vec![Vec2::ZERO, Vec2::X], false)], &StrokeStyle::new(0.0)).is_empty());
+    }
+
+    #[test]
+    fn splits_dashes_along_path() {
+        let points = [Vec2::new(0.0, 0.0), Vec2::new(10.0, 0.0)];
+        let lengths = |dashes: Vec<Vec<Vec2>>| -> Vec<(f32, f32)> {
+            dashes.iter().map(|dash| (dash[0].x, dash[dash.len() - 1].x)).collect()
+        };
+        assert_eq!(lengths(split_dashes(&points, false, &[3.0, 2.0], 0.0)), [(0.0, 3.0), (5.0, 8.0)]);
+        // 从模式的第 4 个单位开始，先是 1 个单位的空白
+        assert_eq!(lengths(split_dashes(&points, false, &[3.0, 2.0], 4.0)), [(1.0, 4.0), (6.0, 9.0)]);
+        // 奇数个长度时重复一次：实 2 空 2 实 2 空 2
+        assert_eq!(lengths(split_dashes(&points, false, &[2.0], 0.0)), [(0.0, 2.0), (4.0, 6.0), (8.0, 10.0)]);
+    }
+}
@@ -1,10 +1,19 @@
+use std::sync::Arc;
 use pixels::{Pixels, SurfaceTexture};
 use winit::window::Window;
+use crate::image::{ImageData, ScaleFilter};
+use crate::layout::Rect;
+use crate::theme::Theme;
+use crate::color::Color;
+use crate::box_style::{BoxShadow, BoxStyle, CornerRadii};
+use crate::path::{rasterize, stroke_polygons, FillRule, Path, StrokeStyle};
 
 pub struct Renderer {
     pixels: Pixels,
     width: u32,
     height: u32,
+    // 组件绘制时从这里读取颜色
+    theme: Arc<Theme>,
 }
 
 impl Renderer {
@@ -25,17 +34,36 @@ impl Renderer {
             pixels,
             width: window_size.width,
             height: window_size.height,
+            theme: Arc::new(Theme::default()),
         }
     }
 
-    pub fn clear(&mut self, color: [u8; 4]) {
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: Arc<Theme>) {
+        self.theme = theme;
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn clear(&mut self, color: Color) {
+        let color = color.to_array();
         let frame = self.pixels.frame_mut();
         for pixel in frame.chunks_exact_mut(4) {
             pixel.copy_from_slice(&color);
         }
     }
 
-    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: [u8; 4]) {
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        let color = color.to_array();
         let frame = self.pixels.frame_mut();
         for dy in 0..height {
             for dx in 0..width {
@@ -50,7 +78,29 @@ impl Renderer {
         }
     }
     
-    pub fn draw_pixel(&mut self, x: i32, y: i32, color: [u8; 4]) {
+    // 按 alpha 通道与已有内容混合
+    pub fn blend_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Color) {
+        let alpha = color.a as u32;
+        let color = color.to_array();
+        let frame = self.pixels.frame_mut();
+        for dy in 0..height {
+            for dx in 0..width {
+                let px = x + dx as i32;
+                let py = y + dy as i32;
+
+                if px >= 0 && px < self.width as i32 && py >= 0 && py < self.height as i32 {
+                    let idx = (py * self.width as i32 + px) as usize * 4;
+                    for c in 0..3 {
+                        let dst = frame[idx + c] as u32;
+                        frame[idx + c] = ((color[c] as u32 * alpha + dst * (255 - alpha)) / 255) as u8;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn draw_pixel(&mut self, x: i32, y: i32, color: Color) {
+        let color = color.to_array();
         if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
             let idx = (y * self.width as i32 + x) as usize * 4;
             let frame = self.pixels.frame_mut();
@@ -58,7 +108,323 @@ impl Renderer {
         }
     }
 
+    // 按 BoxStyle 绘制组件背景：阴影、背景、边框依次叠加
+    pub fn draw_box(&mut self, rect: Rect, style: &BoxStyle) {
+        let radii = style.corner_radii.fit(rect.width, rect.height);
+        for shadow in style.shadows.iter().filter(|shadow| shadow.is_visible()) {
+            self.draw_box_shadow(rect, radii, shadow);
+        }
+        if let Some(background) = style.background {
+            self.fill_rounded_rect(rect, radii, background);
+        }
+        if let Some(border) = style.border.filter(|border| border.width > 0.0) {
+            self.stroke_rounded_rect(rect, radii, border.width, border.color);
+        }
+    }
+
+    // 抗锯齿的圆角矩形填充，边缘像素按覆盖率混合
+    pub fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: Color) {
+        let radii = radii.fit(rect.width, rect.height);
+        self.fill_coverage(rect, color, |x, y| edge_coverage(rounded_rect_distance(x, y, rect, radii)));
+    }
+
+    // 沿圆角矩形内侧画宽为 width 的边框，内侧的圆角半径减去边框宽度
+    pub fn stroke_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, width: f32, color: Color) {
+        let radii = radii.fit(rect.width, rect.height);
+        let inner = Rect {
+            x: rect.x + width,
+            y: rect.y + width,
+            width: rect.width - width * 2.0,
+            height: rect.height - width * 2.0,
+        };
+        let inner_radii = radii.grow(-width);
+        self.fill_coverage(rect, color, |x, y| {
+            let outer = edge_coverage(rounded_rect_distance(x, y, rect, radii));
+            if inner.width <= 0.0 || inner.height <= 0.0 {
+                return outer;
+            }
+            outer - edge_coverage(rounded_rect_distance(x, y, inner, inner_radii))
+        });
+    }
+
+    // 盒子外的阴影。模糊按高斯分布近似：覆盖率为到阴影形状距离的误差函数，
+    // 盒子内部不画阴影，背景半透明时也不会透出阴影
+    pub fn draw_box_shadow(&mut self, rect: Rect, radii: CornerRadii, shadow: &BoxShadow) {
+        if let Some((bounds, coverage)) = box_shadow_coverage(rect, radii, shadow) {
+            self.fill_coverage(bounds, shadow.color, coverage);
+        }
+    }
+
+    // 对 bounds 中的每个像素按像素中心计算覆盖率（0..1），与颜色的 alpha 相乘后混合
+    fn fill_coverage(&mut self, bounds: Rect, color: Color, coverage: impl Fn(f32, f32) -> f32) {
+        let x0 = (bounds.x.floor() as i32).max(0);
+        let y0 = (bounds.y.floor() as i32).max(0);
+        let x1 = ((bounds.x + bounds.width).ceil() as i32).min(self.width as i32);
+        let y1 = ((bounds.y + bounds.height).ceil() as i32).min(self.height as i32);
+        let width = self.width as i32;
+        let frame = self.pixels.frame_mut();
+        for py in y0..y1 {
+            for px in x0..x1 {
+                let idx = (py * width + px) as usize * 4;
+                blend_coverage(frame, idx, color, coverage(px as f32 + 0.5, py as f32 + 0.5));
+            }
+        }
+    }
+
+    // 按填充规则填充路径，未闭合的子路径自动闭合
+    pub fn fill_path(&mut self, path: &Path, color: Color, rule: FillRule) {
+        let polygons: Vec<_> = path.flatten().into_iter().map(|(points, _)| points).collect();
+        self.fill_polygons(&polygons, color, rule);
+    }
+
+    // 按线宽、端点、连接方式和虚线模式描边
+    pub fn stroke_path(&mut self, path: &Path, color: Color, style: &StrokeStyle) {
+        let polygons = stroke_polygons(&path.flatten(), style);
+        self.fill_polygons(&polygons, color, FillRule::NonZero);
+    }
+
+    fn fill_polygons(&mut self, polygons: &[Vec<glam::Vec2>], color: Color, rule: FillRule) {
+        let width = self.width as i32;
+        let frame = self.pixels.frame_mut();
+        rasterize(polygons, rule, self.width, self.height, |y, x0, coverage| {
+            for (i, value) in coverage.iter().enumerate() {
+                let idx = (y * width + x0 + i as i32) as usize * 4;
+                blend_coverage(frame, idx, color, *value);
+            }
+        });
+    }
+
+    // 按 alpha 通道与已有像素混合
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: Color) {
+        self.blend_rect(x, y, 1, 1, color);
+    }
+
+    // 把整张图片缩放绘制到目标区域
+    pub fn draw_image(&mut self, image: &ImageData, dest: Rect, filter: ScaleFilter) {
+        let src = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: image.width() as f32,
+            height: image.height() as f32,
+        };
+        self.draw_image_region(image, src, dest, filter);
+    }
+
+    // 把图片中的 src 区域缩放绘制到 dest 区域，按 alpha 与背景混合
+    pub fn draw_image_region(&mut self, image: &ImageData, src: Rect, dest: Rect, filter: ScaleFilter) {
+        self.blit_image(image, src, dest, filter, false);
+    }
+
+    // 水平镜像后绘制，用于从右到左布局中有方向的图标
+    pub fn draw_image_region_mirrored(&mut self, image: &ImageData, src: Rect, dest: Rect, filter: ScaleFilter) {
+        self.blit_image(image, src, dest, filter, true);
+    }
+
+    fn blit_image(&mut self, image: &ImageData, src: Rect, dest: Rect, filter: ScaleFilter, mirrored: bool) {
+        if dest.width <= 0.0 || dest.height <= 0.0 || image.width() == 0 || image.height() == 0 {
+            return;
+        }
+
+        let scale_x = src.width / dest.width;
+        let scale_y = src.height / dest.height;
+        let x0 = (dest.x.floor() as i32).max(0);
+        let y0 = (dest.y.floor() as i32).max(0);
+        let x1 = ((dest.x + dest.width).ceil() as i32).min(self.width as i32);
+        let y1 = ((dest.y + dest.height).ceil() as i32).min(self.height as i32);
+
+        let frame = self.pixels.frame_mut();
+        for py in y0..y1 {
+            let v = src.y + (py as f32 + 0.5 - dest.y) * scale_y;
+            if v < src.y || v >= src.y + src.height {
+                continue;
+            }
+            for px in x0..x1 {
+                let offset = (px as f32 + 0.5 - dest.x) * scale_x;
+                let u = if mirrored { src.x + src.width - offset } else { src.x + offset };
+                if u < src.x || u >= src.x + src.width {
+                    continue;
+                }
+
+                let color = image.sample(u, v, filter);
+                let alpha = color[3] as u32;
+                if alpha == 0 {
+                    continue;
+                }
+                let idx = (py * self.width as i32 + px) as usize * 4;
+                for c in 0..3 {
+                    let dst = frame[idx + c] as u32;
+                    frame[idx + c] = ((color[c] as u32 * alpha + dst * (255 - alpha)) / 255) as u8;
+                }
+            }
+        }
+    }
+
+    // 窗口尺寸变化时同时调整窗口表面和绘制缓冲区，保持一个缓冲区像素对应一个窗口像素
+    pub fn resize(&mut self, width: u32, height: u32) {
+        // 窗口最小化时尺寸为 0
+        if width == 0 || height == 0 || (width == self.width && height == self.height) {
+            return;
+        }
+        let resized = self
+            .pixels
+            .resize_surface(width, height)
+            .and_then(|_| self.pixels.resize_buffer(width, height));
+        match resized {
+            Ok(()) => {
+                self.width = width;
+                self.height = height;
+            }
+            Err(e) => println!("Failed to resize renderer: {:?}", e),
+        }
+    }
+
     pub fn render(&mut self) -> Result<(), pixels::Error> {
         self.pixels.render()
     }
-}
\ No newline at end of file
+}
+
+// 把颜色按覆盖率（0..1）乘以 alpha 后与 frame 中 idx 处的像素混合
+fn blend_coverage(frame: &mut [u8], idx: usize, color: Color, coverage: f32) {
+    let alpha = (coverage.clamp(0.0, 1.0) * color.a as f32).round() as u32;
+    if alpha == 0 {
+        return;
+    }
+    if alpha == 255 {
+        frame[idx..idx + 4].copy_from_slice(&color.to_array());
+        return;
+    }
+    for (c, channel) in [color.r, color.g, color.b].into_iter().enumerate() {
+        let dst = frame[idx + c] as u32;
+        frame[idx + c] = ((channel as u32 * alpha + dst * (255 - alpha)) / 255) as u8;
+    }
+}
+
+// 点到圆角矩形边缘的有向距离，内部为负。按点所在的象限选用对应角的半径
+// 阴影的绘制范围和每个像素中心的覆盖率，阴影形状为空时返回 None
+fn box_shadow_coverage(rect: Rect, radii: CornerRadii, shadow: &BoxShadow) -> Option<(Rect, impl Fn(f32, f32) -> f32)> {
+    let shape = Rect {
+        x: rect.x + shadow.offset_x - shadow.spread,
+        y: rect.y + shadow.offset_y - shadow.spread,
+        width: rect.width + shadow.spread * 2.0,
+        height: rect.height + shadow.spread * 2.0,
+    };
+    if shape.width <= 0.0 || shape.height <= 0.0 {
+        return None;
+    }
+    let radii = radii.fit(rect.width, rect.height);
+    let shape_radii = radii.grow(shadow.spread).fit(shape.width, shape.height);
+    // 高斯分布的标准差取模糊半径的一半，3 倍标准差以外可以忽略
+    let sigma = shadow.blur.max(0.0) / 2.0;
+    let extent = (sigma * 3.0).ceil();
+    let bounds = Rect {
+        x: shape.x - extent,
+        y: shape.y - extent,
+        width: shape.width + extent * 2.0,
+        height: shape.height + extent * 2.0,
+    };
+    let coverage = move |x: f32, y: f32| {
+        let distance = rounded_rect_distance(x, y, shape, shape_radii);
+        let coverage = if sigma > 0.0 {
+            0.5 * (1.0 - erf(distance / (sigma * std::f32::consts::SQRT_2)))
+        } else {
+            edge_coverage(distance)
+        };
+        coverage * (1.0 - edge_coverage(rounded_rect_distance(x, y, rect, radii)))
+    };
+    Some((bounds, coverage))
+}
+
+fn rounded_rect_distance(x: f32, y: f32, rect: Rect, radii: CornerRadii) -> f32 {
+    let (half_width, half_height) = (rect.width / 2.0, rect.height / 2.0);
+    let dx = x - (rect.x + half_width);
+    let dy = y - (rect.y + half_height);
+    let radius = match (dx < 0.0, dy < 0.0) {
+        (true, true) => radii.top_left,
+        (false, true) => radii.top_right,
+        (false, false) => radii.bottom_right,
+        (true, false) => radii.bottom_left,
+    };
+    let qx = dx.abs() - half_width + radius;
+    let qy = dy.abs() - half_height + radius;
+    qx.max(0.0).hypot(qy.max(0.0)) + qx.max(qy).min(0.0) - radius
+}
+
+// 由到边缘的距离估计像素被覆盖的比例，边缘两侧各半个像素过渡
+fn edge_coverage(distance: f32) -> f32 {
+    (0.5 - distance).clamp(0.0, 1.0)
+}
+
+// Abramowitz-Stegun 7.1.26 近似，误差小于 1.5e-7
+fn erf(x: f32) -> f32 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let polynomial = ((((1.0614054 * t - 1.4531521) * t + 1.4214138) * t - 0.28449672) * t + 0.2548296) * t;
+    let y = 1.0 - polynomial * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECT: Rect = Rect { x: 0.0, y: 0.0, width: 100.0, height: 50.0 };
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn distance_to_straight_edges() {
+        let radii = CornerRadii::all(10.0);
+        assert_close(rounded_rect_distance(50.0, 25.0, RECT, radii), -25.0);
+        assert_close(rounded_rect_distance(50.0, -5.0, RECT, radii), 5.0);
+        assert_close(rounded_rect_distance(103.0, 25.0, RECT, radii), 3.0);
+        assert_close(rounded_rect_distance(98.0, 25.0, RECT, radii), -2.0);
+    }
+
+    #[test]
+    fn distance_around_corners() {
+        // 直角：角外的距离是到角点的欧氏距离
+        assert_close(rounded_rect_distance(-3.0, -4.0, RECT, CornerRadii::all(0.0)), 5.0);
+        // 圆角：到圆心 (10, 10) 的距离减去半径
+        let radii = CornerRadii::all(10.0);
+        assert_close(rounded_rect_distance(0.0, 0.0, RECT, radii), 200f32.sqrt() - 10.0);
+        assert_close(rounded_rect_distance(10.0, 10.0, RECT, radii), -10.0);
+        // 每个角使用各自的半径
+        let radii = CornerRadii::new(0.0, 20.0, 0.0, 0.0);
+        assert_close(rounded_rect_distance(0.0, 0.0, RECT, radii), 0.0);
+        assert_close(rounded_rect_distance(100.0, 0.0, RECT, radii), 800f32.sqrt() - 20.0);
+        assert_close(rounded_rect_distance(100.0, 50.0, RECT, radii), 0.0);
+    }
+
+    #[test]
+    fn sharp_shadow_covers_offset_shape_outside_box() {
+        let shadow = BoxShadow::new(10.0, 10.0, 0.0, Color::BLACK);
+        let (bounds, coverage) = box_shadow_coverage(RECT, CornerRadii::all(0.0), &shadow).unwrap();
+        assert_eq!((bounds.x, bounds.y, bounds.width, bounds.height), (10.0, 10.0, 100.0, 50.0));
+        // 盒子内部不画阴影
+        assert_close(coverage(50.5, 25.5), 0.0);
+        assert_close(coverage(105.5, 55.5), 1.0);
+        assert_close(coverage(115.5, 55.5), 0.0);
+        // 盒子边缘上的像素一半被盒子遮住
+        assert_close(coverage(100.0, 45.5), 0.5);
+    }
+
+    #[test]
+    fn blurred_shadow_fades_across_edge() {
+        let shadow = BoxShadow { spread: 5.0, ..BoxShadow::new(0.0, 0.0, 4.0, Color::BLACK) };
+        let (bounds, coverage) = box_shadow_coverage(RECT, CornerRadii::all(0.0), &shadow).unwrap();
+        // 扩展 5，模糊范围 3 倍标准差即 6
+        assert_eq!((bounds.x, bounds.y, bounds.width, bounds.height), (-11.0, -11.0, 122.0, 72.0));
+        // 阴影形状边缘处覆盖率为一半，越往外越淡
+        assert_close(coverage(50.0, -5.0), 0.5);
+        assert!(coverage(50.0, -3.0) > coverage(50.0, -5.0));
+        assert!(coverage(50.0, -7.0) < coverage(50.0, -5.0));
+        assert!(coverage(50.0, -11.0) < 0.01);
+    }
+
+    #[test]
+    fn empty_shadow_shape_is_skipped() {
+        let shadow = BoxShadow { spread: -60.0, ..BoxShadow::new(0.0, 0.0, 0.0, Color::BLACK) };
+        assert!(box_shadow_coverage(RECT, CornerRadii::all(0.0), &shadow).is_none());
+    }
+}
@@ -0,0 +1,489 @@
+mod rich;
+
+use unicode_linebreak::{linebreaks, BreakOpportunity};
+use crate::font::{Font, ShapedLine};
+use crate::layout::Alignment;
+use crate::color::Color;
+use crate::renderer::Renderer;
+
+pub use rich::{parse_markup, SpanStyle, TextSpan};
+
+// 段落的基本方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    // 由段落中第一个强方向字符决定，没有时从左到右
+    Auto,
+    Ltr,
+    Rtl,
+}
+
+// 文本排版参数
+#[derive(Debug, Clone, Copy)]
+pub struct TextOptions {
+    // 行的最大宽度，None 表示不限制
+    pub max_width: Option<f32>,
+    // 超过最大宽度时在单词边界换行，CJK 字符之间可以断开，
+    // 行首、行尾禁则按 Unicode 断行规则 (UAX #14) 处理
+    pub wrap: bool,
+    // 最多显示的行数，多出的内容被截断
+    pub max_lines: Option<usize>,
+    // 被截断的行末尾显示省略号
+    pub ellipsis: bool,
+    // 每行在最大宽度内的水平对齐方式，从右到左的行中 Start 和 End 互换
+    pub align: Alignment,
+    // 行距，相对于字体行高的倍数
+    pub line_spacing: f32,
+    pub direction: TextDirection,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            max_width: None,
+            wrap: false,
+            max_lines: None,
+            ellipsis: false,
+            align: Alignment::Start,
+            line_spacing: 1.0,
+            direction: TextDirection::Auto,
+        }
+    }
+}
+
+// 排版后的一行文本
+#[derive(Debug, Clone)]
+pub struct TextLine {
+    // 实际显示的内容，截断时包含省略号
+    pub text: String,
+    // 对应原文中的字节范围，不含行尾空白
+    pub start: usize,
+    pub end: usize,
+    // 相对于排版区域左上角的位置
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub truncated: bool,
+    // 行的基本方向是否为从右到左
+    pub rtl: bool,
+    // 光标可以停留的位置：(原文中的字节位置, 相对于行左边缘的 x)
+    carets: Vec<(usize, f32)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextLayout {
+    lines: Vec<TextLine>,
+    width: f32,
+    height: f32,
+    line_height: f32,
+}
+
+impl TextLayout {
+    pub fn new(font: &Font, text: &str, options: &TextOptions) -> Self {
+        let max_width = options.max_width.map(|width| width.max(0.0));
+        let measure = |start: usize, end: usize| font.text_width(&text[start..end]) as f32;
+        let mut ranges = break_lines(text, max_width.filter(|_| options.wrap), &measure);
+
+        let truncated_lines = options.max_lines.is_some_and(|max| ranges.len() > max);
+        if let Some(max_lines) = options.max_lines {
+            ranges.truncate(max_lines.max(1));
+        }
+
+        let line_height = font.line_height() as f32;
+        let advance = line_height * options.line_spacing;
+        let count = ranges.len();
+        let mut lines: Vec<TextLine> = ranges
+            .into_iter()
+            .enumerate()
+            .map(|(index, (start, end))| {
+                let end = start + text[start..end].trim_end().len();
+                let content = &text[start..end];
+                let natural_width = font.text_width(content) as f32;
+
+                // 最后一行之后还有内容被截掉，或者不换行时超出了最大宽度
+                let overflow = max_width.is_some_and(|max| natural_width > max) && !options.wrap;
+                let cut = truncated_lines && index == count - 1;
+                // kept 为显示内容中来自原文的部分的长度
+                let (display, kept, truncated) = if options.ellipsis && (overflow || cut) {
+                    let (display, kept) = with_ellipsis(font, content, max_width);
+                    (display, kept, true)
+                } else {
+                    (content.to_string(), content.len(), cut)
+                };
+
+                let shaped = font.shape_line(&display, line_direction(text, start, options.direction));
+                TextLine {
+                    width: shaped.width.round(),
+                    carets: caret_stops(&shaped, start, start + kept, end),
+                    rtl: shaped.rtl,
+                    text: display,
+                    start,
+                    end,
+                    x: 0.0,
+                    y: index as f32 * advance,
+                    truncated,
+                }
+            })
+            .collect();
+
+        let width = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        let align_width = max_width.unwrap_or(width);
+        for line in &mut lines {
+            let align = match (options.align, line.rtl) {
+                (Alignment::Start, true) => Alignment::End,
+                (Alignment::End, true) => Alignment::Start,
+                (align, _) => align,
+            };
+            line.x = match align {
+                Alignment::Start => 0.0,
+                Alignment::Center => ((align_width - line.width) / 2.0).max(0.0),
+                Alignment::End => (align_width - line.width).max(0.0),
+            };
+        }
+
+        Self {
+            height: (count.saturating_sub(1)) as f32 * advance + line_height,
+            lines,
+            width,
+            line_height,
+        }
+    }
+
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
+
+    // 最宽一行的宽度
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        self.lines.iter().any(|line| line.truncated)
+    }
+
+    // 以 (x, y) 为排版区域的左上角绘制所有行
+    pub fn draw(&self, renderer: &mut Renderer, font: &Font, x: f32, y: f32, color: Color) {
+        for line in &self.lines {
+            let direction = if line.rtl { TextDirection::Rtl } else { TextDirection::Ltr };
+            font.draw_text_with_direction(renderer, (x + line.x) as i32, (y + line.y) as i32, &line.text, color, direction);
+        }
+    }
+
+    // 原文字节位置 index 处光标的左上角，相对于排版区域
+    pub fn caret_position(&self, index: usize) -> (f32, f32) {
+        let Some(line) = self.line_of(index) else {
+            return (0.0, 0.0);
+        };
+        (line.x + line.caret_x(index), line.y)
+    }
+
+    // 光标在屏幕上向左移动一个字符后的位置。从右到左的文本中向左是逻辑上的向后，
+    // 到达行的边缘时移到相邻的行
+    pub fn move_caret_left(&self, index: usize) -> usize {
+        self.move_caret(index, false)
+    }
+
+    pub fn move_caret_right(&self, index: usize) -> usize {
+        self.move_caret(index, true)
+    }
+
+    // 离 (x, y) 最近的光标位置，坐标相对于排版区域
+    pub fn caret_at(&self, x: f32, y: f32) -> usize {
+        let row = (y / (self.line_height.max(1.0))).floor().max(0.0) as usize;
+        let Some(line) = self.lines.get(row).or(self.lines.last()) else {
+            return 0;
+        };
+        line.carets
+            .iter()
+            .min_by(|a, b| (a.1 + line.x - x).abs().total_cmp(&(b.1 + line.x - x).abs()))
+            .map(|&(index, _)| index)
+            .unwrap_or(line.start)
+    }
+
+    fn line_of(&self, index: usize) -> Option<&TextLine> {
+        self.lines.iter().rev().find(|line| line.start <= index).or(self.lines.first())
+    }
+
+    fn move_caret(&self, index: usize, right: bool) -> usize {
+        let Some(row) = self.lines.iter().rposition(|line| line.start <= index).or((!self.lines.is_empty()).then_some(0)) else {
+            return index;
+        };
+        let line = &self.lines[row];
+        let current = line.caret_x(index);
+        let next = line
+            .carets
+            .iter()
+            .filter(|stop| if right { stop.1 > current + 0.01 } else { stop.1 < current - 0.01 })
+            .min_by(|a, b| (a.1 - current).abs().total_cmp(&(b.1 - current).abs()));
+        if let Some(&(next, _)) = next {
+            return next;
+        }
+
+        // 越过行的边缘：朝行的阅读方向移动时进入下一行，反之回到上一行
+        if right != line.rtl {
+            self.lines.get(row + 1).map(|line| line.start).unwrap_or(index)
+        } else if row > 0 {
+            self.lines[row - 1].end
+        } else {
+            index
+        }
+    }
+}
+
+impl TextLine {
+    // 光标在行内的 x，位置不在光标停留点上时取它之前最近的停留点
+    fn caret_x(&self, index: usize) -> f32 {
+        self.carets
+            .iter()
+            .filter(|stop| stop.0 <= index)
+            .max_by_key(|stop| stop.0)
+            .map(|stop| stop.1)
+            .unwrap_or(if self.rtl { self.width } else { 0.0 })
+    }
+}
+
+// 按段落第一个强方向字符决定自动方向，同一段落换行后的各行方向一致
+fn line_direction(text: &str, start: usize, direction: TextDirection) -> TextDirection {
+    if direction != TextDirection::Auto {
+        return direction;
+    }
+    let paragraph_start = text[..start].rfind('\n').map_or(0, |index| index + 1);
+    let paragraph_end = text[start..].find('\n').map_or(text.len(), |index| start + index);
+    match unicode_bidi::get_base_direction(&text[paragraph_start..paragraph_end]) {
+        unicode_bidi::Direction::Rtl => TextDirection::Rtl,
+        _ => TextDirection::Ltr,
+    }
+}
+
+// 每个字符簇的逻辑起点：从左到右的字符簇在左边缘，从右到左的在右边缘。
+// 行尾的停留点在行的阅读方向末端，省略号内部没有停留点
+fn caret_stops(shaped: &ShapedLine, start: usize, kept: usize, end: usize) -> Vec<(usize, f32)> {
+    let mut stops: Vec<(usize, f32, f32, bool)> = Vec::new();
+    for glyph in &shaped.glyphs {
+        let index = start + glyph.cluster;
+        if index >= kept {
+            continue;
+        }
+        let (left, right) = (glyph.x, glyph.x + glyph.advance);
+        match stops.iter_mut().find(|stop| stop.0 == index) {
+            Some(stop) => {
+                stop.1 = stop.1.min(left);
+                stop.2 = stop.2.max(right);
+            }
+            None => stops.push((index, left, right, glyph.rtl)),
+        }
+    }
+
+    let mut carets: Vec<(usize, f32)> = stops
+        .into_iter()
+        .map(|(index, left, right, rtl)| (index, if rtl { right } else { left }))
+        .collect();
+    carets.push((end, if shaped.rtl { 0.0 } else { shaped.width }));
+    carets
+}
+
+// 按换行符分段，指定最大宽度时再自动换行，返回每行在原文中的字节范围。
+// measure 返回原文中一段字节范围的宽度，多种字体混排时也可以使用
+pub(crate) fn break_lines(text: &str, max_width: Option<f32>, measure: &dyn Fn(usize, usize) -> f32) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut offset = 0;
+    for raw in text.split('\n') {
+        let paragraph = raw.strip_suffix('\r').unwrap_or(raw);
+        match max_width {
+            Some(max_width) => wrap_paragraph(text, offset, offset + paragraph.len(), max_width, measure, &mut ranges),
+            None => ranges.push((offset, offset + paragraph.len())),
+        }
+        offset += raw.len() + 1;
+    }
+    ranges
+}
+
+// 贪心换行：尽量在断行机会处换行，单个单词比最大宽度还长时按字符断开
+fn wrap_paragraph(
+    text: &str,
+    start: usize,
+    end: usize,
+    max_width: f32,
+    measure: &dyn Fn(usize, usize) -> f32,
+    ranges: &mut Vec<(usize, usize)>,
+) {
+    let fits = |from: usize, to: usize| measure(from, from + text[from..to].trim_end().len()) <= max_width;
+
+    let mut line_start = start;
+    let mut segment_start = start;
+    for (position, opportunity) in linebreaks(&text[start..end]) {
+        let position = start + position;
+        if opportunity == BreakOpportunity::Mandatory && position < end {
+            // 段落内的其他强制换行符（如 U+2028）
+            ranges.push((line_start, position));
+            line_start = position;
+            segment_start = position;
+            continue;
+        }
+        if !fits(line_start, position) && segment_start > line_start {
+            ranges.push((line_start, segment_start));
+            line_start = segment_start;
+        }
+        if !fits(line_start, position) {
+            line_start = break_word(text, line_start, position, max_width, measure, ranges);
+        }
+        segment_start = position;
+    }
+    ranges.push((line_start, end));
+}
+
+// 按字符拆开过长的单词，返回剩余部分的起点
+fn break_word(
+    text: &str,
+    start: usize,
+    end: usize,
+    max_width: f32,
+    measure: &dyn Fn(usize, usize) -> f32,
+    ranges: &mut Vec<(usize, usize)>,
+) -> usize {
+    let mut line_start = start;
+    for (index, c) in text[start..end].char_indices() {
+        let position = start + index;
+        // 每行至少保留一个字符
+        if position > line_start && !c.is_whitespace() && measure(line_start, position + c.len_utf8()) > max_width {
+            ranges.push((line_start, position));
+            line_start = position;
+        }
+    }
+    line_start
+}
+
+// 截断文本并添加省略号，使总宽度不超过最大宽度，同时返回保留的原文长度
+fn with_ellipsis(font: &Font, text: &str, max_width: Option<f32>) -> (String, usize) {
+    let ellipsis = if font.has_glyph('…') { "…" } else { "..." };
+    let Some(max_width) = max_width else {
+        return (format!("{}{}", text, ellipsis), text.len());
+    };
+
+    let available = max_width - font.text_width(ellipsis) as f32;
+    let mut end = 0;
+    for (index, c) in text.char_indices() {
+        let next = index + c.len_utf8();
+        if font.text_width(&text[..next]) as f32 > available {
+            break;
+        }
+        end = next;
+    }
+    let kept = text[..end].trim_end();
+    (format!("{}{}", kept, ellipsis), kept.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 每个字符宽 10
+    fn lines(text: &str, max_width: Option<f32>) -> Vec<&str> {
+        let measure = |start: usize, end: usize| text[start..end].chars().count() as f32 * 10.0;
+        break_lines(text, max_width, &measure)
+            .into_iter()
+            .map(|(start, end)| text[start..end].trim_end())
+            .collect()
+    }
+
+    #[test]
+    fn breaks_at_newlines_and_word_boundaries() {
+        assert_eq!(lines("ab\r\ncd\n", None), ["ab", "cd", ""]);
+        assert_eq!(lines("one two three", Some(75.0)), ["one two", "three"]);
+        assert_eq!(lines("one two", Some(1000.0)), ["one two"]);
+        // 行尾空白不计入宽度
+        assert_eq!(lines("abc   def", Some(30.0)), ["abc", "def"]);
+        assert_eq!(lines("a\u{2028}b", Some(100.0)), ["a", "b"]);
+    }
+
+    #[test]
+    fn breaks_between_cjk_characters() {
+        assert_eq!(lines("你好世界", Some(25.0)), ["你好", "世界"]);
+        // 句号不能出现在行首
+        assert_eq!(lines("你好。世界", Some(25.0)), ["你", "好。", "世界"]);
+    }
+
+    #[test]
+    fn breaks_overlong_words_by_character() {
+        assert_eq!(lines("abcdefg hi", Some(30.0)), ["abc", "def", "g", "hi"]);
+        // 宽度为零时每行仍然至少保留一个字符
+        assert_eq!(lines("abc", Some(0.0)), ["a", "b", "c"]);
+        let text = "abc";
+        let mut ranges = Vec::new();
+        let rest = break_word(text, 0, 3, 15.0, &|start, end| (end - start) as f32 * 10.0, &mut ranges);
+        assert_eq!((ranges, rest), (vec![(0, 1), (1, 2)], 2));
+    }
+
+    #[test]
+    fn ellipsis_fits_within_max_width() {
+        let font = Font::basic();
+        let ellipsis = if font.has_glyph('…') { "…" } else { "..." };
+        let char_width = font.text_width("a") as f32;
+        let max_width = font.text_width(ellipsis) as f32 + char_width * 3.0;
+
+        let (display, kept) = with_ellipsis(&font, "abcdefgh", Some(max_width));
+        assert_eq!((display.as_str(), kept), (format!("abc{}", ellipsis).as_str(), 3));
+        // 保留部分末尾的空白被去掉
+        let (display, kept) = with_ellipsis(&font, "ab cdefgh", Some(max_width));
+        assert_eq!((display.as_str(), kept), (format!("ab{}", ellipsis).as_str(), 2));
+        let (display, kept) = with_ellipsis(&font, "abc", Some(0.0));
+        assert_eq!((display.as_str(), kept), (ellipsis, 0));
+    }
+
+    #[test]
+    fn max_lines_truncates_with_ellipsis() {
+        let font = Font::basic();
+        let char_width = font.text_width("a") as f32;
+        let options = TextOptions {
+            max_width: Some(char_width * 7.0),
+            wrap: true,
+            max_lines: Some(2),
+            ellipsis: true,
+            ..TextOptions::default()
+        };
+        let layout = TextLayout::new(&font, "one two three four", &options);
+        assert_eq!(layout.lines().len(), 2);
+        assert_eq!(layout.lines()[0].text, "one two");
+        assert!(!layout.lines()[0].truncated);
+        assert!(layout.lines()[1].truncated);
+        assert!(layout.lines()[1].text.starts_with("thr"));
+        assert!(layout.lines()[1].width <= char_width * 7.0);
+        assert!(layout.is_truncated());
+        assert_eq!(layout.height(), layout.line_height() * 2.0);
+
+        // max_lines 为 0 时仍显示一行
+        let layout = TextLayout::new(&font, "a\nb", &TextOptions { max_lines: Some(0), ..TextOptions::default() });
+        assert_eq!(layout.lines().len(), 1);
+        assert!(layout.lines()[0].truncated);
+    }
+
+    #[test]
+    fn caret_moves_between_stops_and_lines() {
+        let font = Font::basic();
+        let char_width = font.text_width("a") as f32;
+        let options = TextOptions { max_width: Some(char_width * 3.0), wrap: true, ..TextOptions::default() };
+        let layout = TextLayout::new(&font, "ab cd", &options);
+        assert_eq!(layout.lines().len(), 2);
+
+        assert_eq!(layout.caret_position(1), (char_width, 0.0));
+        assert_eq!(layout.caret_position(4), (char_width, layout.line_height()));
+        assert_eq!(layout.move_caret_right(0), 1);
+        assert_eq!(layout.move_caret_left(0), 0);
+        // 行尾向右移到下一行的行首，行首向左回到上一行的行尾
+        assert_eq!(layout.move_caret_right(2), 3);
+        assert_eq!(layout.move_caret_left(3), 2);
+        assert_eq!(layout.move_caret_right(5), 5);
+
+        assert_eq!(layout.caret_at(char_width * 0.9, 0.0), 1);
+        assert_eq!(layout.caret_at(1000.0, layout.line_height() * 5.0), 5);
+        assert_eq!(layout.caret_at(-10.0, -10.0), 0);
+    }
+}
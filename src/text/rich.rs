@@ -0,0 +1,191 @@
+use crate::color::Color;
+use crate::font::MAX_FONT_SIZE;
+
+// 文本片段的样式，未设置的属性沿用组件的默认值
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub color: Option<Color>,
+    // 背景高亮色
+    pub background: Option<Color>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikethrough: bool,
+    // 以像素为单位的字号
+    pub size: Option<f32>,
+    // 可点击的链接地址
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+impl TextSpan {
+    pub fn new(text: &str, style: SpanStyle) -> Self {
+        Self {
+            text: text.to_string(),
+            style,
+        }
+    }
+}
+
+// parse_markup 识别的标签名
+const TAGS: [&str; 8] = ["b", "i", "u", "s", "color", "bg", "size", "link"];
+
+// 解析类似 BBCode 的标记：
+//   [b]粗体[/b]  [i]斜体[/i]  [u]下划线[/u]  [s]删除线[/s]
+//   [color=#ff8800]颜色[/color]  [bg=yellow]背景[/bg]  [size=24]字号[/size]
+//   [link=https://example.com]链接[/link]
+// 标签可以嵌套，无法识别的方括号按普通文本处理，"[[" 表示一个字面的 "["。
+// 交叉嵌套时先出现的关闭标签同时关闭内层的标签
+pub fn parse_markup(markup: &str) -> Vec<TextSpan> {
+    let mut spans: Vec<TextSpan> = Vec::new();
+    // 已打开的标签及其之前的样式
+    let mut stack: Vec<(String, SpanStyle)> = Vec::new();
+    let mut style = SpanStyle::default();
+    let mut text = String::new();
+    let mut rest = markup;
+
+    while let Some(open) = rest.find('[') {
+        text.push_str(&rest[..open]);
+        rest = &rest[open..];
+
+        if let Some(after) = rest.strip_prefix("[[") {
+            text.push('[');
+            rest = after;
+            continue;
+        }
+
+        let Some(close) = rest.find(']') else {
+            break;
+        };
+        let tag = &rest[1..close];
+        let next_style = if let Some(name) = tag.strip_prefix('/') {
+            // 关闭标签恢复到对应打开标签之前的样式，同时关闭它里面还没关闭的标签；
+            // 没有对应打开标签的已知关闭标签（例如交叉嵌套时后关闭的那个）直接丢弃
+            match stack.iter().rposition(|(open_name, _)| open_name == name) {
+                Some(index) => {
+                    let previous = stack[index].1.clone();
+                    stack.truncate(index);
+                    Some(previous)
+                }
+                None => TAGS.contains(&name).then(|| style.clone()),
+            }
+        } else {
+            apply_tag(&style, tag).inspect(|_| {
+                let name = tag.split('=').next().unwrap_or(tag).to_string();
+                stack.push((name, style.clone()));
+            })
+        };
+
+        match next_style {
+            Some(next_style) => {
+                push_span(&mut spans, &mut text, &style);
+                style = next_style;
+            }
+            None => text.push_str(&rest[..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    text.push_str(rest);
+    push_span(&mut spans, &mut text, &style);
+    spans
+}
+
+fn push_span(spans: &mut Vec<TextSpan>, text: &mut String, style: &SpanStyle) {
+    if text.is_empty() {
+        return;
+    }
+    // 与前一个片段样式相同时合并
+    match spans.last_mut() {
+        Some(last) if last.style == *style => last.text.push_str(text),
+        _ => spans.push(TextSpan {
+            text: std::mem::take(text),
+            style: style.clone(),
+        }),
+    }
+    text.clear();
+}
+
+fn apply_tag(style: &SpanStyle, tag: &str) -> Option<SpanStyle> {
+    let mut style = style.clone();
+    let (name, value) = match tag.split_once('=') {
+        Some((name, value)) => (name, Some(value.trim_matches('\''))),
+        None => (tag, None),
+    };
+    match (name, value) {
+        ("b", None) => style.bold = true,
+        ("i", None) => style.italic = true,
+        ("u", None) => style.underline = true,
+        ("s", None) => style.strikethrough = true,
+        ("color", Some(value)) => style.color = Some(Color::parse(value)?),
+        ("bg", Some(value)) => style.background = Some(Color::parse(value)?),
+        ("size", Some(value)) => {
+            let size: f32 = value.parse().ok().filter(|size: &f32| *size > 0.0 && size.is_finite())?;
+            style.size = Some(size.min(MAX_FONT_SIZE));
+        }
+        ("link", Some(value)) => style.link = Some(value.to_string()),
+        _ => return None,
+    }
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(spans: &[TextSpan]) -> Vec<&str> {
+        spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_nested_tags() {
+        let spans = parse_markup("a[b]b[i]c[/i]d[/b]e");
+        assert_eq!(texts(&spans), ["a", "b", "c", "d", "e"]);
+        assert_eq!(spans[0].style, SpanStyle::default());
+        assert!(spans[1].style.bold && !spans[1].style.italic);
+        assert!(spans[2].style.bold && spans[2].style.italic);
+        assert!(spans[3].style.bold && !spans[3].style.italic);
+        assert_eq!(spans[4].style, SpanStyle::default());
+    }
+
+    #[test]
+    fn closes_misnested_tags_without_leaking_markup() {
+        let spans = parse_markup("[b][i]x[/b][/i]y");
+        assert_eq!(texts(&spans), ["x", "y"]);
+        assert!(spans[0].style.bold && spans[0].style.italic);
+        assert_eq!(spans[1].style, SpanStyle::default());
+        assert_eq!(texts(&parse_markup("x[/b]y")), ["xy"]);
+    }
+
+    #[test]
+    fn keeps_unknown_tags_and_brackets_as_text() {
+        assert_eq!(texts(&parse_markup("[foo]x[/foo]")), ["[foo]x[/foo]"]);
+        assert_eq!(texts(&parse_markup("[[b]] and [b")), ["[b]] and [b"]);
+        assert_eq!(texts(&parse_markup("[b=1]x")), ["[b=1]x"]);
+        assert!(parse_markup("").is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_attribute_values() {
+        for markup in ["[color=nope]x", "[bg=]x", "[size=0]x", "[size=-3]x", "[size=big]x", "[size=inf]x", "[size=NaN]x", "[color]x"] {
+            let spans = parse_markup(markup);
+            assert_eq!(spans.len(), 1, "{:?}", markup);
+            assert_eq!(spans[0].text, markup);
+            assert_eq!(spans[0].style, SpanStyle::default());
+        }
+    }
+
+    #[test]
+    fn parses_attributes_and_links() {
+        let spans = parse_markup("[color=#ff8800][size=1e30]big[/size][/color] [link='https://example.com']go[/link]");
+        assert_eq!(texts(&spans), ["big", " ", "go"]);
+        assert_eq!(spans[0].style.color, Some(Color::rgb(255, 136, 0)));
+        assert_eq!(spans[0].style.size, Some(MAX_FONT_SIZE));
+        assert_eq!(spans[2].style.link.as_deref(), Some("https://example.com"));
+        assert_eq!(spans[2].style.color, None);
+    }
+}
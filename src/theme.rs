@@ -0,0 +1,269 @@
+use std::sync::{Arc, Mutex};
+use winit::event_loop::EventLoopProxy;
+use crate::color::Color;
+use crate::font::Font;
+use crate::window::CustomEvent;
+
+// 界面中各处使用的颜色
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    // 窗口背景
+    pub background: Color,
+    // 对话框、分隔条等面板
+    pub surface: Color,
+    // 菜单栏、对话框标题栏
+    pub surface_variant: Color,
+    // 弹出菜单和下拉列表
+    pub popup: Color,
+    pub tooltip: Color,
+    pub border: Color,
+    // 比 border 更醒目的轮廓，例如提示框边框和滚动条
+    pub outline: Color,
+    // 焦点、选中项和高亮
+    pub accent: Color,
+    // accent 背景上的文本
+    pub on_accent: Color,
+    pub text: Color,
+    // 提示文字
+    pub text_muted: Color,
+    pub text_disabled: Color,
+    pub link: Color,
+    pub link_hover: Color,
+    // 模态对话框下方的半透明遮罩
+    pub backdrop: Color,
+}
+
+// 间距刻度，布局时按需选用，避免到处写不同的数字
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spacing {
+    pub xs: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Radii {
+    pub small: f32,
+    pub medium: f32,
+    pub large: f32,
+}
+
+// 各种文本的字号，None 表示使用 Font::default() 的字号
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSizes {
+    pub small: Option<f32>,
+    pub body: Option<f32>,
+    pub heading: Option<f32>,
+}
+
+// 组件在某个状态下的外观，border 为 None 时不画边框
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateStyle {
+    pub background: Color,
+    pub text: Color,
+    pub border: Option<Color>,
+}
+
+// 组件当前的交互状态
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WidgetState {
+    pub hovered: bool,
+    pub pressed: bool,
+    pub focused: bool,
+    pub disabled: bool,
+}
+
+// 一种组件在各个状态下的外观
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WidgetStyle {
+    pub normal: StateStyle,
+    pub hovered: StateStyle,
+    pub pressed: StateStyle,
+    pub focused: StateStyle,
+    pub disabled: StateStyle,
+}
+
+impl WidgetStyle {
+    // 禁用优先，其次是按下、悬停；获得焦点时在此基础上使用 focused 的边框
+    pub fn resolve(&self, state: WidgetState) -> StateStyle {
+        if state.disabled {
+            return self.disabled;
+        }
+        let mut style = if state.pressed {
+            self.pressed
+        } else if state.hovered {
+            self.hovered
+        } else {
+            self.normal
+        };
+        if state.focused {
+            style.border = self.focused.border.or(style.border);
+        }
+        style
+    }
+}
+
+// 主题：调色板、间距刻度、圆角、字号和各组件的状态样式。
+// 安装在窗口上，内置组件绘制时从中读取颜色，可以在运行时切换
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub palette: Palette,
+    pub spacing: Spacing,
+    pub radii: Radii,
+    pub font_sizes: FontSizes,
+    pub button: WidgetStyle,
+    // 下拉框等输入组件
+    pub input: WidgetStyle,
+    // 菜单标题和菜单项
+    pub menu_item: WidgetStyle,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        let palette = Palette {
+            background: Color::rgb(64, 64, 64),
+            surface: Color::rgb(55, 55, 55),
+            surface_variant: Color::rgb(45, 45, 45),
+            popup: Color::rgb(50, 50, 50),
+            tooltip: Color::rgb(30, 30, 30),
+            border: Color::rgb(90, 90, 90),
+            outline: Color::rgb(115, 115, 115),
+            accent: Color::rgb(70, 110, 170),
+            on_accent: Color::rgb(255, 255, 255),
+            text: Color::rgb(255, 255, 255),
+            text_muted: Color::rgb(150, 150, 150),
+            text_disabled: Color::rgb(130, 130, 130),
+            link: Color::rgb(110, 170, 255),
+            link_hover: Color::rgb(160, 200, 255),
+            backdrop: Color::rgba(0, 0, 0, 128),
+        };
+        let button = StateStyle { background: Color::rgb(80, 80, 80), text: palette.text, border: None };
+        let input = StateStyle { border: Some(palette.border), ..button };
+        Self::from_palette(
+            "dark",
+            palette,
+            WidgetStyle {
+                normal: button,
+                hovered: StateStyle { background: Color::rgb(100, 100, 100), ..button },
+                pressed: StateStyle { background: Color::rgb(60, 60, 60), ..button },
+                focused: StateStyle { border: Some(palette.accent), ..button },
+                disabled: StateStyle { text: palette.text_disabled, ..button },
+            },
+            WidgetStyle {
+                normal: input,
+                hovered: StateStyle { background: Color::rgb(100, 100, 100), ..input },
+                pressed: StateStyle { background: Color::rgb(100, 100, 100), ..input },
+                focused: StateStyle { border: Some(palette.accent), ..input },
+                disabled: StateStyle { text: palette.text_disabled, ..input },
+            },
+        )
+    }
+
+    pub fn light() -> Self {
+        let palette = Palette {
+            background: Color::rgb(240, 240, 240),
+            surface: Color::rgb(250, 250, 250),
+            surface_variant: Color::rgb(225, 225, 225),
+            popup: Color::rgb(255, 255, 255),
+            tooltip: Color::rgb(255, 255, 225),
+            border: Color::rgb(180, 180, 180),
+            outline: Color::rgb(140, 140, 140),
+            accent: Color::rgb(50, 110, 200),
+            on_accent: Color::rgb(255, 255, 255),
+            text: Color::rgb(20, 20, 20),
+            text_muted: Color::rgb(110, 110, 110),
+            text_disabled: Color::rgb(160, 160, 160),
+            link: Color::rgb(20, 90, 200),
+            link_hover: Color::rgb(60, 130, 230),
+            backdrop: Color::rgba(0, 0, 0, 96),
+        };
+        let button = StateStyle { background: Color::rgb(225, 225, 225), text: palette.text, border: None };
+        let input = StateStyle { background: Color::rgb(255, 255, 255), border: Some(palette.border), ..button };
+        Self::from_palette(
+            "light",
+            palette,
+            WidgetStyle {
+                normal: button,
+                hovered: StateStyle { background: Color::rgb(210, 210, 210), ..button },
+                pressed: StateStyle { background: Color::rgb(190, 190, 190), ..button },
+                focused: StateStyle { border: Some(palette.accent), ..button },
+                disabled: StateStyle { text: palette.text_disabled, ..button },
+            },
+            WidgetStyle {
+                normal: input,
+                hovered: StateStyle { background: Color::rgb(245, 245, 245), ..input },
+                pressed: StateStyle { background: Color::rgb(245, 245, 245), ..input },
+                focused: StateStyle { border: Some(palette.accent), ..input },
+                disabled: StateStyle { text: palette.text_disabled, ..input },
+            },
+        )
+    }
+
+    // 菜单项的样式、间距、圆角和字号由调色板推导
+    fn from_palette(name: &str, palette: Palette, button: WidgetStyle, input: WidgetStyle) -> Self {
+        let item = StateStyle { background: palette.popup, text: palette.text, border: None };
+        let highlighted = StateStyle { background: palette.accent, text: palette.on_accent, border: None };
+        Self {
+            name: name.to_string(),
+            palette,
+            spacing: Spacing { xs: 2.0, sm: 4.0, md: 8.0, lg: 16.0, xl: 24.0 },
+            radii: Radii { small: 2.0, medium: 4.0, large: 8.0 },
+            font_sizes: FontSizes { small: None, body: None, heading: None },
+            button,
+            input,
+            menu_item: WidgetStyle {
+                normal: item,
+                hovered: highlighted,
+                pressed: highlighted,
+                focused: highlighted,
+                disabled: StateStyle { text: palette.text_disabled, ..item },
+            },
+        }
+    }
+
+    pub fn body_font(&self) -> Font {
+        Self::sized(self.font_sizes.body)
+    }
+
+    pub fn heading_font(&self) -> Font {
+        Self::sized(self.font_sizes.heading)
+    }
+
+    pub fn small_font(&self) -> Font {
+        Self::sized(self.font_sizes.small)
+    }
+
+    fn sized(size: Option<f32>) -> Font {
+        match size {
+            Some(size) => Font::default().with_size(size),
+            None => Font::default(),
+        }
+    }
+}
+
+// 在回调中切换主题的句柄，新主题在下一次事件循环时生效
+#[derive(Clone)]
+pub struct ThemeHandle {
+    proxy: Arc<Mutex<EventLoopProxy<CustomEvent>>>,
+}
+
+impl ThemeHandle {
+    pub(crate) fn new(proxy: EventLoopProxy<CustomEvent>) -> Self {
+        Self {
+            proxy: Arc::new(Mutex::new(proxy)),
+        }
+    }
+
+    pub fn set_theme(&self, theme: Theme) {
+        let _ = self.proxy.lock().unwrap().send_event(CustomEvent::SetTheme(Arc::new(theme)));
+    }
+}
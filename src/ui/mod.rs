@@ -1,21 +1,24 @@
 pub mod parser;
+pub mod style;
 
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use notify::{Watcher, RecursiveMode, recommended_watcher, Result as NotifyResult};
+use notify::{Watcher, RecursiveMode, recommended_watcher};
 use std::sync::mpsc::channel;
-use std::time::Duration;
-use winit::{
-    event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop, EventLoopProxy},
-    window::WindowBuilder,
-};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use winit::event_loop::EventLoopProxy;
 use crate::window::CustomEvent;
 use std::string::ParseError;
 use crate::debug_log;
 
 pub use parser::parse_ui;
+pub use style::{parse_style, Stylesheet};
+
+// 编辑器保存文件时常常连续触发多个修改事件，两次重新加载之间至少间隔这么久
+const RELOAD_INTERVAL: Duration = Duration::from_millis(200);
 
 #[derive(Debug)]
 pub enum LoaderError {
@@ -42,21 +45,21 @@ impl From<ParseError> for LoaderError {
     }
 }
 
+#[derive(Default)]
 pub struct UiLoader {
     watch_paths: Vec<PathBuf>,
-    watcher: Option<notify::RecommendedWatcher>,
+    // 与监视线程共享，重新加载 .ui 文件后线程要监视新引用的图片所在的目录
+    watcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
     current_path: Option<PathBuf>,
+    // 样式表文件，修改后只重新加载样式，不重建界面。
+    // 与监视线程共享，start_watching 之后再调用 load_style 也会被监视
+    style_path: Arc<Mutex<Option<PathBuf>>>,
     event_proxy: Option<EventLoopProxy<CustomEvent>>,    
 }
 
 impl UiLoader {
     pub fn new() -> Self {
-        Self {
-            watch_paths: Vec::new(),
-            watcher: None,
-            current_path: None,
-            event_proxy: None,
-        }
+        Self::default()
     }
     pub fn set_event_proxy(&mut self, proxy: EventLoopProxy<CustomEvent>) {
         self.event_proxy = Some(proxy);
@@ -79,6 +82,17 @@ impl UiLoader {
         Ok(content)
     }
 
+    // 读取 .style 文件，用 parse_style 解析后交给 Window::set_stylesheet
+    pub fn load_style<P: AsRef<Path>>(&mut self, path: P) -> io::Result<String> {
+        let path = path.as_ref().to_path_buf();
+        debug_log!("Loading style file: {:?}", path);
+
+        let content = std::fs::read_to_string(&path)?;
+        debug_log!("Style file content length: {} bytes", content.len());
+        *self.style_path.lock().unwrap() = Some(path);
+        Ok(content)
+    }
+
     pub fn reload(&self) -> io::Result<()> {
         if let Some(path) = &self.current_path {
             let content = std::fs::read_to_string(path)?;
@@ -88,6 +102,15 @@ impl UiLoader {
                     .expect("Failed to send reload event");
             }
         }
+        let style_path = self.style_path.lock().unwrap().clone();
+        if let Some(path) = &style_path {
+            let content = std::fs::read_to_string(path)?;
+
+            if let Some(proxy) = &self.event_proxy {
+                proxy.send_event(CustomEvent::ReloadStyle(content))
+                    .expect("Failed to send style reload event");
+            }
+        }
         Ok(())
     }
 
@@ -108,27 +131,66 @@ impl UiLoader {
         // 开始监控目录
         watcher.watch(watch_path, RecursiveMode::Recursive)?;        
 
+        let watcher = Arc::new(Mutex::new(watcher));
         let event_proxy = self.event_proxy.clone();
-        let path = self.watch_paths[0].clone();
+        let path = self.watch_paths.first().cloned();
+        let style_path = self.style_path.clone();
+        let thread_watcher = watcher.clone();
         
         std::thread::spawn(move || {
-            let mut last_reload = std::time::Instant::now();
+            let mut last_reload = Instant::now();
+            let mut last_style_reload = Instant::now();
+            let cwd = std::env::current_dir().and_then(|dir| dir.canonicalize()).ok();
+            let mut watched_dirs = HashSet::new();
+            let mut images = match path.as_ref().map(std::fs::read_to_string) {
+                Some(Ok(content)) => watch_images(&thread_watcher, &content, cwd.as_deref(), &mut watched_dirs),
+                _ => Vec::new(),
+            };
 
             loop {
                 if let Ok(event) = rx.recv() {
                     debug_log!("Received file system event: {:?}", event);
-                    if let Ok(notify::Event { kind: notify::EventKind::Modify(_), .. }) = event {
-                        let now = std::time::Instant::now();
-                        let duration = now.duration_since(last_reload).as_millis();
-                        debug_log!("Time since last reload: {}ms", duration);                        
-                        // 确保两次重载之间至少间隔 100ms
-                        if duration > 200 {
+                    if let Ok(notify::Event { kind: notify::EventKind::Modify(_), paths, .. }) = event {
+                        // 样式表修改时只重新加载样式，界面和组件状态保持不变
+                        let current_style = style_path.lock().unwrap().clone();
+                        if let Some(style_path) = current_style.filter(|style| paths.iter().any(|path| same_file(path, style))) {
+                            let now = Instant::now();
+                            if now.duration_since(last_style_reload) <= RELOAD_INTERVAL {
+                                debug_log!("Skipping style reload due to time threshold");
+                                continue;
+                            }
+                            if let Some(proxy) = &event_proxy {
+                                std::thread::sleep(Duration::from_millis(100));
+                                match std::fs::read_to_string(&style_path) {
+                                    Ok(content) => match proxy.send_event(CustomEvent::ReloadStyle(content)) {
+                                        Ok(_) => last_style_reload = now,
+                                        Err(e) => debug_log!("Failed to send style reload event: {:?}", e),
+                                    },
+                                    Err(e) => debug_log!("Failed to read style file: {}", e),
+                                }
+                            }
+                            continue;
+                        }
+                        // 当前目录以外只有 .ui 文件引用的图片修改才重新加载
+                        let inside = |changed: &Path| match &cwd {
+                            Some(cwd) => changed.is_relative() || changed.starts_with(cwd),
+                            None => true,
+                        };
+                        if !paths.iter().any(|changed| inside(changed) || images.iter().any(|image| same_file(changed, image))) {
+                            continue;
+                        }
+                        let Some(path) = &path else { continue };
+                        let now = Instant::now();
+                        let duration = now.duration_since(last_reload);
+                        debug_log!("Time since last reload: {}ms", duration.as_millis());
+                        if duration > RELOAD_INTERVAL {
                             debug_log!("Attempting to reload file");
                             if let Some(proxy) = &event_proxy {
                                 std::thread::sleep(Duration::from_millis(100));
-                                match std::fs::read_to_string(&path) {
+                                match std::fs::read_to_string(path) {
                                     Ok(content) => {
                                         debug_log!("Successfully read file, content length: {}", content.len());
+                                        images = watch_images(&thread_watcher, &content, cwd.as_deref(), &mut watched_dirs);
                                         match proxy.send_event(CustomEvent::Reload(content)) {
                                             Ok(_) => {
                                                 debug_log!("Successfully sent reload event");
@@ -155,4 +217,38 @@ impl UiLoader {
         self.watcher = Some(watcher);
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+// 监视 .ui 文件引用的、位于当前目录以外的图片所在的目录（当前目录已经递归监视），返回图片路径。
+// 监视目录而不是文件本身，编辑器保存时先删除再创建文件也能收到事件
+fn watch_images(
+    watcher: &Mutex<notify::RecommendedWatcher>,
+    content: &str,
+    cwd: Option<&Path>,
+    watched_dirs: &mut HashSet<PathBuf>,
+) -> Vec<PathBuf> {
+    let images = parser::image_paths(content);
+    for image in &images {
+        let Some(dir) = image.parent().map(|dir| if dir.as_os_str().is_empty() { Path::new(".") } else { dir }) else { continue };
+        let Ok(dir) = dir.canonicalize() else { continue };
+        if cwd.is_some_and(|cwd| dir.starts_with(cwd)) || watched_dirs.contains(&dir) {
+            continue;
+        }
+        debug_log!("Watching image directory: {:?}", dir);
+        match watcher.lock().unwrap().watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                watched_dirs.insert(dir);
+            }
+            Err(e) => debug_log!("Failed to watch image directory: {:?}", e),
+        }
+    }
+    images
+}
+
+// 文件系统事件中的路径可能是绝对路径，也可能相对于监视的目录
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.ends_with(b),
+    }
+}
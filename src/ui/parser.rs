@@ -1,6 +1,8 @@
 use std::io;
-use crate::{Container, Button, Text};
-use crate::layout::{Direction, Alignment, Padding};
+use std::path::PathBuf;
+use crate::{Container, ConstraintLayout, Grid, Splitter, Stack, Button, Text, Image, RichText, Widget};
+use crate::image::{ImageFit, ScaleFilter};
+use crate::layout::{Direction, Alignment, Anchor, Breakpoint, ConstraintError, FlexItem, GridCell, LayoutDirection, Length, Padding, Rect, SplitPane, StackItem, Track};
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -14,60 +16,537 @@ impl From<io::Error> for ParseError {
     }
 }
 
+impl From<ConstraintError> for ParseError {
+    fn from(err: ConstraintError) -> Self {
+        ParseError::InvalidFormat(format!("invalid constraint: {:?}", err))
+    }
+}
+
+// 文件中的一行或一个用花括号包围的块
+enum Node<'a> {
+    Line(&'a str),
+    Block(&'a str, Vec<Node<'a>>),
+}
+
+// 支持嵌套的块：
+//   Container { direction: horizontal  breakpoint: 600 vertical  ...带 margin=、width=50%、grow= 的子组件... }
+//   Grid { columns: auto 1fr  rows: auto auto  gap: 8  ...带 row=、col= 的子组件... }
+//   Stack { ...带 anchor=、x=、y=、width=50%、z= 的子组件... }
+//   Constraints { width: 400  height: 200  ...带 id= 的子组件...  ok.right == parent.right - 8 }
+//   Splitter { direction: horizontal  state: layout.split  ...带 ratio=、min=、max=、collapsible 的子组件... }
+// 每种块都可以用 layout_direction: rtl 让这个子树从右到左镜像显示
+// Button、Text、RichText 和 Container 可以带样式表使用的 id=save class=danger,wide
+// 文件只有一个 Container 块时它就是根容器，否则所有内容放进一个默认的根容器
 pub fn parse_ui(content: &str) -> Result<Container, ParseError> {
-    // 这里先实现一个简单的解析器
-    let mut lines = content.lines();
-    let mut container = Container::new(0.0, 0.0, 800.0, 600.0);
+    let nodes = parse_nodes(&mut content.lines(), false)?;
+    let root = Rect { x: 0.0, y: 0.0, width: 800.0, height: 600.0 };
+    match nodes.as_slice() {
+        [Node::Block(header, children)] if header.starts_with("Container") => build_container(header, children, root),
+        _ => build_container("Container", &nodes, root),
+    }
+}
 
+fn parse_nodes<'a>(lines: &mut impl Iterator<Item = &'a str>, nested: bool) -> Result<Vec<Node<'a>>, ParseError> {
+    let mut nodes = Vec::new();
     while let Some(line) = lines.next() {
         let line = line.trim();
-        if line.starts_with("Container") {
-            // 解析容器属性
-            let _ = parse_container(&mut container, line);
-        } else if line.starts_with("Button") {
-            // 解析按钮
-            if let Some(button) = parse_button(line) {
-                container.add_child(button);
+        if line == "}" {
+            if nested {
+                return Ok(nodes);
             }
-        } else if line.starts_with("Text") {
-            // 解析文本
-            if let Some(text) = parse_text(line) {
-                container.add_child(text);
+            return Err(ParseError::InvalidFormat("unexpected '}'".to_string()));
+        }
+        if let Some(header) = line.strip_suffix('{') {
+            let children = parse_nodes(lines, true)?;
+            nodes.push(Node::Block(header.trim(), children));
+        } else if !line.is_empty() {
+            nodes.push(Node::Line(line));
+        }
+    }
+    if nested {
+        return Err(ParseError::InvalidFormat("missing '}'".to_string()));
+    }
+    Ok(nodes)
+}
+
+// "key: value" 形式的块属性
+fn property(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    let key = key.trim();
+    key.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        .then_some((key, value.trim()))
+}
+
+// .ui 和 .style 文件中的数值属性，inf 和 NaN 会让布局计算失控，一律拒绝
+pub(super) fn parse_number(key: &str, value: &str) -> Result<f32, ParseError> {
+    value
+        .trim_end_matches("px")
+        .parse::<f32>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| ParseError::InvalidFormat(format!("invalid {}: {}", key, value)))
+}
+
+fn parse_alignment(value: &str) -> Option<Alignment> {
+    match value {
+        "start" => Some(Alignment::Start),
+        "center" => Some(Alignment::Center),
+        "end" => Some(Alignment::End),
+        _ => None,
+    }
+}
+
+fn build_container(header: &str, nodes: &[Node], rect: Rect) -> Result<Container, ParseError> {
+    let container = Container::new(rect.x, rect.y, rect.width, rect.height);
+    let mut container = apply_style_names(container, header, Container::with_id, Container::with_class);
+    let _ = parse_container(&mut container, header);
+
+    for node in nodes {
+        let Node::Line(line) = node else { continue };
+        let Some((key, value)) = property(line) else { continue };
+        match key {
+            "direction" | "alignment" => {
+                let _ = parse_container(&mut container, value);
+            }
+            "padding" => container = container.with_padding(Padding::all(parse_number(key, value)?)),
+            "spacing" => container = container.with_spacing(parse_number(key, value)?),
+            "wrap" => container = container.with_wrap(value == "true"),
+            "line_spacing" => container = container.with_line_spacing(parse_number(key, value)?),
+            "line_alignment" => {
+                if let Some(alignment) = parse_alignment(value) {
+                    container = container.with_line_alignment(alignment);
+                }
             }
+            "breakpoint" => container = container.with_breakpoint(parse_breakpoint(value)?),
+            "layout_direction" => container = container.with_layout_direction(parse_layout_direction(value)?),
+            _ => {}
         }
     }
 
+    for node in nodes {
+        let line = match node {
+            Node::Line(line) => line,
+            Node::Block(header, _) => header,
+        };
+        if let Some(widget) = build_widget(node)? {
+            container.add_boxed(widget, parse_flex_item(line)?);
+        }
+    }
     Ok(container)
 }
 
-fn parse_container(container: &mut Container, line: &str) -> Result<(), ParseError> {
-    // 简单的属性解析
-    if line.contains("vertical") {
-        container.with_direction(Direction::Vertical);
+// 样式表中选择组件用的名字：id=save class=danger,wide，写在引号内的文本之后，块写在块头中
+fn apply_style_names<W>(mut widget: W, line: &str, with_id: fn(W, &str) -> W, with_class: fn(W, &str) -> W) -> W {
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        match option.split_once('=') {
+            Some(("id", id)) => widget = with_id(widget, id),
+            Some(("class", classes)) => {
+                for class in classes.split(',').filter(|class| !class.is_empty()) {
+                    widget = with_class(widget, class);
+                }
+            }
+            _ => {}
+        }
     }
-    if line.contains("horizontal") {
-        container.with_direction(Direction::Horizontal);
+    widget
+}
+
+fn parse_layout_direction(value: &str) -> Result<LayoutDirection, ParseError> {
+    LayoutDirection::parse(value).ok_or_else(|| ParseError::InvalidFormat(format!("invalid layout_direction: {}", value)))
+}
+
+// 容器宽度小于给定值时的布局参数：breakpoint: 600 vertical center wrap spacing=4
+fn parse_breakpoint(value: &str) -> Result<Breakpoint, ParseError> {
+    let mut words = value.split_whitespace();
+    let max_width = words.next().unwrap_or("");
+    let mut breakpoint = Breakpoint::below(parse_number("breakpoint", max_width)?);
+    for word in words {
+        breakpoint = match word {
+            "vertical" => breakpoint.with_direction(Direction::Vertical),
+            "horizontal" => breakpoint.with_direction(Direction::Horizontal),
+            "wrap" => breakpoint.with_wrap(true),
+            "nowrap" => breakpoint.with_wrap(false),
+            _ => match (word.strip_prefix("spacing="), parse_alignment(word)) {
+                (Some(spacing), _) => breakpoint.with_spacing(parse_number("spacing", spacing)?),
+                (None, Some(alignment)) => breakpoint.with_alignment(alignment),
+                (None, None) => return Err(ParseError::InvalidFormat(format!("invalid breakpoint: {}", value))),
+            },
+        };
     }
-    if line.contains("center") {
-        container.with_alignment(Alignment::Center);
+    Ok(breakpoint)
+}
+
+// 容器子组件的弹性参数：margin=8 width=50% height=40 grow=1 shrink=0，写在引号内的文本之后
+fn parse_flex_item(line: &str) -> Result<FlexItem, ParseError> {
+    let mut item = FlexItem::default();
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        let Some((key, value)) = option.split_once('=') else { continue };
+        let length = || Length::parse(value).ok_or_else(|| ParseError::InvalidFormat(format!("invalid {}: {}", key, value)));
+        match key {
+            "margin" => item = item.with_margin(Padding::all(parse_number(key, value)?)),
+            "width" => item = item.with_width(length()?),
+            "height" => item = item.with_height(length()?),
+            "grow" => item = item.with_grow(parse_number(key, value)?),
+            "shrink" => item = item.with_shrink(parse_number(key, value)?),
+            _ => {}
+        }
     }
-    Ok(())
+    Ok(item)
 }
 
-fn parse_button(line: &str) -> Option<Button> {
-    // 简单的按钮解析
-    if let Some(label) = line.split('"').nth(1) {
-        Some(Button::new(0.0, 0.0, 200.0, 50.0, label))
+fn build_grid(nodes: &[Node]) -> Result<Grid, ParseError> {
+    let mut grid = Grid::new(0.0, 0.0, 0.0, 0.0);
+    for node in nodes {
+        let Node::Line(line) = node else { continue };
+        let Some((key, value)) = property(line) else { continue };
+        grid = match key {
+            "columns" => grid.with_columns(parse_tracks(value)?),
+            "rows" => grid.with_rows(parse_tracks(value)?),
+            "gap" => grid.with_gap(parse_number(key, value)?),
+            "column_gap" => grid.with_column_gap(parse_number(key, value)?),
+            "row_gap" => grid.with_row_gap(parse_number(key, value)?),
+            "padding" => grid.with_padding(Padding::all(parse_number(key, value)?)),
+            "layout_direction" => grid.with_layout_direction(parse_layout_direction(value)?),
+            _ => grid,
+        };
+    }
+
+    for node in nodes {
+        let line = match node {
+            Node::Line(line) => line,
+            Node::Block(header, _) => header,
+        };
+        if let Some(widget) = build_widget(node)? {
+            grid.add_boxed(widget, parse_cell(line)?);
+        }
+    }
+    Ok(grid)
+}
+
+fn build_stack(nodes: &[Node]) -> Result<Stack, ParseError> {
+    let mut stack = Stack::new(0.0, 0.0, 0.0, 0.0);
+    for node in nodes {
+        let line = match node {
+            Node::Line(line) => {
+                match property(line) {
+                    Some(("padding", value)) => stack = stack.with_padding(Padding::all(parse_number("padding", value)?)),
+                    Some(("layout_direction", value)) => stack = stack.with_layout_direction(parse_layout_direction(value)?),
+                    _ => {}
+                }
+                line
+            }
+            Node::Block(header, _) => header,
+        };
+        if let Some(widget) = build_widget(node)? {
+            stack.add_boxed(widget, parse_stack_item(line)?);
+        }
+    }
+    Ok(stack)
+}
+
+fn build_constraints(nodes: &[Node]) -> Result<ConstraintLayout, ParseError> {
+    let (mut width, mut height) = (0.0, 0.0);
+    let mut direction = None;
+    for node in nodes {
+        let Node::Line(line) = node else { continue };
+        match property(line) {
+            Some(("width", value)) => width = parse_number("width", value)?,
+            Some(("height", value)) => height = parse_number("height", value)?,
+            Some(("layout_direction", value)) => direction = Some(parse_layout_direction(value)?),
+            _ => {}
+        }
+    }
+
+    let mut layout = ConstraintLayout::new(0.0, 0.0, width, height);
+    if let Some(direction) = direction {
+        layout = layout.with_layout_direction(direction);
+    }
+    for node in nodes {
+        let line = match node {
+            Node::Line(line) => line,
+            Node::Block(header, _) => header,
+        };
+        if let Some(widget) = build_widget(node)? {
+            layout.add_boxed(parse_id(line), widget)?;
+        }
+    }
+    // 约束可以引用写在它后面的组件，所以在添加完所有子组件后再添加
+    for node in nodes {
+        if let Node::Line(line) = node {
+            if is_constraint(line) {
+                layout.add_constraint(line)?;
+            }
+        }
+    }
+    Ok(layout)
+}
+
+fn build_splitter(nodes: &[Node]) -> Result<Splitter, ParseError> {
+    let mut splitter = Splitter::new(0.0, 0.0, 0.0, 0.0);
+    for node in nodes {
+        let Node::Line(line) = node else { continue };
+        let Some((key, value)) = property(line) else { continue };
+        splitter = match key {
+            "direction" if value == "vertical" => splitter.with_direction(Direction::Vertical),
+            "direction" if value == "horizontal" => splitter.with_direction(Direction::Horizontal),
+            "state" => splitter.with_state_file(value),
+            _ => splitter,
+        };
+    }
+
+    for node in nodes {
+        let line = match node {
+            Node::Line(line) => line,
+            Node::Block(header, _) => header,
+        };
+        if let Some(widget) = build_widget(node)? {
+            splitter.add_boxed(widget, parse_split_pane(line)?);
+        }
+    }
+    Ok(splitter)
+}
+
+// 分割容器的窗格参数：ratio=0.3 min=120 max=400 collapsible
+fn parse_split_pane(line: &str) -> Result<SplitPane, ParseError> {
+    let mut pane = SplitPane::default();
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        if option == "collapsible" {
+            pane = pane.with_collapsible(true);
+            continue;
+        }
+        let Some((key, value)) = option.split_once('=') else { continue };
+        pane = match key {
+            "ratio" => pane.with_ratio(parse_number(key, value)?),
+            "min" => pane.with_min_size(parse_number(key, value)?),
+            "max" => pane.with_max_size(parse_number(key, value)?),
+            _ => pane,
+        };
+    }
+    Ok(pane)
+}
+
+fn is_constraint(line: &str) -> bool {
+    !line.contains('"') && ["==", "<=", ">="].iter().any(|operator| line.contains(operator))
+}
+
+// 约束布局中子组件的名字：id=ok，写在引号内的文本之后
+fn parse_id(line: &str) -> &str {
+    let options = line.rsplit('"').next().unwrap_or("");
+    options
+        .split_whitespace()
+        .find_map(|option| option.strip_prefix("id="))
+        .unwrap_or("")
+}
+
+// 层叠容器子组件的位置：anchor=bottom-right x=-8 y=-8 width=50% height=20% z=1 margin=8
+fn parse_stack_item(line: &str) -> Result<StackItem, ParseError> {
+    let mut item = StackItem::default();
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        let Some((key, value)) = option.split_once('=') else { continue };
+        let invalid = || ParseError::InvalidFormat(format!("invalid {}: {}", key, value));
+        match key {
+            "anchor" => item.anchor = Anchor::parse(value).ok_or_else(invalid)?,
+            "x" => item.offset_x = parse_number(key, value)?,
+            "y" => item.offset_y = parse_number(key, value)?,
+            "width" => item = item.with_width_percent(parse_number(key, value.trim_end_matches('%'))?),
+            "height" => item = item.with_height_percent(parse_number(key, value.trim_end_matches('%'))?),
+            "z" => item.z_index = value.parse().map_err(|_| invalid())?,
+            "margin" => item = item.with_margin(Padding::all(parse_number(key, value)?)),
+            _ => {}
+        }
+    }
+    Ok(item)
+}
+
+// columns: auto 120 1fr
+fn parse_tracks(value: &str) -> Result<Vec<Track>, ParseError> {
+    value
+        .split_whitespace()
+        .map(|track| Track::parse(track).ok_or_else(|| ParseError::InvalidFormat(format!("invalid track: {}", track))))
+        .collect()
+}
+
+// 网格子组件的位置：row=0 col=1 rowspan=2 colspan=2 align=center valign=end margin=4，
+// 写在引号内的文本之后
+fn parse_cell(line: &str) -> Result<GridCell, ParseError> {
+    let mut cell = GridCell::new(0, 0);
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        let Some((key, value)) = option.split_once('=') else { continue };
+        let index = || value.parse::<usize>().map_err(|_| ParseError::InvalidFormat(format!("invalid {}: {}", key, value)));
+        match key {
+            "row" => cell.row = index()?,
+            "col" | "column" => cell.column = index()?,
+            "rowspan" => cell = cell.with_span(index()?, cell.column_span),
+            "colspan" => cell = cell.with_span(cell.row_span, index()?),
+            "align" => cell.align_x = parse_alignment(value),
+            "valign" => cell.align_y = parse_alignment(value),
+            "margin" => cell = cell.with_margin(Padding::all(parse_number(key, value)?)),
+            _ => {}
+        }
+    }
+    Ok(cell)
+}
+
+// 组件行或嵌套的块，属性行返回 None
+fn build_widget(node: &Node) -> Result<Option<Box<dyn Widget>>, ParseError> {
+    let line = match node {
+        Node::Block(header, children) if header.starts_with("Container") => {
+            let rect = Rect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 };
+            return Ok(Some(Box::new(build_container(header, children, rect)?)));
+        }
+        Node::Block(header, children) if header.starts_with("Grid") => return Ok(Some(Box::new(build_grid(children)?))),
+        Node::Block(header, children) if header.starts_with("Stack") => return Ok(Some(Box::new(build_stack(children)?))),
+        Node::Block(header, children) if header.starts_with("Constraints") => {
+            return Ok(Some(Box::new(build_constraints(children)?)));
+        }
+        Node::Block(header, children) if header.starts_with("Splitter") => return Ok(Some(Box::new(build_splitter(children)?))),
+        Node::Block(..) => return Ok(None),
+        Node::Line(line) => *line,
+    };
+
+    let widget: Option<Box<dyn Widget>> = if line.starts_with("Button") {
+        // 解析按钮
+        parse_button(line).map(|button| Box::new(button) as Box<dyn Widget>)
+    } else if line.starts_with("Image") {
+        // 解析图片
+        parse_image(line).map(|image| Box::new(image) as Box<dyn Widget>)
+    } else if line.starts_with("RichText") {
+        // 解析富文本
+        parse_rich_text(line).map(|text| Box::new(text) as Box<dyn Widget>)
+    } else if line.starts_with("Text") {
+        // 解析文本
+        parse_text(line).map(|text| Box::new(text) as Box<dyn Widget>)
     } else {
         None
+    };
+    Ok(widget)
+}
+
+fn parse_container(container: &mut Container, line: &str) -> Result<(), ParseError> {
+    // 简单的属性解析
+    for word in line.split_whitespace() {
+        match word {
+            "vertical" => {
+                container.with_direction(Direction::Vertical);
+            }
+            "horizontal" => {
+                container.with_direction(Direction::Horizontal);
+            }
+            _ => {
+                if let Some(alignment) = parse_alignment(word) {
+                    container.with_alignment(alignment);
+                }
+            }
+        }
     }
+    Ok(())
+}
+
+fn parse_button(line: &str) -> Option<Button> {
+    // 简单的按钮解析
+    line.split('"')
+        .nth(1)
+        .map(|label| apply_style_names(Button::new(0.0, 0.0, 200.0, 50.0, label), line, Button::with_id, Button::with_class))
 }
 
 fn parse_text(line: &str) -> Option<Text> {
     // 简单的文本解析
-    if let Some(content) = line.split('"').nth(1) {
-        Some(Text::new(0.0, 0.0, content))
-    } else {
-        None
+    line.split('"')
+        .nth(1)
+        .map(|content| apply_style_names(Text::new(0.0, 0.0, content), line, Text::with_id, Text::with_class))
+}
+
+// RichText "标记文本" [wrap] [center|end]
+// 标记语法见 parse_markup，例如 "[b]粗体[/b] [link=https://example.com]链接[/link]"
+fn parse_rich_text(line: &str) -> Option<RichText> {
+    let start = line.find('"')?;
+    let end = line.rfind('"').filter(|&end| end > start)?;
+    let mut text = RichText::from_markup(0.0, 0.0, &line[start + 1..end]);
+
+    for option in line[end + 1..].split_whitespace() {
+        text = match option {
+            "wrap" => text.with_wrap(true),
+            "center" => text.with_align(Alignment::Center),
+            "end" => text.with_align(Alignment::End),
+            _ => text,
+        };
+    }
+    Some(apply_style_names(text, line, RichText::with_id, RichText::with_class))
+}
+
+// Image "path" [contain|cover|fill|none] [nearest|bilinear] [directional]
+// directional 表示图标有方向，从右到左的布局中水平镜像
+// 图片路径相对于当前工作目录，读取失败时显示占位框。UiLoader 会同时监视 .ui 文件引用的图片，
+// 图片修改后重新加载整个 .ui 文件，图片随之重新读取
+// .ui 文件中所有 Image 引用的路径，供 UiLoader 监视
+pub(super) fn image_paths(content: &str) -> Vec<PathBuf> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("Image"))
+        .filter_map(|line| line.split('"').nth(1))
+        .map(PathBuf::from)
+        .collect()
+}
+
+fn parse_image(line: &str) -> Option<Image> {
+    let path = line.split('"').nth(1)?;
+    let mut image = Image::from_file(0.0, 0.0, path);
+
+    let options = line.rsplit('"').next().unwrap_or("");
+    for option in options.split_whitespace() {
+        image = match option {
+            "contain" => image.with_fit(ImageFit::Contain),
+            "cover" => image.with_fit(ImageFit::Cover),
+            "fill" => image.with_fit(ImageFit::Fill),
+            "none" => image.with_fit(ImageFit::None),
+            "nearest" => image.with_filter(ScaleFilter::Nearest),
+            "bilinear" => image.with_filter(ScaleFilter::Bilinear),
+            "directional" => image.with_directional(true),
+            _ => image,
+        };
+    }
+    Some(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_finite_numbers() {
+        assert_eq!(parse_number("padding", "12").unwrap(), 12.0);
+        assert_eq!(parse_number("padding", "12px").unwrap(), 12.0);
+        assert_eq!(parse_number("grow", "-1.5").unwrap(), -1.5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rejects_non_finite_numbers() {
+        for value in ["inf", "-inf", "infinity", "NaN", "nan", "1e39", "-1e39px", "", "px", "12pt"] {
+            assert!(parse_number("padding", value).is_err(), "{:?}", value);
+        }
+        assert!(Length::parse("inf").is_none());
+        assert!(Length::parse("1e39%").is_none());
+    }
+
+    #[test]
+    fn rejects_non_finite_ui_properties() {
+        for content in [
+            "Container {\n padding: inf\n}",
+            "Container {\n spacing: NaN\n}",
+            "Text \"a\" grow=inf",
+            "Text \"a\" width=1e39",
+            "Stack {\n Text \"a\" x=nan\n}",
+        ] {
+            assert!(parse_ui(content).is_err(), "{:?}", content);
+        }
+        assert!(parse_ui("Container {\n padding: 8px\n Text \"a\" grow=1 width=50%\n}").is_ok());
+        assert!(parse_ui("Stack {\n Text \"a\" x=3\n}").is_ok());
+    }
+}
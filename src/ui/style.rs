@@ -0,0 +1,522 @@
+use crate::box_style::{Border, BoxShadow, BoxStyle, CornerRadii};
+use crate::layout::Padding;
+use crate::color::Color;
+use crate::theme::{StateStyle, WidgetState};
+use super::parser::{parse_number, ParseError};
+
+// .style 文件：用 CSS 风格的选择器设置组件外观，例如
+//   Button { background: #505050; corner-radius: 4 }
+//   Button:hover { background: #646464 }
+//   #save { color: white }
+//   .danger, .danger:hover { background: #b43c3c; border: 2px solid #ff7070 }
+//   Container.panel { padding: 8 16; spacing: 12; border-radius: 8 8 0 0; box-shadow: 0 2 6 #00000080 }
+// 选择器由类型名（或 *）、#id、.class 和 :hover、:active、:focus、:disabled 组合而成，逗号分隔多个选择器。
+// 多条规则设置同一属性时，特异性高的规则生效（id 多于 class 和状态，再多于类型名），特异性相同时后写的生效。
+// 样式表中的属性覆盖主题和组件自身的设置，没有设置的属性保持原样
+
+// 选择器中的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PseudoState {
+    Hover,
+    // 按下，也可以写作 :pressed
+    Active,
+    Focus,
+    Disabled,
+}
+
+impl PseudoState {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "hover" => Some(PseudoState::Hover),
+            "active" | "pressed" => Some(PseudoState::Active),
+            "focus" => Some(PseudoState::Focus),
+            "disabled" => Some(PseudoState::Disabled),
+            _ => None,
+        }
+    }
+
+    fn matches(self, state: WidgetState) -> bool {
+        match self {
+            PseudoState::Hover => state.hovered,
+            PseudoState::Active => state.pressed,
+            PseudoState::Focus => state.focused,
+            PseudoState::Disabled => state.disabled,
+        }
+    }
+}
+
+// 一个复合选择器，例如 Button#save.danger:hover。不支持后代和子组件选择器
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selector {
+    // None 表示任意类型
+    pub kind: Option<String>,
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    pub states: Vec<PseudoState>,
+}
+
+impl Selector {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        let mut selector = Selector::default();
+        let end = text.find(['#', '.', ':']).unwrap_or(text.len());
+        match &text[..end] {
+            "" | "*" => {}
+            kind if is_name(kind) => selector.kind = Some(kind.to_string()),
+            _ => return None,
+        }
+
+        let mut rest = &text[end..];
+        while let Some(prefix) = rest.chars().next() {
+            let body = &rest[1..];
+            let end = body.find(['#', '.', ':']).unwrap_or(body.len());
+            let name = &body[..end];
+            if !is_name(name) {
+                return None;
+            }
+            match prefix {
+                '#' => selector.id = Some(name.to_string()),
+                '.' => selector.classes.push(name.to_string()),
+                _ => selector.states.push(PseudoState::parse(name)?),
+            }
+            rest = &body[end..];
+        }
+        Some(selector)
+    }
+
+    // (id 数量, class 和状态数量, 类型名数量)，按字典序比较
+    pub fn specificity(&self) -> (usize, usize, usize) {
+        (
+            self.id.is_some() as usize,
+            self.classes.len() + self.states.len(),
+            self.kind.is_some() as usize,
+        )
+    }
+
+    pub fn matches(&self, kind: &str, id: Option<&str>, classes: &[String], state: WidgetState) -> bool {
+        self.kind.as_deref().is_none_or(|expected| expected == kind)
+            && self.id.as_deref().is_none_or(|expected| Some(expected) == id)
+            && self.classes.iter().all(|class| classes.contains(class))
+            && self.states.iter().all(|pseudo| pseudo.matches(state))
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// 样式表为组件计算出的属性，None 表示没有规则设置这个属性
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StyleProperties {
+    pub background: Option<Color>,
+    // 文本颜色
+    pub color: Option<Color>,
+    pub border_color: Option<Color>,
+    // 0 表示不画边框
+    pub border_width: Option<f32>,
+    pub padding: Option<Padding>,
+    // 容器中子组件之间的间距
+    pub spacing: Option<f32>,
+    pub corner_radii: Option<CornerRadii>,
+    // box-shadow: none 解析为透明的阴影，覆盖组件自身的阴影
+    pub box_shadow: Option<BoxShadow>,
+}
+
+impl StyleProperties {
+    // other 中设置了的属性覆盖当前的值
+    fn merge(&mut self, other: &StyleProperties) {
+        self.background = other.background.or(self.background);
+        self.color = other.color.or(self.color);
+        self.border_color = other.border_color.or(self.border_color);
+        self.border_width = other.border_width.or(self.border_width);
+        self.padding = other.padding.or(self.padding);
+        self.spacing = other.spacing.or(self.spacing);
+        self.corner_radii = other.corner_radii.or(self.corner_radii);
+        self.box_shadow = other.box_shadow.or(self.box_shadow);
+    }
+
+    // 用这些属性覆盖主题中的状态样式。与 CSS 一样，只设置了边框宽度时边框使用文本颜色
+    pub fn apply(&self, base: StateStyle) -> StateStyle {
+        let text = self.color.unwrap_or(base.text);
+        let border = match self.border_width {
+            Some(width) if width <= 0.0 => None,
+            Some(_) => Some(self.border_color.or(base.border).unwrap_or(text)),
+            None => self.border_color.or(base.border),
+        };
+        StateStyle {
+            background: self.background.unwrap_or(base.background),
+            text,
+            border,
+        }
+    }
+
+    // 用这些属性覆盖组件的背景样式，与 apply 一样只设置了边框宽度时边框使用 border_color 颜色
+    pub fn apply_box(&self, base: BoxStyle, border_color: Color) -> BoxStyle {
+        let border = match (self.border_width, self.border_color) {
+            (Some(width), _) if width <= 0.0 => None,
+            (None, None) => base.border,
+            (width, color) => Some(Border::new(
+                width.or(base.border.map(|border| border.width)).unwrap_or(1.0),
+                color.or(base.border.map(|border| border.color)).unwrap_or(border_color),
+            )),
+        };
+        BoxStyle {
+            background: self.background.or(base.background),
+            border,
+            corner_radii: self.corner_radii.unwrap_or(base.corner_radii),
+            shadows: match self.box_shadow {
+                Some(shadow) => Some(shadow).filter(BoxShadow::is_visible).into_iter().collect(),
+                None => base.shadows,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StyleRule {
+    selector: Selector,
+    properties: StyleProperties,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Stylesheet {
+    // 按在文件中出现的顺序排列，逗号分隔的选择器各占一条
+    rules: Vec<StyleRule>,
+}
+
+impl Stylesheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    // 计算某种状态下组件的属性：按特异性从低到高依次应用匹配的规则，
+    // 排序是稳定的，特异性相同的规则保持文件中的顺序
+    pub fn resolve(&self, kind: &str, id: Option<&str>, classes: &[String], state: WidgetState) -> StyleProperties {
+        let mut matched: Vec<&StyleRule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.selector.matches(kind, id, classes, state))
+            .collect();
+        matched.sort_by_key(|rule| rule.selector.specificity());
+
+        let mut properties = StyleProperties::default();
+        for rule in matched {
+            properties.merge(&rule.properties);
+        }
+        properties
+    }
+}
+
+// 解析 .style 文件，支持 /* */ 注释，声明之间用分号或换行分隔
+pub fn parse_style(content: &str) -> Result<Stylesheet, ParseError> {
+    let content = strip_comments(content);
+    let mut rules = Vec::new();
+    let mut rest = content.as_str();
+    while !rest.trim().is_empty() {
+        let open = rest
+            .find('{')
+            .ok_or_else(|| ParseError::InvalidFormat(format!("missing '{{' after: {}", rest.trim())))?;
+        let close = rest[open..]
+            .find('}')
+            .map(|close| open + close)
+            .ok_or_else(|| ParseError::InvalidFormat("missing '}'".to_string()))?;
+        let body = &rest[open + 1..close];
+        if body.contains('{') {
+            return Err(ParseError::InvalidFormat("unexpected '{'".to_string()));
+        }
+
+        let properties = parse_declarations(body)?;
+        for text in rest[..open].split(',') {
+            let selector = Selector::parse(text)
+                .ok_or_else(|| ParseError::InvalidFormat(format!("invalid selector: {}", text.trim())))?;
+            rules.push(StyleRule { selector, properties });
+        }
+        rest = &rest[close + 1..];
+    }
+    Ok(Stylesheet { rules })
+}
+
+fn strip_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = rest[start + 2..].find("*/").map_or("", |end| &rest[start + 2 + end + 2..]);
+    }
+    result.push_str(rest);
+    result
+}
+
+// 属性名中的下划线和连字符等价，未知的属性被忽略
+fn parse_declarations(body: &str) -> Result<StyleProperties, ParseError> {
+    let mut properties = StyleProperties::default();
+    for declaration in body.split([';', '\n']) {
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+        let (key, value) = declaration
+            .split_once(':')
+            .ok_or_else(|| ParseError::InvalidFormat(format!("invalid declaration: {}", declaration)))?;
+        let key = key.trim().replace('_', "-");
+        let value = value.trim();
+        match key.as_str() {
+            "background" | "background-color" => properties.background = Some(parse_style_color(&key, value)?),
+            "color" => properties.color = Some(parse_style_color(&key, value)?),
+            "border" => parse_border(value, &mut properties)?,
+            "border-color" => properties.border_color = Some(parse_style_color(&key, value)?),
+            "border-width" => properties.border_width = Some(parse_number(&key, value)?),
+            "padding" => properties.padding = Some(parse_padding(value)?),
+            "spacing" | "gap" => properties.spacing = Some(parse_number(&key, value)?),
+            "corner-radius" | "border-radius" => properties.corner_radii = Some(parse_corner_radii(value)?),
+            "box-shadow" | "shadow" => properties.box_shadow = Some(parse_box_shadow(value)?),
+            _ => {}
+        }
+    }
+    Ok(properties)
+}
+
+fn parse_style_color(key: &str, value: &str) -> Result<Color, ParseError> {
+    Color::parse(value).ok_or_else(|| ParseError::InvalidFormat(format!("invalid {}: {}", key, value)))
+}
+
+// border: none | [宽度] [solid] [颜色]
+fn parse_border(value: &str, properties: &mut StyleProperties) -> Result<(), ParseError> {
+    for word in split_words(value) {
+        match word {
+            "none" => properties.border_width = Some(0.0),
+            "solid" => {}
+            _ if word.starts_with(|c: char| c.is_ascii_digit() || c == '.') => {
+                properties.border_width = Some(parse_number("border", word)?);
+            }
+            _ => properties.border_color = Some(parse_style_color("border", word)?),
+        }
+    }
+    Ok(())
+}
+
+// box-shadow: none | 水平偏移 垂直偏移 [模糊半径 [扩展]] 颜色
+fn parse_box_shadow(value: &str) -> Result<BoxShadow, ParseError> {
+    if value == "none" {
+        return Ok(BoxShadow::default());
+    }
+    let mut lengths = Vec::new();
+    let mut color = None;
+    for word in split_words(value) {
+        if word.starts_with(|c: char| c.is_ascii_digit() || c == '.' || c == '-') {
+            lengths.push(parse_number("box-shadow", word)?);
+        } else {
+            color = Some(parse_style_color("box-shadow", word)?);
+        }
+    }
+    let (offset_x, offset_y, blur, spread) = match lengths.as_slice() {
+        [x, y] => (*x, *y, 0.0, 0.0),
+        [x, y, blur] => (*x, *y, *blur, 0.0),
+        [x, y, blur, spread] => (*x, *y, *blur, *spread),
+        _ => return Err(ParseError::InvalidFormat(format!("invalid box-shadow: {}", value))),
+    };
+    // 与 CSS 相同，没有写颜色时使用半透明的黑色
+    let color = color.unwrap_or(Color::rgba(0, 0, 0, 128));
+    Ok(BoxShadow::new(offset_x, offset_y, blur, color).with_spread(spread))
+}
+
+// 按空白分词，括号内的空白不分开，使 rgb(255, 0, 0) 这样的颜色保持完整
+fn split_words(value: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    words.push(&value[start..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        words.push(&value[start..]);
+    }
+    words
+}
+
+// 与 CSS 相同：一个值用于四边；两个值为上下、左右；三个值为上、左右、下；四个值为上、右、下、左
+fn parse_padding(value: &str) -> Result<Padding, ParseError> {
+    let values = parse_numbers("padding", value)?;
+    let (top, right, bottom, left) = match values.as_slice() {
+        [all] => (*all, *all, *all, *all),
+        [vertical, horizontal] => (*vertical, *horizontal, *vertical, *horizontal),
+        [top, horizontal, bottom] => (*top, *horizontal, *bottom, *horizontal),
+        [top, right, bottom, left] => (*top, *right, *bottom, *left),
+        _ => return Err(ParseError::InvalidFormat(format!("invalid padding: {}", value))),
+    };
+    Ok(Padding { left, right, top, bottom })
+}
+
+// 与 CSS 相同：一个值用于四个角；两个值为左上和右下、右上和左下；
+// 三个值为左上、右上和左下、右下；四个值为左上、右上、右下、左下
+fn parse_corner_radii(value: &str) -> Result<CornerRadii, ParseError> {
+    let values = parse_numbers("border-radius", value)?;
+    let radii = match values.as_slice() {
+        [all] => CornerRadii::all(*all),
+        [main, cross] => CornerRadii::new(*main, *cross, *main, *cross),
+        [top_left, cross, bottom_right] => CornerRadii::new(*top_left, *cross, *bottom_right, *cross),
+        [top_left, top_right, bottom_right, bottom_left] => CornerRadii::new(*top_left, *top_right, *bottom_right, *bottom_left),
+        _ => return Err(ParseError::InvalidFormat(format!("invalid border-radius: {}", value))),
+    };
+    Ok(radii)
+}
+
+fn parse_numbers(key: &str, value: &str) -> Result<Vec<f32>, ParseError> {
+    value.split_whitespace().map(|word| parse_number(key, word)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE: WidgetState = WidgetState { hovered: false, pressed: false, focused: false, disabled: false };
+    const HOVERED: WidgetState = WidgetState { hovered: true, ..IDLE };
+
+    fn classes(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn background(sheet: &Stylesheet, kind: &str, id: Option<&str>, names: &[&str], state: WidgetState) -> Option<Color> {
+        sheet.resolve(kind, id, &classes(names), state).background
+    }
+
+    #[test]
+    fn parses_compound_selectors() {
+        let selector = Selector::parse(" Button#save.danger.big:hover:pressed ").unwrap();
+        assert_eq!(selector.kind.as_deref(), Some("Button"));
+        assert_eq!(selector.id.as_deref(), Some("save"));
+        assert_eq!(selector.classes, classes(&["danger", "big"]));
+        assert_eq!(selector.states, [PseudoState::Hover, PseudoState::Active]);
+
+        assert_eq!(Selector::parse("*"), Some(Selector::default()));
+        assert_eq!(Selector::parse(".panel").unwrap().classes, classes(&["panel"]));
+        assert_eq!(Selector::parse(":focus").unwrap().states, [PseudoState::Focus]);
+        for text in ["", "Button:unknown", "Button#", "Button..a", "Button Text", "Button>Text", "#a b"] {
+            assert_eq!(Selector::parse(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn matches_type_id_class_and_state() {
+        let selector = Selector::parse("Button#save.danger:hover").unwrap();
+        let names = classes(&["danger", "wide"]);
+        assert!(selector.matches("Button", Some("save"), &names, HOVERED));
+        assert!(!selector.matches("Text", Some("save"), &names, HOVERED));
+        assert!(!selector.matches("Button", Some("open"), &names, HOVERED));
+        assert!(!selector.matches("Button", None, &names, HOVERED));
+        assert!(!selector.matches("Button", Some("save"), &classes(&["wide"]), HOVERED));
+        assert!(!selector.matches("Button", Some("save"), &names, IDLE));
+        let disabled = WidgetState { disabled: true, ..IDLE };
+        assert!(Selector::parse(":disabled").unwrap().matches("Text", None, &[], disabled));
+    }
+
+    #[test]
+    fn orders_specificity() {
+        let specificity = |text: &str| Selector::parse(text).unwrap().specificity();
+        assert_eq!(specificity("*"), (0, 0, 0));
+        assert_eq!(specificity("Button.a.b:hover"), (0, 3, 1));
+        assert!(specificity("Button") < specificity(".a"));
+        assert!(specificity(".a") < specificity("Button.a"));
+        assert!(specificity("Button.a.b:hover") < specificity("#save"));
+    }
+
+    #[test]
+    fn resolves_by_specificity_then_source_order() {
+        let sheet = parse_style(
+            "#save { background: #000003 }
+             Button.danger { background: #000002 }
+             Button { background: #000001; color: white }
+             .danger { background: #000004 }
+             .primary { background: #000005 }",
+        )
+        .unwrap();
+        let blue = |b| Some(Color::rgb(0, 0, b));
+        assert_eq!(background(&sheet, "Button", None, &[], IDLE), blue(1));
+        assert_eq!(background(&sheet, "Button", None, &["danger"], IDLE), blue(2));
+        assert_eq!(background(&sheet, "Button", Some("save"), &["danger"], IDLE), blue(3));
+        // 特异性相同时后写的规则生效
+        assert_eq!(background(&sheet, "Text", None, &["danger", "primary"], IDLE), blue(5));
+        // 没有被覆盖的属性保留
+        assert_eq!(sheet.resolve("Button", Some("save"), &[], IDLE).color, Some(Color::WHITE));
+        assert_eq!(sheet.resolve("Text", None, &[], IDLE), StyleProperties::default());
+    }
+
+    #[test]
+    fn state_rules_apply_only_in_state() {
+        let sheet = parse_style("Button { background: #000001 }\nButton:hover, .x { background: #000002 }").unwrap();
+        assert_eq!(background(&sheet, "Button", None, &[], IDLE), Some(Color::rgb(0, 0, 1)));
+        assert_eq!(background(&sheet, "Button", None, &[], HOVERED), Some(Color::rgb(0, 0, 2)));
+        assert_eq!(background(&sheet, "Text", None, &["x"], IDLE), Some(Color::rgb(0, 0, 2)));
+    }
+
+    #[test]
+    fn parses_declarations() {
+        let properties = parse_declarations(
+            "border: 2px solid #ff0000; padding: 8 16\n border_radius: 1 2 3; box-shadow: 0 2 6 #00000080; gap: 4; unknown: 1",
+        )
+        .unwrap();
+        assert_eq!(properties.border_width, Some(2.0));
+        assert_eq!(properties.border_color, Some(Color::rgb(255, 0, 0)));
+        assert_eq!(properties.padding, Some(Padding { left: 16.0, right: 16.0, top: 8.0, bottom: 8.0 }));
+        assert_eq!(properties.corner_radii, Some(CornerRadii::new(1.0, 2.0, 3.0, 2.0)));
+        assert_eq!(properties.box_shadow, Some(BoxShadow::new(0.0, 2.0, 6.0, Color::rgba(0, 0, 0, 128))));
+        assert_eq!(properties.spacing, Some(4.0));
+
+        let properties = parse_declarations("border: none; box-shadow: none; padding: 1 2 3 4").unwrap();
+        assert_eq!(properties.border_width, Some(0.0));
+        assert_eq!(properties.box_shadow, Some(BoxShadow::default()));
+        assert_eq!(properties.padding, Some(Padding { left: 4.0, right: 2.0, top: 1.0, bottom: 3.0 }));
+        let shadow = parse_declarations("box-shadow: 1 2 3 4 rgb(0, 0, 0)").unwrap().box_shadow.unwrap();
+        assert_eq!((shadow.blur, shadow.spread, shadow.color), (3.0, 4.0, Color::BLACK));
+    }
+
+    #[test]
+    fn rejects_malformed_declarations() {
+        for body in [
+            "background",
+            "background: nope",
+            "border: 2px dotted",
+            "border: 1e39",
+            "border-width: wide",
+            "padding: 1 2 3 4 5",
+            "padding: a",
+            "padding:",
+            "border-radius: 1 2 3 4 5",
+            "border-radius: inf",
+            "box-shadow: 1 #000",
+            "box-shadow: 1 2 3 4 5",
+            "box-shadow: 1 2 nope",
+            "spacing: NaN",
+        ] {
+            assert!(parse_declarations(body).is_err(), "{:?}", body);
+        }
+    }
+
+    #[test]
+    fn parses_style_files() {
+        let sheet = parse_style("/* 注释 */ Button, Text:hover { color: #010203 } /* 未闭合").unwrap();
+        assert_eq!(sheet.rules.len(), 2);
+        assert!(parse_style("").unwrap().is_empty());
+        for content in ["Button { color: red", "Button color: red }", "Button { a { } }", "Button! { color: red }"] {
+            assert!(parse_style(content).is_err(), "{:?}", content);
+        }
+    }
+}
@@ -1,16 +1,27 @@
 use std::sync::Arc;
 use winit::event::WindowEvent;
-use super::Widget;
+use super::{StyleState, Widget};
+use crate::box_style::BoxStyle;
 use crate::renderer::Renderer;
+use crate::theme::{Theme, WidgetState};
 use crate::font::Font;
-use crate::layout::Rect;
+use crate::layout::{Rect, Alignment, Padding};
+use crate::text::{TextLayout, TextOptions};
+use crate::ui::style::Stylesheet;
+
+// 文本与按钮边缘的最小间距
+const LABEL_PADDING: f32 = 8.0;
 
 pub struct Button {
     rect: Rect,
     label: String,
+    font: Font,
+    // 通过 with_font 指定字体后不再使用主题的字号
+    custom_font: bool,
     is_hovered: bool,
     is_pressed: bool,
     on_click: Option<Arc<dyn Fn() + Send + Sync>>,
+    style: StyleState,
 }
 
 impl Button {
@@ -18,53 +29,75 @@ impl Button {
         Self {
             rect: Rect { x, y, width, height },
             label: label.to_string(),
+            font: Font::default(),
+            custom_font: false,
             is_hovered: false,
             is_pressed: false,
             on_click: None,
+            style: StyleState::new("Button"),
         }
     }
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self.custom_font = true;
+        self
+    }
+
+    // 样式表中用 #id 选择这个按钮
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.style.set_id(id);
+        self
+    }
+
+    // 样式表中用 .class 选择这个按钮，可以多次调用添加多个 class
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.style.add_class(class);
+        self
+    }
+
     pub fn on_click<F>(mut self, callback: F) -> Self 
     where
         F: Fn() + Send + Sync + 'static,
     {
         self.on_click = Some(Arc::new(callback));
         self
-    }    
+    }
+
+    // 标签居中显示，放不下时以省略号截断
+    fn label_layout(&self, max_width: Option<f32>) -> TextLayout {
+        let options = TextOptions {
+            max_width,
+            ellipsis: true,
+            align: Alignment::Center,
+            ..TextOptions::default()
+        };
+        TextLayout::new(&self.font, &self.label, &options)
+    }
+
+    fn padding(&self) -> Padding {
+        self.style.normal().padding.unwrap_or(Padding::all(LABEL_PADDING))
+    }
 }
 
 impl Widget for Button {
     fn draw(&self, renderer: &mut Renderer) {
-        // 绘制按钮背景
-        let color = if self.is_pressed {
-            [60, 60, 60, 255]  // 按下状态
-        } else if self.is_hovered {
-            [100, 100, 100, 255]  // 悬停状态
-        } else {
-            [80, 80, 80, 255]  // 普通状态
+        // 按当前状态从主题中取背景、文本和边框颜色，样式表中的属性覆盖主题
+        let state = WidgetState {
+            hovered: self.is_hovered,
+            pressed: self.is_pressed,
+            ..WidgetState::default()
         };
-        
-        renderer.draw_rect(
-            self.rect.x as i32,
-            self.rect.y as i32,
-            self.rect.width as u32,
-            self.rect.height as u32,
-            color,
-        );
+        let properties = self.style.resolve(state);
+        let theme_style = renderer.theme().button.resolve(state);
+        let style = properties.apply(theme_style);
+        let base = BoxStyle::from(theme_style).with_corner_radius(renderer.theme().radii.small);
+        renderer.draw_box(self.rect, &properties.apply_box(base, style.text));
 
         // 绘制按钮文本
-        let font = Font::default();
-        let text_x = self.rect.x as i32 + (self.rect.width as i32 - (self.label.len() * 8) as i32) / 2;
-        let text_y = self.rect.y as i32 + (self.rect.height as i32 - 8) / 2;
-
-        for (i, c) in self.label.chars().enumerate() {
-            font.render_char(
-                renderer,
-                text_x + (i * 8) as i32,
-                text_y,
-                c,
-                [255, 255, 255, 255]
-            );
-        }        
+        let padding = self.padding();
+        let layout = self.label_layout(Some(self.rect.width - padding.horizontal()));
+        let text_y = self.rect.y + (self.rect.height - layout.height()) / 2.0;
+        layout.draw(renderer, &self.font, self.rect.x + padding.left, text_y, style.text);
     }
 
     fn handle_event(&mut self, event: &WindowEvent) {
@@ -82,10 +115,8 @@ impl Widget for Button {
                 state: winit::event::ElementState::Pressed,
                 button: winit::event::MouseButton::Left,
                 ..
-            } => {
-                if self.is_hovered {
-                    self.is_pressed = true;
-                }
+            } if self.is_hovered => {
+                self.is_pressed = true;
             }
             WindowEvent::MouseInput { 
                 state: winit::event::ElementState::Released,
@@ -111,6 +142,22 @@ impl Widget for Button {
     }
 
     fn preferred_size(&self) -> (f32, f32) {
-        (200.0, 50.0)  // 按钮的默认大小
+        // 默认大小为 200x50，标签较长时加宽
+        let layout = self.label_layout(None);
+        let padding = self.padding();
+        (
+            (layout.width() + padding.horizontal()).max(200.0),
+            (layout.height() + padding.vertical()).max(50.0),
+        )
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        if !self.custom_font {
+            self.font = theme.body_font();
+        }
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.style.apply(sheet);
     }    
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+use std::time::Instant;
+use winit::event::WindowEvent;
+use winit::window::CursorIcon;
+use super::Widget;
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::layout::LayoutDirection;
+
+// 容器把事件、绘制和查询转发给子组件的通用实现。
+// 子组件按添加顺序绘制，后添加的在上层，所以查询弹出层事件和光标时从后往前找
+
+pub(crate) fn draw<'a>(children: impl IntoIterator<Item = &'a Box<dyn Widget>>, renderer: &mut Renderer) {
+    for child in children {
+        child.draw(renderer);
+    }
+}
+
+pub(crate) fn handle_event<'a>(children: impl IntoIterator<Item = &'a mut Box<dyn Widget>>, event: &WindowEvent) {
+    for child in children {
+        child.handle_event(event);
+    }
+}
+
+pub(crate) fn draw_overlay<'a>(children: impl IntoIterator<Item = &'a Box<dyn Widget>>, renderer: &mut Renderer) {
+    for child in children {
+        child.draw_overlay(renderer);
+    }
+}
+
+pub(crate) fn handle_overlay_event<'a, I>(children: I, event: &WindowEvent) -> bool
+where
+    I: IntoIterator<Item = &'a mut Box<dyn Widget>>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    children.into_iter().rev().any(|child| child.handle_overlay_event(event))
+}
+
+pub(crate) fn next_wakeup<'a>(children: impl IntoIterator<Item = &'a Box<dyn Widget>>) -> Option<Instant> {
+    children.into_iter().filter_map(|child| child.next_wakeup()).min()
+}
+
+pub(crate) fn cursor<'a, I>(children: I) -> Option<CursorIcon>
+where
+    I: IntoIterator<Item = &'a Box<dyn Widget>>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    children.into_iter().rev().find_map(|child| child.cursor())
+}
+
+pub(crate) fn set_theme(children: &mut [Box<dyn Widget>], theme: &Theme) {
+    for child in children {
+        child.set_theme(theme);
+    }
+}
+
+pub(crate) fn set_layout_direction(children: &mut [Box<dyn Widget>], direction: LayoutDirection) {
+    for child in children {
+        child.set_layout_direction(direction);
+    }
+}
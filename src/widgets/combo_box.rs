@@ -0,0 +1,628 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, MouseScrollDelta, VirtualKeyCode, WindowEvent};
+use super::{StyleState, Widget};
+use crate::color::Color;
+use crate::box_style::BoxStyle;
+use crate::renderer::Renderer;
+use crate::theme::WidgetState;
+use crate::font::Font;
+use crate::layout::{place_popup, LayoutDirection, Placement, Rect};
+use crate::text::{TextLayout, TextOptions};
+use crate::ui::style::Stylesheet;
+
+const ROW_HEIGHT: f32 = 20.0;
+const POPUP_BORDER: f32 = 1.0;
+const TEXT_PADDING: f32 = 6.0;
+const ARROW_WIDTH: f32 = 20.0;
+const SCROLLBAR_WIDTH: f32 = 6.0;
+const DEFAULT_MAX_VISIBLE: usize = 8;
+// 超过这个时间没有输入，输入查找重新开始
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+type SelectCallback = Arc<dyn Fn(usize, &str) + Send + Sync>;
+
+// 下拉选择框，可编辑模式下输入的内容会过滤选项
+pub struct ComboBox {
+    rect: Rect,
+    options: Vec<String>,
+    selected: Option<usize>,
+    editable: bool,
+    text: String,
+    // 可编辑模式下光标在 text 中的字节位置
+    caret: usize,
+    max_visible: usize,
+    focused: bool,
+    hovered: bool,
+    open: bool,
+    // 弹出列表中显示的选项下标
+    filtered: Vec<usize>,
+    // filtered 中高亮的位置
+    highlighted: usize,
+    scroll: usize,
+    popup: Rect,
+    cursor: (f32, f32),
+    viewport: Rect,
+    modifiers: ModifiersState,
+    type_ahead: String,
+    last_typed: Option<Instant>,
+    on_select: Option<SelectCallback>,
+    // 从右到左时下拉箭头和列表的滚动条在左侧
+    rtl: bool,
+    style: StyleState,
+}
+
+impl ComboBox {
+    pub fn new(x: f32, y: f32, width: f32, height: f32, options: &[&str]) -> Self {
+        Self {
+            rect: Rect { x, y, width, height },
+            options: options.iter().map(|option| option.to_string()).collect(),
+            selected: None,
+            editable: false,
+            text: String::new(),
+            caret: 0,
+            max_visible: DEFAULT_MAX_VISIBLE,
+            focused: false,
+            hovered: false,
+            open: false,
+            filtered: Vec::new(),
+            highlighted: 0,
+            scroll: 0,
+            popup: Rect { x, y, width: 0.0, height: 0.0 },
+            cursor: (0.0, 0.0),
+            viewport: Rect { x: 0.0, y: 0.0, width: f32::MAX, height: f32::MAX },
+            modifiers: ModifiersState::empty(),
+            type_ahead: String::new(),
+            last_typed: None,
+            on_select: None,
+            rtl: false,
+            style: StyleState::new("ComboBox"),
+        }
+    }
+
+    pub fn with_selected(mut self, index: usize) -> Self {
+        if index < self.options.len() {
+            self.selected = Some(index);
+            self.text = self.options[index].clone();
+            self.caret = self.text.len();
+        }
+        self
+    }
+
+    pub fn with_editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    // 弹出列表最多显示的行数，超出时滚动
+    pub fn with_max_visible(mut self, max_visible: usize) -> Self {
+        self.max_visible = max_visible.max(1);
+        self
+    }
+
+    // 样式表中用 #id 选择这个下拉框
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.style.set_id(id);
+        self
+    }
+
+    // 样式表中用 .class 选择这个下拉框，可以多次调用添加多个 class
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.style.add_class(class);
+        self
+    }
+
+    pub fn on_select<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, &str) + Send + Sync + 'static,
+    {
+        self.on_select = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_value(&self) -> Option<&str> {
+        self.selected.map(|index| self.options[index].as_str())
+    }
+
+    fn choose(&mut self, index: usize) {
+        self.selected = Some(index);
+        self.text = self.options[index].clone();
+        self.caret = self.text.len();
+        self.close();
+        if let Some(callback) = &self.on_select {
+            callback(index, &self.options[index]);
+        }
+    }
+
+    fn open_list(&mut self) {
+        self.open = true;
+        self.refilter();
+        if let Some(position) = self.selected.and_then(|index| self.filtered.iter().position(|&i| i == index)) {
+            self.highlighted = position;
+        }
+        self.ensure_visible();
+    }
+
+    fn close(&mut self) {
+        self.open = false;
+        self.scroll = 0;
+    }
+
+    // 可编辑模式下按输入内容过滤，不区分大小写
+    fn refilter(&mut self) {
+        let filter = if self.editable { self.text.to_lowercase() } else { String::new() };
+        self.filtered = (0..self.options.len())
+            .filter(|&index| self.options[index].to_lowercase().contains(&filter))
+            .collect();
+        self.highlighted = 0;
+        self.scroll = 0;
+        self.place_list();
+    }
+
+    // 列表与输入框同宽，高度随过滤后的选项数变化
+    fn place_list(&mut self) {
+        let rows = self.filtered.len().clamp(1, self.max_visible);
+        let height = rows as f32 * ROW_HEIGHT + POPUP_BORDER * 2.0;
+        self.popup = place_popup(self.rect, self.rect.width, height, self.viewport, Placement::Below);
+    }
+
+    fn visible_rows(&self) -> usize {
+        self.filtered.len().min(self.max_visible)
+    }
+
+    fn ensure_visible(&mut self) {
+        let visible = self.visible_rows();
+        if self.highlighted < self.scroll {
+            self.scroll = self.highlighted;
+        } else if visible > 0 && self.highlighted >= self.scroll + visible {
+            self.scroll = self.highlighted + 1 - visible;
+        }
+    }
+
+    fn scroll_by(&mut self, rows: i32) {
+        let max_scroll = self.filtered.len().saturating_sub(self.visible_rows());
+        self.scroll = (self.scroll as i32 + rows).clamp(0, max_scroll as i32) as usize;
+    }
+
+    fn row_at(&self, x: f32, y: f32) -> Option<usize> {
+        if !self.popup.contains(x, y) {
+            return None;
+        }
+        let row = ((y - self.popup.y - POPUP_BORDER) / ROW_HEIGHT).floor();
+        if row < 0.0 {
+            return None;
+        }
+        let position = self.scroll + row as usize;
+        (position < self.filtered.len()).then_some(position)
+    }
+
+    fn move_highlight(&mut self, delta: i32) {
+        if self.filtered.is_empty() {
+            return;
+        }
+        let last = self.filtered.len() as i32 - 1;
+        self.highlighted = (self.highlighted as i32 + delta).clamp(0, last) as usize;
+        self.ensure_visible();
+    }
+
+    // 输入查找：跳到第一个以已输入内容开头的选项
+    fn type_ahead(&mut self, c: char) -> Option<usize> {
+        let now = Instant::now();
+        let expired = self
+            .last_typed
+            .map(|time| now.duration_since(time) > TYPE_AHEAD_TIMEOUT)
+            .unwrap_or(true);
+        if expired {
+            self.type_ahead.clear();
+        }
+        // 空格只作为查找内容的一部分，不能作为开头
+        if c == ' ' && self.type_ahead.is_empty() {
+            return None;
+        }
+        self.last_typed = Some(now);
+        self.type_ahead.extend(c.to_lowercase());
+
+        let prefix = self.type_ahead.clone();
+        self.options
+            .iter()
+            .position(|option| option.to_lowercase().starts_with(&prefix))
+    }
+
+    fn edit_text(&mut self, c: char) {
+        if c == '\u{8}' {
+            let Some(previous) = self.text[..self.caret].chars().next_back() else {
+                return;
+            };
+            self.caret -= previous.len_utf8();
+            self.text.remove(self.caret);
+        } else if c == '\u{7f}' {
+            if self.caret >= self.text.len() {
+                return;
+            }
+            self.text.remove(self.caret);
+        } else if !c.is_control() {
+            self.text.insert(self.caret, c);
+            self.caret += c.len_utf8();
+        } else {
+            return;
+        }
+        self.open = true;
+        self.refilter();
+    }
+
+    // 可编辑模式下移动光标的按键，左右方向键按视觉顺序移动，在从右到左的文本中同样直观
+    fn move_caret(&mut self, key: VirtualKeyCode) -> bool {
+        let layout = self.text_layout(&Font::default(), &self.text);
+        self.caret = match key {
+            VirtualKeyCode::Left => layout.move_caret_left(self.caret),
+            VirtualKeyCode::Right => layout.move_caret_right(self.caret),
+            VirtualKeyCode::Home => 0,
+            VirtualKeyCode::End => self.text.len(),
+            _ => return false,
+        };
+        true
+    }
+
+    fn handle_key(&mut self, key: VirtualKeyCode) -> bool {
+        if self.editable && self.move_caret(key) {
+            return true;
+        }
+        if self.open {
+            match key {
+                VirtualKeyCode::Up => self.move_highlight(-1),
+                VirtualKeyCode::Down => self.move_highlight(1),
+                VirtualKeyCode::PageUp => self.move_highlight(-(self.max_visible as i32)),
+                VirtualKeyCode::PageDown => self.move_highlight(self.max_visible as i32),
+                VirtualKeyCode::Home => self.move_highlight(i32::MIN / 2),
+                VirtualKeyCode::End => self.move_highlight(i32::MAX / 2),
+                VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter => {
+                    if let Some(&index) = self.filtered.get(self.highlighted) {
+                        self.choose(index);
+                    }
+                }
+                VirtualKeyCode::Escape | VirtualKeyCode::Tab => self.close(),
+                _ => {}
+            }
+            return true;
+        }
+
+        match key {
+            VirtualKeyCode::Down if self.modifiers.alt() => self.open_list(),
+            VirtualKeyCode::F4 => self.open_list(),
+            VirtualKeyCode::Space | VirtualKeyCode::Return if !self.editable => self.open_list(),
+            // 列表关闭时用方向键直接切换选项
+            VirtualKeyCode::Up | VirtualKeyCode::Down if !self.options.is_empty() => {
+                let last = self.options.len() - 1;
+                let index = match (self.selected, key) {
+                    (None, _) => 0,
+                    (Some(index), VirtualKeyCode::Up) => index.saturating_sub(1),
+                    (Some(index), _) => (index + 1).min(last),
+                };
+                if self.selected != Some(index) {
+                    self.choose(index);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    // 选择框中文本的排版，超出宽度时以省略号结尾，从右到左的文本靠右对齐
+    fn text_layout(&self, font: &Font, text: &str) -> TextLayout {
+        Self::layout(font, text, self.text_width())
+    }
+
+    fn text_width(&self) -> f32 {
+        self.rect.width - TEXT_PADDING * 2.0 - ARROW_WIDTH
+    }
+
+    fn layout(font: &Font, text: &str, max_width: f32) -> TextLayout {
+        let options = TextOptions {
+            max_width: Some(max_width),
+            ellipsis: true,
+            ..TextOptions::default()
+        };
+        TextLayout::new(font, text, &options)
+    }
+
+    fn text_origin(&self, font: &Font) -> (f32, f32) {
+        let x = self.rect.x + TEXT_PADDING + if self.rtl { ARROW_WIDTH } else { 0.0 };
+        (x, self.rect.y + (self.rect.height - font.line_height() as f32) / 2.0)
+    }
+
+    // 点击位置对应的光标位置
+    fn place_caret(&mut self) {
+        let font = Font::default();
+        let (text_x, text_y) = self.text_origin(&font);
+        let layout = self.text_layout(&font, &self.text);
+        self.caret = layout.caret_at(self.cursor.0 - text_x, self.cursor.1 - text_y);
+    }
+
+    fn draw_text(renderer: &mut Renderer, font: &Font, x: f32, y: f32, text: &str, max_width: f32, color: Color) {
+        Self::layout(font, text, max_width).draw(renderer, font, x, y, color);
+    }
+}
+
+impl Widget for ComboBox {
+    fn draw(&self, renderer: &mut Renderer) {
+        let rect = self.rect;
+        // 展开时保持悬停的外观，样式表中的属性覆盖主题
+        let state = WidgetState {
+            hovered: self.hovered || self.open,
+            focused: self.focused,
+            ..WidgetState::default()
+        };
+        let properties = self.style.resolve(state);
+        let theme_style = renderer.theme().input.resolve(state);
+        let style = properties.apply(theme_style);
+        let base = BoxStyle::from(theme_style).with_corner_radius(renderer.theme().radii.small);
+        renderer.draw_box(rect, &properties.apply_box(base, style.text));
+
+        let font = Font::default();
+        let text = if self.editable { self.text.as_str() } else { self.selected_value().unwrap_or("") };
+        let (text_x, text_y) = self.text_origin(&font);
+        let layout = self.text_layout(&font, text);
+        layout.draw(renderer, &font, text_x, text_y, style.text);
+
+        // 可编辑模式下的光标
+        if self.editable && self.focused {
+            let (caret_x, _) = layout.caret_position(self.caret);
+            let caret_x = (text_x + caret_x).min(text_x + self.text_width() + TEXT_PADDING);
+            renderer.draw_rect(caret_x as i32, text_y as i32 - 2, 1, font.line_height() + 4, style.text);
+        }
+
+        // 下拉箭头
+        let arrow_x = if self.rtl { rect.x + 5.0 } else { rect.x + rect.width - ARROW_WIDTH + 5.0 } as i32;
+        let arrow_y = (rect.y + rect.height / 2.0 - 2.0) as i32;
+        for row in 0..5 {
+            renderer.draw_rect(arrow_x + row, arrow_y + row, (9 - row * 2) as u32, 1, style.text);
+        }
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        if !self.open {
+            return;
+        }
+
+        let theme = renderer.theme();
+        let (palette, item, highlighted) = (theme.palette, theme.menu_item.normal, theme.menu_item.hovered);
+        let popup = self.popup;
+        renderer.draw_rect(popup.x as i32, popup.y as i32, popup.width as u32, popup.height as u32, palette.border);
+        renderer.draw_rect(
+            popup.x as i32 + 1,
+            popup.y as i32 + 1,
+            (popup.width as u32).saturating_sub(2),
+            (popup.height as u32).saturating_sub(2),
+            palette.popup,
+        );
+
+        let font = Font::default();
+        // 从右到左时滚动条在左侧，选项行随之右移
+        let row_x = popup.x + POPUP_BORDER + if self.rtl { SCROLLBAR_WIDTH } else { 0.0 };
+        let row_width = popup.width - POPUP_BORDER * 2.0 - SCROLLBAR_WIDTH;
+
+        if self.filtered.is_empty() {
+            let y = popup.y + POPUP_BORDER + (ROW_HEIGHT - font.line_height() as f32) / 2.0;
+            Self::draw_text(renderer, &font, row_x + TEXT_PADDING, y, "No matches", row_width, palette.text_muted);
+            return;
+        }
+
+        let visible = self.visible_rows();
+        for row in 0..visible {
+            let position = self.scroll + row;
+            let y = popup.y + POPUP_BORDER + row as f32 * ROW_HEIGHT;
+            let style = if position == self.highlighted { highlighted } else { item };
+            if position == self.highlighted {
+                renderer.draw_rect(row_x as i32, y as i32, row_width as u32, ROW_HEIGHT as u32, style.background);
+            }
+            let option = &self.options[self.filtered[position]];
+            Self::draw_text(
+                renderer,
+                &font,
+                row_x + TEXT_PADDING,
+                y + (ROW_HEIGHT - font.line_height() as f32) / 2.0,
+                option,
+                row_width - TEXT_PADDING * 2.0,
+                style.text,
+            );
+        }
+
+        // 滚动条
+        if self.filtered.len() > visible {
+            let track = popup.height - POPUP_BORDER * 2.0;
+            let thumb = (track * visible as f32 / self.filtered.len() as f32).max(8.0);
+            let offset = (track - thumb) * self.scroll as f32 / (self.filtered.len() - visible) as f32;
+            let scrollbar_x = if self.rtl {
+                popup.x + POPUP_BORDER
+            } else {
+                popup.x + popup.width - POPUP_BORDER - SCROLLBAR_WIDTH
+            };
+            renderer.draw_rect(
+                scrollbar_x as i32,
+                (popup.y + POPUP_BORDER + offset) as i32,
+                SCROLLBAR_WIDTH as u32,
+                thumb as u32,
+                palette.outline,
+            );
+        }
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+                self.hovered = self.rect.contains(self.cursor.0, self.cursor.1);
+                if let Some(position) = self.row_at(self.cursor.0, self.cursor.1) {
+                    self.highlighted = position;
+                }
+                self.popup.contains(self.cursor.0, self.cursor.1)
+            }
+            WindowEvent::MouseWheel { delta, .. } if self.popup.contains(self.cursor.0, self.cursor.1) => {
+                let rows = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => -y.round() as i32,
+                    MouseScrollDelta::PixelDelta(position) => -(position.y as f32 / ROW_HEIGHT).round() as i32,
+                };
+                self.scroll_by(rows);
+                true
+            }
+            WindowEvent::MouseInput { state: ElementState::Pressed, .. } => {
+                let (x, y) = self.cursor;
+                if self.rect.contains(x, y) {
+                    // 再次点击选择框时关闭列表
+                    self.close();
+                    if self.editable {
+                        self.place_caret();
+                    }
+                } else if !self.popup.contains(x, y) {
+                    // 点击外部关闭列表并失去焦点
+                    self.close();
+                    self.focused = false;
+                }
+                true
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Some(position) = self.row_at(self.cursor.0, self.cursor.1) {
+                    self.choose(self.filtered[position]);
+                }
+                true
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+                false
+            }
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(key),
+                    ..
+                },
+                ..
+            } => self.handle_key(*key),
+            WindowEvent::ReceivedCharacter(c) => {
+                if self.editable {
+                    self.edit_text(*c);
+                } else if !c.is_control() {
+                    if let Some(position) = self
+                        .type_ahead(*c)
+                        .and_then(|index| self.filtered.iter().position(|&i| i == index))
+                    {
+                        self.highlighted = position;
+                        self.ensure_visible();
+                    }
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => {
+                self.viewport = Rect { x: 0.0, y: 0.0, width: size.width as f32, height: size.height as f32 };
+                self.close();
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+                self.hovered = self.rect.contains(self.cursor.0, self.cursor.1);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.focused = self.hovered;
+                if self.hovered {
+                    if self.editable {
+                        self.place_caret();
+                    }
+                    self.open_list();
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+            }
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(key),
+                    ..
+                },
+                ..
+            } if self.focused => {
+                self.handle_key(*key);
+            }
+            WindowEvent::ReceivedCharacter(c) if self.focused => {
+                if self.editable {
+                    self.edit_text(*c);
+                } else if !c.is_control() {
+                    if let Some(index) = self.type_ahead(*c) {
+                        if self.selected != Some(index) {
+                            self.choose(index);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    // 重新布局时只有位置变化才关闭列表，大小变化时列表跟着调整
+    fn set_rect(&mut self, rect: Rect) {
+        let moved = rect.x != self.rect.x || rect.y != self.rect.y;
+        self.rect = rect;
+        if moved {
+            self.close();
+        } else if self.open {
+            self.place_list();
+        }
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width, self.rect.height)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.rtl = direction.is_rtl();
+        self.close();
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.style.apply(sheet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relayout_keeps_list_open_unless_moved() {
+        let mut combo = ComboBox::new(10.0, 10.0, 100.0, 24.0, &["a", "b"]);
+        combo.viewport = Rect { x: 0.0, y: 0.0, width: 800.0, height: 600.0 };
+        combo.open_list();
+
+        combo.set_rect(Rect { x: 10.0, y: 10.0, width: 150.0, height: 24.0 });
+        assert!(combo.open);
+        assert_eq!(combo.popup.width, 150.0);
+
+        combo.set_rect(Rect { x: 20.0, y: 10.0, width: 150.0, height: 24.0 });
+        assert!(!combo.open);
+    }
+}
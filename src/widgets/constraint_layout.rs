@@ -0,0 +1,326 @@
+use std::time::Instant;
+use kasuari::{Constraint, Expression, RelationalOperator, Solver, Term, Variable};
+use winit::event::WindowEvent;
+use winit::window::CursorIcon;
+use super::{children, LayoutState, Widget};
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{Attribute, ConstraintError, ConstraintExpr, Constraints, LayoutConstraint, LayoutDirection, Rect, Relation, Strength};
+
+// 容器自身在约束中的名字
+const PARENT: &str = "parent";
+
+// 子组件的首选尺寸比 weak 约束还弱，只在没有其他约束决定尺寸时生效
+const PREFERRED_STRENGTH: kasuari::Strength = kasuari::Strength::WEAK.mul_f64(0.5);
+
+// 一个组件的所有属性对应的求解器变量，按 Attribute 的顺序排列
+type Anchors = [Variable; 8];
+
+// 按约束关系摆放子组件的容器，适合用嵌套的容器难以表达的对话框布局：
+//   layout.add_constraint("ok.right == parent.right - 8")
+//   layout.add_constraint("label.width >= 120 weak")
+// 约束由增量的 Cassowary 求解器求解，添加约束或建议编辑变量的值时只更新受影响的部分。
+// 子组件的坐标相对于容器的左上角
+pub struct ConstraintLayout {
+    rect: Rect,
+    solver: Solver,
+    parent: Anchors,
+    children: Vec<Box<dyn Widget>>,
+    // 与 children 一一对应的名字和变量
+    names: Vec<String>,
+    anchors: Vec<Anchors>,
+    // 最近一次建议给求解器的子组件首选尺寸
+    preferred: Vec<Option<(f32, f32)>>,
+    // 最近一次建议给求解器的容器尺寸
+    suggested: Option<(f32, f32)>,
+    // 通过 add_edit_variable 添加的编辑变量，子组件的首选尺寸不再覆盖它们
+    edits: Vec<Variable>,
+    layout_state: LayoutState,
+}
+
+impl ConstraintLayout {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        let mut solver = Solver::new();
+        let parent = Self::add_anchors(&mut solver);
+        // 容器的位置固定在原点，尺寸由 set_rect 建议
+        let origin = [
+            Constraint::new(Expression::from_variable(parent[Attribute::Left as usize]), RelationalOperator::Equal, kasuari::Strength::REQUIRED),
+            Constraint::new(Expression::from_variable(parent[Attribute::Top as usize]), RelationalOperator::Equal, kasuari::Strength::REQUIRED),
+        ];
+        let _ = solver.add_constraints(origin);
+        for attribute in [Attribute::Width, Attribute::Height] {
+            let _ = solver.add_edit_variable(parent[attribute as usize], kasuari::Strength::STRONG);
+        }
+
+        Self {
+            rect: Rect { x, y, width, height },
+            solver,
+            parent,
+            children: Vec::new(),
+            names: Vec::new(),
+            anchors: Vec::new(),
+            preferred: Vec::new(),
+            suggested: None,
+            edits: Vec::new(),
+            layout_state: LayoutState::default(),
+        }
+    }
+
+    // 创建一个组件的变量，并添加由位置和尺寸推导 right、bottom 和中心点的约束
+    fn add_anchors(solver: &mut Solver) -> Anchors {
+        let anchors: Anchors = std::array::from_fn(|_| Variable::new());
+        let var = |attribute: Attribute| anchors[attribute as usize];
+        let derived = [
+            (Attribute::Right, Attribute::Left, Attribute::Width, 1.0),
+            (Attribute::Bottom, Attribute::Top, Attribute::Height, 1.0),
+            (Attribute::CenterX, Attribute::Left, Attribute::Width, 0.5),
+            (Attribute::CenterY, Attribute::Top, Attribute::Height, 0.5),
+        ];
+        for (target, start, size, factor) in derived {
+            // target - start - factor × size == 0
+            let expression = Expression::from_terms(vec![
+                Term::new(var(target), 1.0),
+                Term::new(var(start), -1.0),
+                Term::new(var(size), -factor),
+            ]);
+            let _ = solver.add_constraint(Constraint::new(expression, RelationalOperator::Equal, kasuari::Strength::REQUIRED));
+        }
+        for size in [Attribute::Width, Attribute::Height] {
+            let expression = Expression::from_variable(var(size));
+            let _ = solver.add_constraint(Constraint::new(expression, RelationalOperator::GreaterOrEqual, kasuari::Strength::REQUIRED));
+        }
+        anchors
+    }
+
+    // 从右到左时求解结果在容器中水平镜像，left 约束的是靠近起始边（右边）的一侧
+    pub fn with_layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_state.set_direction(direction, &mut self.children);
+        self
+    }
+
+    // 添加一个可以在约束中用名字引用的子组件。名字不能是 parent，也不能与已有的子组件重复；
+    // 名字为空的子组件不能被约束引用，可以有多个
+    pub fn add_child<W: Widget + 'static>(&mut self, name: &str, widget: W) -> Result<(), ConstraintError> {
+        self.add_boxed(name, Box::new(widget))
+    }
+
+    pub(crate) fn add_boxed(&mut self, name: &str, mut widget: Box<dyn Widget>) -> Result<(), ConstraintError> {
+        if name == PARENT || (!name.is_empty() && self.names.iter().any(|existing| existing == name)) {
+            return Err(ConstraintError::DuplicateWidget(name.to_string()));
+        }
+        widget.set_layout_direction(self.layout_state.direction());
+        let anchors = Self::add_anchors(&mut self.solver);
+        for size in [Attribute::Width, Attribute::Height] {
+            let _ = self.solver.add_edit_variable(anchors[size as usize], PREFERRED_STRENGTH);
+        }
+        self.children.push(widget);
+        self.names.push(name.to_string());
+        self.anchors.push(anchors);
+        self.preferred.push(None);
+        self.layout_state.invalidate();
+        Ok(())
+    }
+
+    // 添加一条约束，语法见 LayoutConstraint
+    pub fn add_constraint(&mut self, constraint: &str) -> Result<(), ConstraintError> {
+        let parsed = LayoutConstraint::parse(constraint)
+            .ok_or_else(|| ConstraintError::InvalidSyntax(constraint.to_string()))?;
+        // left - right (关系) 0
+        let mut expression = self.expression(&parsed.left)?;
+        let right = self.expression(&parsed.right)?;
+        expression.terms.extend(right.terms.into_iter().map(|term| Term::new(term.variable, -term.coefficient)));
+        expression.constant -= right.constant;
+
+        let operator = match parsed.relation {
+            Relation::Equal => RelationalOperator::Equal,
+            Relation::LessOrEqual => RelationalOperator::LessOrEqual,
+            Relation::GreaterOrEqual => RelationalOperator::GreaterOrEqual,
+        };
+        self.solver
+            .add_constraint(Constraint::new(expression, operator, parsed.strength.solver_strength()))
+            .map_err(|_| ConstraintError::Unsatisfiable(constraint.to_string()))?;
+        self.layout_state.invalidate();
+        Ok(())
+    }
+
+    // 把属性标记为可编辑，之后可以用 suggest_value 交互地修改，例如拖动分隔条调整侧栏宽度。
+    // 编辑变量的强度不能是 required
+    pub fn add_edit_variable(&mut self, target: &str, strength: Strength) -> Result<(), ConstraintError> {
+        let variable = self.target(target)?;
+        let invalid = || ConstraintError::InvalidEdit(target.to_string());
+        if strength == Strength::Required || self.edits.contains(&variable) {
+            return Err(invalid());
+        }
+        // 子组件的宽高已经是首选尺寸的编辑变量，换成调用者指定的强度
+        if self.solver.has_edit_variable(&variable) {
+            self.solver.remove_edit_variable(variable).map_err(|_| invalid())?;
+        }
+        self.solver.add_edit_variable(variable, strength.solver_strength()).map_err(|_| invalid())?;
+        self.edits.push(variable);
+        Ok(())
+    }
+
+    pub fn remove_edit_variable(&mut self, target: &str) -> Result<(), ConstraintError> {
+        let variable = self.target(target)?;
+        let invalid = || ConstraintError::InvalidEdit(target.to_string());
+        let index = self.edits.iter().position(|&edit| edit == variable).ok_or_else(invalid)?;
+        self.edits.remove(index);
+        self.solver.remove_edit_variable(variable).map_err(|_| invalid())?;
+
+        // 子组件的宽高恢复为按首选尺寸建议
+        let child = self.anchors.iter().position(|anchors| {
+            anchors[Attribute::Width as usize] == variable || anchors[Attribute::Height as usize] == variable
+        });
+        if let Some(child) = child {
+            self.solver.add_edit_variable(variable, PREFERRED_STRENGTH).map_err(|_| invalid())?;
+            self.preferred[child] = None;
+        }
+        self.layout_state.invalidate();
+        Ok(())
+    }
+
+    pub fn suggest_value(&mut self, target: &str, value: f32) -> Result<(), ConstraintError> {
+        let variable = self.target(target)?;
+        if !self.edits.contains(&variable) {
+            return Err(ConstraintError::InvalidEdit(target.to_string()));
+        }
+        self.solver
+            .suggest_value(variable, value as f64)
+            .map_err(|_| ConstraintError::InvalidEdit(target.to_string()))?;
+        self.layout_state.invalidate();
+        Ok(())
+    }
+
+    // 求解后属性的值，相对于容器的左上角
+    pub fn value(&self, target: &str) -> Result<f32, ConstraintError> {
+        Ok(self.solver.get_value(self.target(target)?) as f32)
+    }
+
+    fn anchors(&self, widget: &str) -> Result<&Anchors, ConstraintError> {
+        if widget == PARENT {
+            return Ok(&self.parent);
+        }
+        self.names
+            .iter()
+            .position(|name| name == widget)
+            .map(|index| &self.anchors[index])
+            .ok_or_else(|| ConstraintError::UnknownWidget(widget.to_string()))
+    }
+
+    // "组件名.属性" 对应的变量
+    fn target(&self, target: &str) -> Result<Variable, ConstraintError> {
+        let invalid = || ConstraintError::InvalidSyntax(target.to_string());
+        let (widget, attribute) = target.trim().split_once('.').ok_or_else(invalid)?;
+        let attribute = Attribute::parse(attribute).ok_or_else(invalid)?;
+        Ok(self.anchors(widget)?[attribute as usize])
+    }
+
+    fn expression(&self, expr: &ConstraintExpr) -> Result<Expression, ConstraintError> {
+        let terms = expr
+            .terms
+            .iter()
+            .map(|term| {
+                let variable = self.anchors(&term.widget)?[term.attribute as usize];
+                Ok(Term::new(variable, term.coefficient as f64))
+            })
+            .collect::<Result<Vec<Term>, ConstraintError>>()?;
+        Ok(Expression::new(terms, expr.constant as f64))
+    }
+
+    // 建议宽高，跳过调用者自己编辑的变量
+    fn suggest(&mut self, anchors: Anchors, (width, height): (f32, f32)) {
+        for (attribute, value) in [(Attribute::Width, width), (Attribute::Height, height)] {
+            let variable = anchors[attribute as usize];
+            if !self.edits.contains(&variable) {
+                let _ = self.solver.suggest_value(variable, value as f64);
+            }
+        }
+    }
+
+    fn layout(&mut self) {
+        let size = (self.rect.width, self.rect.height);
+        if self.suggested != Some(size) {
+            self.suggest(self.parent, size);
+            self.suggested = Some(size);
+        }
+        // 只把变化了的首选尺寸告诉求解器
+        for index in 0..self.children.len() {
+            let preferred = self.children[index].measure(Constraints::unbounded());
+            if self.preferred[index] != Some(preferred) {
+                self.suggest(self.anchors[index], preferred);
+                self.preferred[index] = Some(preferred);
+            }
+        }
+
+        let rtl = self.layout_state.direction().is_rtl();
+        for (child, anchors) in self.children.iter_mut().zip(&self.anchors) {
+            let value = |attribute: Attribute| self.solver.get_value(anchors[attribute as usize]) as f32;
+            let rect = Rect {
+                x: self.rect.x + value(Attribute::Left),
+                y: self.rect.y + value(Attribute::Top),
+                width: value(Attribute::Width),
+                height: value(Attribute::Height),
+            };
+            child.arrange(if rtl { rect.mirror(self.rect) } else { rect });
+        }
+    }
+}
+
+impl Widget for ConstraintLayout {
+    fn draw(&self, renderer: &mut Renderer) {
+        children::draw(&self.children, renderer);
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        children::handle_event(&mut self.children, event);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        children::draw_overlay(&self.children, renderer);
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        children::handle_overlay_event(&mut self.children, event)
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        children::next_wakeup(&self.children)
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        children::cursor(&self.children)
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        if self.layout_state.update_rect(&mut self.rect, rect, &self.children) {
+            self.layout();
+            self.layout_state.clean();
+        }
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.layout_state.needs_layout(&self.children)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_state.inherit_direction(direction, &mut self.children);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        children::set_theme(&mut self.children, theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.layout_state.set_stylesheet(sheet, &mut self.children);
+    }
+
+    // 约束通常相对于容器的边缘，容器的尺寸由外部决定，首选尺寸就是当前尺寸
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width, self.rect.height)
+    }
+}
@@ -1,16 +1,38 @@
+use std::time::Instant;
 use winit::event::WindowEvent;
-use super::Widget;
+use winit::window::CursorIcon;
+use super::{children, measure_preferred, LayoutState, StyleState, Widget};
+use crate::box_style::BoxStyle;
 use crate::renderer::Renderer;
-use crate::layout::{Rect, Padding, Alignment, Direction};
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{resolve_flex, AlignSelf, Breakpoint, Constraints, FlexItem, FlexLine, LayoutDirection, Rect, Padding, Alignment, Direction};
 use crate::debug_log;
 
+// 百分比尺寸的参照：内容区域的宽度和高度，测量首选尺寸时可能未知
+type PercentBase = (Option<f32>, Option<f32>);
+
 pub struct Container {
     rect: Rect,
     padding: Padding,
     children: Vec<Box<dyn Widget>>,
+    // 与 children 一一对应的弹性布局参数
+    items: Vec<FlexItem>,
     direction: Direction,
     alignment: Alignment,
     spacing: f32,
+    // 水平布局中超出内容宽度时换行
+    wrap: bool,
+    // 换行后行与行之间的间距
+    line_spacing: f32,
+    // 每一行在主轴上的对齐方式
+    line_alignment: Alignment,
+    // 按容器宽度切换布局参数的断点，按 max_width 从小到大排列
+    breakpoints: Vec<Breakpoint>,
+    layout_state: LayoutState,
+    style: StyleState,
+    // 画在子组件下方的背景、边框和阴影，样式表中的属性覆盖它
+    box_style: BoxStyle,
 }
 
 impl Container {
@@ -19,209 +41,528 @@ impl Container {
             rect: Rect { x, y, width, height },
             padding: Padding::all(10.0),
             children: Vec::new(),
+            items: Vec::new(),
             direction: Direction::Vertical,
             alignment: Alignment::Start,
             spacing: 5.0,
+            wrap: false,
+            line_spacing: 5.0,
+            line_alignment: Alignment::Start,
+            breakpoints: Vec::new(),
+            layout_state: LayoutState::default(),
+            style: StyleState::new("Container"),
+            box_style: BoxStyle::default(),
         }
     }
 
     pub fn with_padding(mut self, padding: Padding) -> Self {
         self.padding = padding;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_box_style(mut self, box_style: BoxStyle) -> Self {
+        self.box_style = box_style;
+        self
+    }
+
+    // 样式表中用 #id 选择这个容器
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.style.set_id(id);
+        self
+    }
+
+    // 样式表中用 .class 选择这个容器，可以多次调用添加多个 class
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.style.add_class(class);
         self
     }
 
     pub fn with_direction(&mut self, direction: Direction) -> &mut Self {
         self.direction = direction;
+        self.layout_state.invalidate();
         self
     }
 
     pub fn with_alignment(&mut self, alignment: Alignment) -> &mut Self {
         self.alignment = alignment;
+        self.layout_state.invalidate();
         self
     }
 
     pub fn with_spacing(mut self, spacing: f32) -> Self {
         self.spacing = spacing;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 水平布局的子组件超出内容宽度时换到下一行，类似 CSS 的 flex-wrap，
+    // 每一行单独按弹性参数分配宽度。垂直布局不受影响
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = line_spacing;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_line_alignment(mut self, line_alignment: Alignment) -> Self {
+        self.line_alignment = line_alignment;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 容器宽度小于断点的 max_width 时使用断点中的布局参数，
+    // 例如窄窗口中把水平排列的按钮改为垂直排列
+    pub fn with_breakpoint(mut self, breakpoint: Breakpoint) -> Self {
+        let index = self.breakpoints.partition_point(|existing| existing.max_width <= breakpoint.max_width);
+        self.breakpoints.insert(index, breakpoint);
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 设置这个容器及其子组件的布局方向，不随窗口或父容器的方向变化。
+    // 从右到左时子组件从右边开始排列，Start 和 End 对齐互换
+    pub fn with_layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_state.set_direction(direction, &mut self.children);
         self
     }
 
     pub fn add_child<W: Widget + 'static>(&mut self, widget: W) {
-        self.children.push(Box::new(widget));
-        self.layout();
+        self.add_flex_child(widget, FlexItem::default());
     }
 
     // 同样的功能 add_child但是名称使用 add_widget
     pub fn add_widget<W: Widget + 'static>(&mut self, widget: W) {
-        self.children.push(Box::new(widget));
-        self.layout();
+        self.add_flex_child(widget, FlexItem::default());
+    }
+
+    // 添加子组件并指定弹性布局参数，例如 FlexItem::new().with_grow(1.0) 占满剩余空间
+    pub fn add_flex_child<W: Widget + 'static>(&mut self, widget: W, item: FlexItem) {
+        self.add_boxed(Box::new(widget), item);
+    }
+
+    // 只标记需要重新布局，添加多个子组件后在下一帧统一排列一次
+    pub(crate) fn add_boxed(&mut self, mut widget: Box<dyn Widget>, item: FlexItem) {
+        widget.set_layout_direction(self.layout_state.direction());
+        self.children.push(widget);
+        self.items.push(item);
+        self.layout_state.invalidate();
+    }
+
+    // 修改已添加的子组件的弹性布局参数
+    pub fn set_flex(&mut self, index: usize, item: FlexItem) {
+        if let Some(slot) = self.items.get_mut(index) {
+            *slot = item;
+            self.layout_state.invalidate();
+        }
+    }
+
+    // 当前宽度下生效的断点中第一个设置了该参数的，断点按 max_width 从小到大排列
+    fn breakpoint<T>(&self, pick: impl Fn(&Breakpoint) -> Option<T>) -> Option<T> {
+        self.breakpoints
+            .iter()
+            .filter(|breakpoint| breakpoint.matches(self.rect.width))
+            .find_map(pick)
+    }
+
+    fn direction(&self) -> Direction {
+        self.breakpoint(|breakpoint| breakpoint.direction).unwrap_or(self.direction)
+    }
+
+    fn alignment(&self) -> Alignment {
+        self.breakpoint(|breakpoint| breakpoint.alignment).unwrap_or(self.alignment)
+    }
+
+    // 断点中的间距优先于样式表，样式表优先于容器自身的设置
+    fn spacing(&self) -> f32 {
+        self.breakpoint(|breakpoint| breakpoint.spacing)
+            .or(self.style.normal().spacing)
+            .unwrap_or(self.spacing)
+    }
+
+    fn padding(&self) -> Padding {
+        self.style.normal().padding.unwrap_or(self.padding)
+    }
+
+    fn wrap(&self) -> bool {
+        self.breakpoint(|breakpoint| breakpoint.wrap).unwrap_or(self.wrap)
+    }
+
+    // 子组件之间间距的总和
+    fn total_spacing(&self) -> f32 {
+        self.spacing() * self.children.len().saturating_sub(1) as f32
     }
 
     fn layout(&mut self) {
         debug_log!("Layout container: {:?}", self.rect);
-        let content_x = self.rect.x + self.padding.left;
-        let content_y = self.rect.y + self.padding.top;
-        let content_width = self.rect.width - (self.padding.left + self.padding.right);
-        let content_height = self.rect.height - (self.padding.top + self.padding.bottom);
+        let content = self.rect.inset(self.padding());
 
         debug_log!("Content area: x={}, y={}, width={}, height={}", 
-            content_x, content_y, content_width, content_height);
+            content.x, content.y, content.width, content.height);
 
-        match self.direction {
+        match self.direction() {
             Direction::Vertical => {
                 debug_log!("Using vertical layout");
-                self.layout_vertical(content_x, content_y, content_width, content_height)
+                self.layout_vertical(content)
+            },
+            Direction::Horizontal if self.wrap() => {
+                debug_log!("Using wrapping flow layout");
+                self.layout_flow(content)
             },
             Direction::Horizontal => {
                 debug_log!("Using horizontal layout");
-                self.layout_horizontal(content_x, content_y, content_width, content_height)
+                self.layout_horizontal(content)
             },
         }
     }
 
-    fn layout_vertical(&mut self, x: f32, y: f32, width: f32, height: f32) {
+    // 以下的尺寸除特别说明外都包含子组件的外边距
+
+    // 子组件的首选主轴尺寸，cross 为子组件在交叉轴上的尺寸
+    fn flex_line(&self, index: usize, cross: f32, base: PercentBase) -> FlexLine {
+        let child = &self.children[index];
+        let item = &self.items[index];
+        let (size, margin, min, max) = match self.direction() {
+            Direction::Vertical => {
+                let width = (cross - item.margin.horizontal()).max(0.0);
+                let height = item
+                    .height
+                    .and_then(|height| height.resolve(base.1))
+                    .unwrap_or_else(|| child.measure(Constraints::tight_width(width)).1);
+                (height, item.margin.vertical(), item.min_height, item.max_height)
+            }
+            Direction::Horizontal => {
+                let width = item
+                    .width
+                    .and_then(|width| width.resolve(base.0))
+                    .unwrap_or_else(|| child.measure(Constraints::unbounded()).0);
+                (width, item.margin.horizontal(), item.min_width, item.max_width)
+            }
+        };
+        FlexLine {
+            basis: item.basis.unwrap_or(size) + margin,
+            grow: item.grow,
+            shrink: item.shrink,
+            min: min + margin,
+            max: max + margin,
+        }
+    }
+
+    // 考虑 basis、固定尺寸和最小、最大尺寸后的首选尺寸
+    fn child_preferred_size(&self, index: usize, base: PercentBase) -> (f32, f32) {
+        let item = &self.items[index];
+        let (width, height) = self.children[index].measure(Constraints::unbounded());
+        let width = item.width.and_then(|length| length.resolve(base.0)).unwrap_or(width);
+        let height = item.height.and_then(|length| length.resolve(base.1)).unwrap_or(height);
+        let (width, height) = match self.direction() {
+            Direction::Horizontal => (item.clamp_width(item.basis.unwrap_or(width)), item.clamp_height(height)),
+            Direction::Vertical => (item.clamp_width(width), item.clamp_height(item.basis.unwrap_or(height))),
+        };
+        (width + item.margin.horizontal(), height + item.margin.vertical())
+    }
+
+    // 交叉轴为宽度时子组件的宽度，拉伸的子组件占满 available，其余不超过 available
+    fn cross_width(&self, index: usize, available: f32, base: PercentBase) -> f32 {
+        let item = &self.items[index];
+        let margin = item.margin.horizontal();
+        let width = match (item.width.and_then(|width| width.resolve(base.0)), self.align_self(index)) {
+            (Some(width), _) => width,
+            (None, AlignSelf::Stretch) => available - margin,
+            (None, _) => self.children[index].measure(Constraints::loose((available - margin).max(0.0), f32::INFINITY)).0,
+        };
+        item.clamp_width(width) + margin
+    }
+
+    // 交叉轴为高度时子组件的高度，width 为已确定的子组件宽度（含外边距），
+    // stretch 为拉伸时占满的高度
+    fn cross_height(&self, index: usize, width: f32, stretch: Option<f32>, base: PercentBase) -> f32 {
+        let item = &self.items[index];
+        let margin = item.margin.vertical();
+        let height = match (item.height.and_then(|height| height.resolve(base.1)), stretch) {
+            (Some(height), _) => height,
+            (None, Some(stretch)) => stretch - margin,
+            (None, None) => {
+                let inner_width = (width - item.margin.horizontal()).max(0.0);
+                self.children[index].measure(Constraints::tight_width(inner_width)).1
+            }
+        };
+        item.clamp_height(height) + margin
+    }
+
+    fn align_self(&self, index: usize) -> AlignSelf {
+        match self.items[index].align_self {
+            AlignSelf::Auto => self.alignment().into(),
+            align => align,
+        }
+    }
+
+    // 按外边距收缩后排列子组件。各个布局都按从左到右计算，
+    // 从右到左时在容器中水平镜像，内边距和外边距的左右也随之互换
+    fn arrange_children(&mut self, rects: Vec<Rect>) {
+        let bounds = self.rect;
+        let rtl = self.layout_state.direction().is_rtl();
+        for ((child, item), rect) in self.children.iter_mut().zip(&self.items).zip(rects) {
+            let rect = rect.inset(item.margin);
+            child.arrange(if rtl { rect.mirror(bounds) } else { rect });
+        }
+    }
+
+    fn layout_vertical(&mut self, content: Rect) {
         if self.children.is_empty() {
             return;
         }
+        let Rect { x, y, width, height } = content;
+        let base = (Some(width), Some(height));
 
-        // 计算所有子组件的总高度和固定高度
-        let mut total_height = 0.0;
-        let mut total_flex = 0.0;
-        
-        for child in &self.children {
-            let (_, child_height) = child.preferred_size();
-            total_height += child_height;
-            total_flex += 1.0; // 未来可以添加 flex 属性
-        }
+        // 交叉轴：拉伸的子组件占满内容宽度，其余不超过内容宽度
+        let widths: Vec<f32> = (0..self.children.len()).map(|index| self.cross_width(index, width, base)).collect();
 
-        // 计算间距的总高度
-        let spacing_height = self.spacing * (self.children.len() - 1) as f32;
-        
-        // 计算剩余空间
-        let available_height = height - spacing_height;
-        let unit_height = if total_flex > 0.0 {
-            (available_height - total_height) / total_flex
-        } else {
-            0.0
-        };
+        // 主轴：自动换行的文本按实际宽度计算首选高度，再按弹性参数分配剩余空间
+        let lines: Vec<FlexLine> = (0..self.children.len()).map(|index| self.flex_line(index, widths[index], base)).collect();
+        let heights = resolve_flex(height - self.total_spacing(), &lines);
 
-        // 布局每个子组件
+        let spacing = self.spacing();
         let mut current_y = y;
-        for child in &mut self.children {
-            let (child_preferred_width, child_preferred_height) = child.preferred_size();
-            
-            // 根据对齐方式计算 x 坐标
-            let child_x = match self.alignment {
-                Alignment::Start => x,
-                Alignment::Center => x + (width - child_preferred_width) / 2.0,
-                Alignment::End => x + width - child_preferred_width,
+        let mut rects = Vec::with_capacity(self.children.len());
+        for (index, (&child_width, &child_height)) in widths.iter().zip(&heights).enumerate() {
+            let child_x = match self.align_self(index) {
+                AlignSelf::Center => x + (width - child_width) / 2.0,
+                AlignSelf::End => x + width - child_width,
+                _ => x,
             };
-
-            // 设置子组件的位置和大小
-            child.set_rect(Rect {
+            rects.push(Rect {
                 x: child_x,
                 y: current_y,
-                width: child_preferred_width,
-                height: child_preferred_height + unit_height,
+                width: child_width,
+                height: child_height,
             });
-
-            current_y += child_preferred_height + unit_height + self.spacing;
+            current_y += child_height + spacing;
         }
+        self.arrange_children(rects);
     }
 
-    fn layout_horizontal(&mut self, x: f32, y: f32, width: f32, height: f32) {
+    fn layout_horizontal(&mut self, content: Rect) {
         if self.children.is_empty() {
             return;
         }
+        let Rect { x, y, width, height } = content;
+        let base = (Some(width), Some(height));
 
-        // 计算所有子组件的总宽度和固定宽度
-        let mut total_width = 0.0;
-        let mut total_flex = 0.0;
-        
-        for child in &self.children {
-            let (child_width, _) = child.preferred_size();
-            total_width += child_width;
-            total_flex += 1.0; // 未来可以添加 flex 属性
-        }
-
-        // 计算间距的总宽度
-        let spacing_width = self.spacing * (self.children.len() - 1) as f32;
-        
-        // 计算剩余空间
-        let available_width = width - spacing_width;
-        let unit_width = if total_flex > 0.0 {
-            (available_width - total_width) / total_flex
-        } else {
-            0.0
-        };
+        let lines: Vec<FlexLine> = (0..self.children.len()).map(|index| self.flex_line(index, height, base)).collect();
+        let widths = resolve_flex(width - self.total_spacing(), &lines);
 
-        // 布局每个子组件
+        let spacing = self.spacing();
         let mut current_x = x;
-        for child in &mut self.children {
-            let (child_preferred_width, child_preferred_height) = child.preferred_size();
-            
-            // 根据对齐方式计算 y 坐标
-            let child_y = match self.alignment {
-                Alignment::Start => y,
-                Alignment::Center => y + (height - child_preferred_height) / 2.0,
-                Alignment::End => y + height - child_preferred_height,
+        let mut rects = Vec::with_capacity(self.children.len());
+        for (index, &child_width) in widths.iter().enumerate() {
+            // 交叉轴上按分配到的宽度计算首选高度
+            let align = self.align_self(index);
+            let stretch = matches!(align, AlignSelf::Stretch).then_some(height);
+            let child_height = self.cross_height(index, child_width, stretch, base);
+            let child_y = match align {
+                AlignSelf::Center => y + (height - child_height) / 2.0,
+                AlignSelf::End => y + height - child_height,
+                _ => y,
             };
-
-            // 设置子组件的位置和大小
-            child.set_rect(Rect {
+            rects.push(Rect {
                 x: current_x,
                 y: child_y,
-                width: child_preferred_width + unit_width,
-                height: child_preferred_height,
+                width: child_width,
+                height: child_height,
             });
+            current_x += child_width + spacing;
+        }
+        self.arrange_children(rects);
+    }
+
+    // 按首选宽度贪心地把子组件分成多行，每行至少一个
+    fn flow_lines(&self, width: f32, base: PercentBase) -> Vec<Vec<usize>> {
+        let spacing = self.spacing();
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        let mut line_width = 0.0;
+        for index in 0..self.children.len() {
+            let child_width = self.child_preferred_size(index, base).0;
+            match lines.last_mut() {
+                Some(line) if line_width + spacing + child_width <= width => {
+                    line.push(index);
+                    line_width += spacing + child_width;
+                }
+                _ => {
+                    lines.push(vec![index]);
+                    line_width = child_width;
+                }
+            }
+        }
+        lines
+    }
 
-            current_x += child_preferred_width + unit_width + self.spacing;
+    // 一行中各子组件的宽度和这一行的高度
+    fn flow_line_sizes(&self, line: &[usize], width: f32, base: PercentBase) -> (Vec<f32>, f32) {
+        let flex_lines: Vec<FlexLine> = line.iter().map(|&index| self.flex_line(index, 0.0, base)).collect();
+        let spacing = self.spacing() * line.len().saturating_sub(1) as f32;
+        let widths = resolve_flex(width - spacing, &flex_lines);
+        let height = line
+            .iter()
+            .zip(&widths)
+            .map(|(&index, &child_width)| self.cross_height(index, child_width, None, base))
+            .fold(0.0, f32::max);
+        (widths, height)
+    }
+
+    fn flow_height(&self, width: f32, base: PercentBase) -> f32 {
+        let lines = self.flow_lines(width, base);
+        let heights: f32 = lines.iter().map(|line| self.flow_line_sizes(line, width, base).1).sum();
+        heights + self.line_spacing * lines.len().saturating_sub(1) as f32
+    }
+
+    fn layout_flow(&mut self, content: Rect) {
+        let Rect { x, y, width, height } = content;
+        let base = (Some(width), Some(height));
+        let spacing = self.spacing();
+        let mut rects = vec![Rect { x, y, width: 0.0, height: 0.0 }; self.children.len()];
+        let mut current_y = y;
+        for line in self.flow_lines(width, base) {
+            let (widths, line_height) = self.flow_line_sizes(&line, width, base);
+            let used = widths.iter().sum::<f32>() + spacing * (line.len() - 1) as f32;
+            let mut current_x = x + match self.line_alignment {
+                Alignment::Start => 0.0,
+                Alignment::Center => ((width - used) / 2.0).max(0.0),
+                Alignment::End => (width - used).max(0.0),
+            };
+
+            // 交叉轴上在行内对齐，拉伸的子组件与行一样高
+            for (&index, &child_width) in line.iter().zip(&widths) {
+                let align = self.align_self(index);
+                let stretch = matches!(align, AlignSelf::Stretch).then_some(line_height);
+                let child_height = self.cross_height(index, child_width, stretch, base);
+                let child_y = match align {
+                    AlignSelf::Center => current_y + (line_height - child_height) / 2.0,
+                    AlignSelf::End => current_y + line_height - child_height,
+                    _ => current_y,
+                };
+                rects[index] = Rect {
+                    x: current_x,
+                    y: child_y,
+                    width: child_width,
+                    height: child_height,
+                };
+                current_x += child_width + spacing;
+            }
+            current_y += line_height + self.line_spacing;
         }
+        self.arrange_children(rects);
     }
 }
 
 impl Widget for Container {
     fn draw(&self, renderer: &mut Renderer) {
+        // 容器默认透明，设置了背景样式或样式表设置了背景、边框、阴影时画在子组件下方
+        let box_style = self.style.normal().apply_box(self.box_style.clone(), renderer.theme().palette.border);
+        if !box_style.is_empty() {
+            renderer.draw_box(self.rect, &box_style);
+        }
         for child in &self.children {
             child.draw(renderer);
         }
     }
 
     fn handle_event(&mut self, event: &WindowEvent) {
-        for child in &mut self.children {
-            child.handle_event(event);
-        }
+        children::handle_event(&mut self.children, event);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        children::draw_overlay(&self.children, renderer);
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        children::handle_overlay_event(&mut self.children, event)
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        children::next_wakeup(&self.children)
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        children::cursor(&self.children)
     }
     fn get_rect(&self) -> Rect {
         self.rect
     }
 
     fn set_rect(&mut self, rect: Rect) {
-        self.rect = rect;
-        self.layout();  // 重新布局子组件
+        if self.layout_state.update_rect(&mut self.rect, rect, &self.children) {
+            self.layout();  // 重新布局子组件
+            self.layout_state.clean();
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.layout_state.measure(constraints, self.needs_layout(), || measure_preferred(self, constraints))
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.layout_state.needs_layout(&self.children)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_state.inherit_direction(direction, &mut self.children);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        children::set_theme(&mut self.children, theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.style.apply(sheet);
+        self.layout_state.set_stylesheet(sheet, &mut self.children);
     }
 
+    // 测量首选尺寸时容器的内容区域未知，百分比尺寸的子组件按自身的首选尺寸计算
     fn preferred_size(&self) -> (f32, f32) {
-        match self.direction {
+        let base = (None, None);
+        let sizes = (0..self.children.len()).map(|index| self.child_preferred_size(index, base));
+        let (content_width, content_height) = match self.direction() {
             Direction::Horizontal => {
-                let mut width: f32 = 0.0;
-                let mut max_height: f32 = 0.0;
-                for child in &self.children {
-                    let (child_width, child_height) = child.preferred_size();
-                    width += child_width;
-                    max_height = max_height.max(child_height);
-                }
-                (width, max_height)
+                let (width, height) = sizes.fold((0.0, 0.0f32), |(width, height), size| (width + size.0, height.max(size.1)));
+                (width + self.total_spacing(), height)
             }
             Direction::Vertical => {
-                let mut max_width: f32 = 0.0;
-                let mut height: f32 = 0.0;
-                for child in &self.children {
-                    let (child_width, child_height) = child.preferred_size();
-                    max_width = max_width.max(child_width);
-                    height += child_height;
-                }
-                (max_width, height)
+                let (width, height) = sizes.fold((0.0f32, 0.0), |(width, height), size| (width.max(size.0), height + size.1));
+                (width, height + self.total_spacing())
             }
-        }
-    }    
-}
\ No newline at end of file
+        };
+        (
+            content_width + self.padding().horizontal(),
+            content_height + self.padding().vertical(),
+        )
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        let content_width = width - self.padding().horizontal();
+        let base = (Some(content_width), None);
+        let content_height = match self.direction() {
+            Direction::Horizontal if self.wrap() => self.flow_height(content_width, base),
+            Direction::Horizontal => (0..self.children.len())
+                .map(|index| {
+                    let child_width = self.child_preferred_size(index, base).0;
+                    self.cross_height(index, child_width, None, base)
+                })
+                .fold(0.0, f32::max),
+            Direction::Vertical => {
+                (0..self.children.len())
+                    .map(|index| {
+                        let child_width = self.cross_width(index, content_width, base);
+                        self.flex_line(index, child_width, base).clamp_basis()
+                    })
+                    .sum::<f32>()
+                    + self.total_spacing()
+            }
+        };
+        content_height + self.padding().vertical()
+    }
+}
@@ -0,0 +1,291 @@
+use std::time::Instant;
+use winit::event::WindowEvent;
+use winit::window::CursorIcon;
+use super::{children, measure_preferred, LayoutState, Widget};
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{resolve_tracks, track_offsets, Alignment, Constraints, GridCell, LayoutDirection, Padding, Rect, Track, TrackItem};
+
+// 按行列轨道排列子组件的网格容器，适合标签和输入框对齐的表单。
+// 子组件超出已定义的行列时自动添加 auto 轨道
+pub struct Grid {
+    rect: Rect,
+    padding: Padding,
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    column_gap: f32,
+    row_gap: f32,
+    children: Vec<Box<dyn Widget>>,
+    // 与 children 一一对应的位置
+    cells: Vec<GridCell>,
+    layout_state: LayoutState,
+}
+
+impl Grid {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            rect: Rect { x, y, width, height },
+            padding: Padding::all(10.0),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            column_gap: 5.0,
+            row_gap: 5.0,
+            children: Vec::new(),
+            cells: Vec::new(),
+            layout_state: LayoutState::default(),
+        }
+    }
+
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_columns(mut self, columns: Vec<Track>) -> Self {
+        self.columns = columns;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_rows(mut self, rows: Vec<Track>) -> Self {
+        self.rows = rows;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 同时设置行间距和列间距
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.column_gap = gap;
+        self.row_gap = gap;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_column_gap(mut self, gap: f32) -> Self {
+        self.column_gap = gap;
+        self.layout_state.invalidate();
+        self
+    }
+
+    pub fn with_row_gap(mut self, gap: f32) -> Self {
+        self.row_gap = gap;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 从右到左时第一列在最右边，单元格内的 Start 和 End 对齐互换
+    pub fn with_layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_state.set_direction(direction, &mut self.children);
+        self
+    }
+
+    pub fn add_child<W: Widget + 'static>(&mut self, widget: W, cell: GridCell) {
+        self.add_boxed(Box::new(widget), cell);
+    }
+
+    pub(crate) fn add_boxed(&mut self, mut widget: Box<dyn Widget>, cell: GridCell) {
+        widget.set_layout_direction(self.layout_state.direction());
+        self.children.push(widget);
+        self.cells.push(cell);
+        self.layout_state.invalidate();
+    }
+
+    // 已定义的轨道加上容纳所有子组件所需的 auto 轨道
+    fn tracks(defined: &[Track], needed: usize) -> Vec<Track> {
+        let mut tracks = defined.to_vec();
+        if tracks.len() < needed {
+            tracks.resize(needed, Track::Auto);
+        }
+        tracks
+    }
+
+    fn column_tracks(&self) -> Vec<Track> {
+        let needed = self.cells.iter().map(|cell| cell.column + cell.column_span).max().unwrap_or(0);
+        Self::tracks(&self.columns, needed)
+    }
+
+    fn row_tracks(&self) -> Vec<Track> {
+        let needed = self.cells.iter().map(|cell| cell.row + cell.row_span).max().unwrap_or(0);
+        Self::tracks(&self.rows, needed)
+    }
+
+    fn column_widths(&self, available: Option<f32>) -> Vec<f32> {
+        let items: Vec<TrackItem> = self
+            .children
+            .iter()
+            .zip(&self.cells)
+            .map(|(child, cell)| TrackItem {
+                start: cell.column,
+                span: cell.column_span,
+                size: child.measure(Constraints::unbounded()).0 + cell.margin.horizontal(),
+            })
+            .collect();
+        resolve_tracks(&self.column_tracks(), &items, self.column_gap, available)
+    }
+
+    // 自动换行的文本等按所在单元格的宽度计算首选高度
+    fn row_heights(&self, widths: &[f32], available: Option<f32>) -> Vec<f32> {
+        let items: Vec<TrackItem> = self
+            .children
+            .iter()
+            .zip(&self.cells)
+            .map(|(child, cell)| TrackItem {
+                start: cell.row,
+                span: cell.row_span,
+                size: self.child_height(child.as_ref(), cell, self.child_width(child.as_ref(), cell, widths)),
+            })
+            .collect();
+        resolve_tracks(&self.row_tracks(), &items, self.row_gap, available)
+    }
+
+    fn span_size(sizes: &[f32], start: usize, span: usize, gap: f32) -> f32 {
+        sizes[start..start + span].iter().sum::<f32>() + gap * (span - 1) as f32
+    }
+
+    // 子组件含外边距的宽度
+    fn child_width(&self, child: &dyn Widget, cell: &GridCell, widths: &[f32]) -> f32 {
+        let cell_width = Self::span_size(widths, cell.column, cell.column_span, self.column_gap);
+        let margin = cell.margin.horizontal();
+        match cell.align_x {
+            Some(_) => child.measure(Constraints::loose((cell_width - margin).max(0.0), f32::INFINITY)).0 + margin,
+            None => cell_width,
+        }
+    }
+
+    // 子组件在给定宽度（含外边距）下含外边距的首选高度
+    fn child_height(&self, child: &dyn Widget, cell: &GridCell, width: f32) -> f32 {
+        let width = (width - cell.margin.horizontal()).max(0.0);
+        child.measure(Constraints::tight_width(width)).1 + cell.margin.vertical()
+    }
+
+    fn content_size(&self) -> (f32, f32) {
+        (
+            self.rect.width - (self.padding.left + self.padding.right),
+            self.rect.height - (self.padding.top + self.padding.bottom),
+        )
+    }
+
+    fn layout(&mut self) {
+        if self.children.is_empty() {
+            return;
+        }
+        let (content_width, content_height) = self.content_size();
+        let widths = self.column_widths(Some(content_width));
+        let heights = self.row_heights(&widths, Some(content_height));
+        let column_offsets = track_offsets(&widths, self.column_gap);
+        let row_offsets = track_offsets(&heights, self.row_gap);
+
+        let rects: Vec<Rect> = self
+            .children
+            .iter()
+            .zip(&self.cells)
+            .map(|(child, cell)| {
+                let cell_x = self.rect.x + self.padding.left + column_offsets[cell.column];
+                let cell_y = self.rect.y + self.padding.top + row_offsets[cell.row];
+                let cell_width = Self::span_size(&widths, cell.column, cell.column_span, self.column_gap);
+                let cell_height = Self::span_size(&heights, cell.row, cell.row_span, self.row_gap);
+
+                let width = self.child_width(child.as_ref(), cell, &widths);
+                let height = match cell.align_y {
+                    Some(_) => self.child_height(child.as_ref(), cell, width).min(cell_height),
+                    None => cell_height,
+                };
+                let offset = |align: Option<Alignment>, free: f32| match align {
+                    Some(Alignment::Center) => free / 2.0,
+                    Some(Alignment::End) => free,
+                    _ => 0.0,
+                };
+                Rect {
+                    x: cell_x + offset(cell.align_x, cell_width - width),
+                    y: cell_y + offset(cell.align_y, cell_height - height),
+                    width,
+                    height,
+                }
+                .inset(cell.margin)
+            })
+            .collect();
+
+        let rtl = self.layout_state.direction().is_rtl();
+        for (child, rect) in self.children.iter_mut().zip(rects) {
+            child.arrange(if rtl { rect.mirror(self.rect) } else { rect });
+        }
+    }
+
+    fn total_size(sizes: &[f32], gap: f32) -> f32 {
+        sizes.iter().sum::<f32>() + gap * sizes.len().saturating_sub(1) as f32
+    }
+}
+
+impl Widget for Grid {
+    fn draw(&self, renderer: &mut Renderer) {
+        children::draw(&self.children, renderer);
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        children::handle_event(&mut self.children, event);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        children::draw_overlay(&self.children, renderer);
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        children::handle_overlay_event(&mut self.children, event)
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        children::next_wakeup(&self.children)
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        children::cursor(&self.children)
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        if self.layout_state.update_rect(&mut self.rect, rect, &self.children) {
+            self.layout();
+            self.layout_state.clean();
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.layout_state.measure(constraints, self.needs_layout(), || measure_preferred(self, constraints))
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.layout_state.needs_layout(&self.children)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_state.inherit_direction(direction, &mut self.children);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        children::set_theme(&mut self.children, theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.layout_state.set_stylesheet(sheet, &mut self.children);
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        let widths = self.column_widths(None);
+        let heights = self.row_heights(&widths, None);
+        (
+            Self::total_size(&widths, self.column_gap) + self.padding.left + self.padding.right,
+            Self::total_size(&heights, self.row_gap) + self.padding.top + self.padding.bottom,
+        )
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        let widths = self.column_widths(Some(width - (self.padding.left + self.padding.right)));
+        let heights = self.row_heights(&widths, None);
+        Self::total_size(&heights, self.row_gap) + self.padding.top + self.padding.bottom
+    }
+}
@@ -0,0 +1,173 @@
+use std::path::Path;
+use std::sync::Arc;
+use winit::event::WindowEvent;
+use super::Widget;
+use crate::renderer::Renderer;
+use crate::image::{ImageData, ImageFit, ScaleFilter};
+use crate::layout::{LayoutDirection, Rect};
+
+// 加载失败时占位框的默认边长，可以用 with_size 修改
+const PLACEHOLDER_SIZE: f32 = 32.0;
+
+pub struct Image {
+    rect: Rect,
+    data: Option<Arc<ImageData>>,
+    fit: ImageFit,
+    filter: ScaleFilter,
+    // 有方向的图标（箭头、返回等）在从右到左的布局中水平镜像
+    directional: bool,
+    rtl: bool,
+}
+
+impl Image {
+    pub fn new(x: f32, y: f32, data: ImageData) -> Self {
+        Self {
+            rect: Rect {
+                x,
+                y,
+                width: data.width() as f32,
+                height: data.height() as f32,
+            },
+            data: Some(Arc::new(data)),
+            fit: ImageFit::Contain,
+            filter: ScaleFilter::Bilinear,
+            directional: false,
+            rtl: false,
+        }
+    }
+
+    // 只在创建时读取一次文件，之后文件修改不会更新；加载失败时显示 PLACEHOLDER_SIZE 大小的占位框。
+    // 通过 .ui 文件创建的图片由 UiLoader 监视，修改后随界面一起重新加载
+    pub fn from_file<P: AsRef<Path>>(x: f32, y: f32, path: P) -> Self {
+        match ImageData::load(path.as_ref()) {
+            Ok(data) => Self::new(x, y, data),
+            Err(e) => {
+                println!("Failed to load image {:?}: {:?}", path.as_ref(), e);
+                Self {
+                    rect: Rect { x, y, width: PLACEHOLDER_SIZE, height: PLACEHOLDER_SIZE },
+                    data: None,
+                    fit: ImageFit::Contain,
+                    filter: ScaleFilter::Bilinear,
+                    directional: false,
+                    rtl: false,
+                }
+            }
+        }
+    }
+
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.rect.width = width;
+        self.rect.height = height;
+        self
+    }
+
+    pub fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: ScaleFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_directional(mut self, directional: bool) -> Self {
+        self.directional = directional;
+        self
+    }
+
+    pub fn set_image(&mut self, data: ImageData) {
+        self.data = Some(Arc::new(data));
+    }
+
+    // 根据适配方式计算图片的源区域和目标区域
+    fn regions(&self, data: &ImageData) -> (Rect, Rect) {
+        let rect = self.rect;
+        let image_width = data.width() as f32;
+        let image_height = data.height() as f32;
+        let full = Rect { x: 0.0, y: 0.0, width: image_width, height: image_height };
+
+        let centered = |width: f32, height: f32| Rect {
+            x: rect.x + (rect.width - width) / 2.0,
+            y: rect.y + (rect.height - height) / 2.0,
+            width,
+            height,
+        };
+
+        match self.fit {
+            ImageFit::Fill => (full, rect),
+            ImageFit::Contain => {
+                let scale = (rect.width / image_width).min(rect.height / image_height);
+                (full, centered(image_width * scale, image_height * scale))
+            }
+            ImageFit::Cover => {
+                let scale = (rect.width / image_width).max(rect.height / image_height);
+                let width = rect.width / scale;
+                let height = rect.height / scale;
+                let src = Rect {
+                    x: (image_width - width) / 2.0,
+                    y: (image_height - height) / 2.0,
+                    width,
+                    height,
+                };
+                (src, rect)
+            }
+            ImageFit::None => {
+                // 原始大小，超出区域的部分居中裁掉
+                let width = image_width.min(rect.width);
+                let height = image_height.min(rect.height);
+                let src = Rect {
+                    x: (image_width - width) / 2.0,
+                    y: (image_height - height) / 2.0,
+                    width,
+                    height,
+                };
+                (src, centered(width, height))
+            }
+        }
+    }
+}
+
+impl Widget for Image {
+    fn draw(&self, renderer: &mut Renderer) {
+        match &self.data {
+            Some(data) => {
+                let (src, dest) = self.regions(data);
+                if self.directional && self.rtl {
+                    renderer.draw_image_region_mirrored(data, src, dest, self.filter);
+                } else {
+                    renderer.draw_image_region(data, src, dest, self.filter);
+                }
+            }
+            None => {
+                renderer.draw_rect(
+                    self.rect.x as i32,
+                    self.rect.y as i32,
+                    self.rect.width as u32,
+                    self.rect.height as u32,
+                    renderer.theme().palette.border,
+                );
+            }
+        }
+    }
+
+    fn handle_event(&mut self, _event: &WindowEvent) {
+        // 图片组件不需要处理事件
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width, self.rect.height)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.rtl = direction.is_rtl();
+    }
+}
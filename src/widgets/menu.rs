@@ -0,0 +1,1039 @@
+use std::sync::Arc;
+use std::time::Instant;
+use winit::event::{ElementState, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode, WindowEvent};
+use winit::window::CursorIcon;
+use super::Widget;
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::theme::{Theme, WidgetState};
+use crate::ui::style::Stylesheet;
+use crate::font::Font;
+use crate::layout::{place_popup, Constraints, LayoutDirection, Placement, Rect};
+
+const BAR_HEIGHT: f32 = 24.0;
+const TITLE_PADDING: f32 = 10.0;
+const ITEM_HEIGHT: f32 = 20.0;
+const SEPARATOR_HEIGHT: f32 = 7.0;
+const POPUP_BORDER: f32 = 2.0;
+const CHECK_COLUMN: f32 = 22.0;
+const ARROW_COLUMN: f32 = 18.0;
+const ACCELERATOR_GAP: f32 = 24.0;
+const MIN_POPUP_WIDTH: f32 = 120.0;
+const CHECK_WIDTH: f32 = 9.0;
+
+// 收到 Resized 事件之前不限制弹出位置
+const UNBOUNDED: Rect = Rect { x: 0.0, y: 0.0, width: f32::MAX, height: f32::MAX };
+
+fn viewport(width: u32, height: u32) -> Rect {
+    Rect { x: 0.0, y: 0.0, width: width as f32, height: height as f32 }
+}
+
+// 解析助记符：'&' 之后的字符作为助记符，"&&" 表示字面量 '&'
+// 返回去掉标记后的文本和助记符所在的字符下标
+fn parse_mnemonic(label: &str) -> (String, Option<usize>) {
+    let mut text = String::new();
+    let mut mnemonic = None;
+    let mut count = 0;
+    let mut chars = label.chars();
+
+    while let Some(c) = chars.next() {
+        let c = if c == '&' {
+            match chars.next() {
+                Some('&') => '&',
+                Some(next) => {
+                    if mnemonic.is_none() {
+                        mnemonic = Some(count);
+                    }
+                    next
+                }
+                None => break,
+            }
+        } else {
+            c
+        };
+        text.push(c);
+        count += 1;
+    }
+
+    (text, mnemonic)
+}
+
+fn mnemonic_char(text: &str, mnemonic: Option<usize>) -> Option<char> {
+    mnemonic
+        .and_then(|index| text.chars().nth(index))
+        .map(|c| c.to_ascii_lowercase())
+}
+
+fn text_width(text: &str) -> f32 {
+    Font::default().text_width(text) as f32
+}
+
+// 文本在高度为 height 的区域中垂直居中时的顶部偏移
+fn text_offset(font: &Font, height: f32) -> f32 {
+    ((height - font.line_height() as f32) / 2.0).max(0.0)
+}
+
+// 把字母键转换为小写字符，用于 Alt + 助记符
+fn key_to_char(key: VirtualKeyCode) -> Option<char> {
+    let code = key as u32;
+    let a = VirtualKeyCode::A as u32;
+    if (a..=VirtualKeyCode::Z as u32).contains(&code) {
+        Some((b'a' + (code - a) as u8) as char)
+    } else {
+        None
+    }
+}
+
+// 绘制带助记符下划线的文本
+fn draw_label(renderer: &mut Renderer, font: &Font, x: i32, y: i32, text: &str, mnemonic: Option<usize>, color: Color) {
+    let mut offset = 0;
+    for (i, c) in text.chars().enumerate() {
+        let advance = font.render_char(renderer, x + offset as i32, y, c, color);
+        if mnemonic == Some(i) {
+            renderer.draw_rect(x + offset as i32, y + font.ascent() as i32 + 1, advance, 1, color);
+        }
+        offset += advance;
+    }
+}
+
+// 绘制勾选标记
+fn draw_check(renderer: &mut Renderer, x: i32, y: i32, color: Color) {
+    for i in 0..3 {
+        renderer.draw_rect(x + i, y + 4 + i, 2, 2, color);
+    }
+    for i in 0..5 {
+        renderer.draw_rect(x + 3 + i, y + 5 - i, 2, 2, color);
+    }
+}
+
+pub struct MenuItem {
+    text: String,
+    mnemonic: Option<usize>,
+    accelerator: Option<String>,
+    checkable: bool,
+    checked: bool,
+    enabled: bool,
+    on_activate: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_toggle: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+}
+
+impl MenuItem {
+    pub fn new(label: &str) -> Self {
+        let (text, mnemonic) = parse_mnemonic(label);
+        Self {
+            text,
+            mnemonic,
+            accelerator: None,
+            checkable: false,
+            checked: false,
+            enabled: true,
+            on_activate: None,
+            on_toggle: None,
+        }
+    }
+
+    // 快捷键提示，例如 "Ctrl+O"，仅用于显示
+    pub fn with_accelerator(mut self, accelerator: &str) -> Self {
+        self.accelerator = Some(accelerator.to_string());
+        self
+    }
+
+    // 设置后菜单项变为可勾选项
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checkable = true;
+        self.checked = checked;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn on_activate<F>(mut self, callback: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_activate = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn on_toggle<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(bool) + Send + Sync + 'static,
+    {
+        self.on_toggle = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn is_checked(&self) -> bool {
+        self.checked
+    }
+
+    fn activate(&mut self) {
+        if self.checkable {
+            self.checked = !self.checked;
+            if let Some(callback) = &self.on_toggle {
+                callback(self.checked);
+            }
+        }
+        if let Some(callback) = &self.on_activate {
+            callback();
+        }
+    }
+}
+
+pub enum MenuEntry {
+    Item(MenuItem),
+    Separator,
+    Submenu(Menu),
+}
+
+impl MenuEntry {
+    fn height(&self) -> f32 {
+        match self {
+            MenuEntry::Separator => SEPARATOR_HEIGHT,
+            _ => ITEM_HEIGHT,
+        }
+    }
+
+    fn is_selectable(&self) -> bool {
+        match self {
+            MenuEntry::Item(item) => item.enabled,
+            MenuEntry::Submenu(menu) => menu.enabled,
+            MenuEntry::Separator => false,
+        }
+    }
+
+    fn mnemonic(&self) -> Option<char> {
+        match self {
+            MenuEntry::Item(item) => mnemonic_char(&item.text, item.mnemonic),
+            MenuEntry::Submenu(menu) => mnemonic_char(&menu.text, menu.mnemonic),
+            MenuEntry::Separator => None,
+        }
+    }
+}
+
+pub struct Menu {
+    text: String,
+    mnemonic: Option<usize>,
+    enabled: bool,
+    entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    pub fn new(title: &str) -> Self {
+        let (text, mnemonic) = parse_mnemonic(title);
+        Self {
+            text,
+            mnemonic,
+            enabled: true,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn with_item(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    pub fn with_separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    pub fn with_submenu(mut self, menu: Menu) -> Self {
+        self.entries.push(MenuEntry::Submenu(menu));
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    fn popup_size(&self) -> (f32, f32) {
+        let mut label_width: f32 = 0.0;
+        let mut accelerator_width: f32 = 0.0;
+        let mut height = POPUP_BORDER * 2.0;
+
+        for entry in &self.entries {
+            match entry {
+                MenuEntry::Item(item) => {
+                    label_width = label_width.max(text_width(&item.text));
+                    if let Some(accelerator) = &item.accelerator {
+                        accelerator_width = accelerator_width.max(text_width(accelerator));
+                    }
+                }
+                MenuEntry::Submenu(menu) => {
+                    label_width = label_width.max(text_width(&menu.text));
+                }
+                MenuEntry::Separator => {}
+            }
+            height += entry.height();
+        }
+
+        let accelerator_width = if accelerator_width > 0.0 {
+            ACCELERATOR_GAP + accelerator_width
+        } else {
+            0.0
+        };
+        let width = CHECK_COLUMN + label_width + accelerator_width + ARROW_COLUMN;
+        (width.max(MIN_POPUP_WIDTH), height)
+    }
+
+    fn entry_rect(&self, popup: Rect, index: usize) -> Rect {
+        let y = popup.y + POPUP_BORDER + self.entries[..index].iter().map(MenuEntry::height).sum::<f32>();
+        Rect {
+            x: popup.x,
+            y,
+            width: popup.width,
+            height: self.entries[index].height(),
+        }
+    }
+
+    fn entry_at(&self, popup: Rect, y: f32) -> Option<usize> {
+        let mut top = popup.y + POPUP_BORDER;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let bottom = top + entry.height();
+            if y >= top && y < bottom {
+                return Some(index);
+            }
+            top = bottom;
+        }
+        None
+    }
+
+    fn next_selectable(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+        let count = self.entries.len();
+        if count == 0 {
+            return None;
+        }
+        let mut index = match from {
+            Some(index) => index,
+            None if forward => count - 1,
+            None => 0,
+        };
+        for _ in 0..count {
+            index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+            if self.entries[index].is_selectable() {
+                return Some(index);
+            }
+        }
+        None
+    }
+}
+
+// 弹出菜单的处理结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PopupEvent {
+    Ignored,
+    Consumed,
+    Closed,
+    // 在顶层菜单上按左右方向键，由菜单栏切换到相邻菜单
+    NavigateLeft,
+    NavigateRight,
+}
+
+struct PopupLevel {
+    rect: Rect,
+    selected: Option<usize>,
+}
+
+// 一组级联的弹出菜单，第 0 层是根菜单，之后每层都是上一层选中项的子菜单
+struct MenuPopup {
+    levels: Vec<PopupLevel>,
+    bounds: Rect,
+    cursor: (f32, f32),
+    // 从右到左时与锚点右对齐，子菜单向左展开，菜单项镜像显示
+    rtl: bool,
+}
+
+impl MenuPopup {
+    // 弹出在锚点下方，超出窗口边界时自动翻转
+    fn new(root: &Menu, anchor: Rect, bounds: Rect, cursor: (f32, f32), direction: LayoutDirection) -> Self {
+        let (width, height) = root.popup_size();
+        let rtl = direction.is_rtl();
+        let placement = if rtl { Placement::BelowEnd } else { Placement::Below };
+        Self {
+            levels: vec![PopupLevel {
+                rect: place_popup(anchor, width, height, bounds, placement),
+                selected: None,
+            }],
+            bounds,
+            cursor,
+            rtl,
+        }
+    }
+
+    fn menu<'a>(&self, root: &'a Menu, level: usize) -> &'a Menu {
+        let mut menu = root;
+        for popup in &self.levels[..level] {
+            match popup.selected.and_then(|index| menu.entries.get(index)) {
+                Some(MenuEntry::Submenu(submenu)) => menu = submenu,
+                _ => break,
+            }
+        }
+        menu
+    }
+
+    fn item_mut<'a>(&self, root: &'a mut Menu, level: usize, index: usize) -> Option<&'a mut MenuItem> {
+        let mut menu = root;
+        for popup in &self.levels[..level] {
+            menu = match menu.entries.get_mut(popup.selected?)? {
+                MenuEntry::Submenu(submenu) => submenu,
+                _ => return None,
+            };
+        }
+        match menu.entries.get_mut(index)? {
+            MenuEntry::Item(item) => Some(item),
+            _ => None,
+        }
+    }
+
+    fn contains(&self, x: f32, y: f32) -> bool {
+        self.levels.iter().any(|level| level.rect.contains(x, y))
+    }
+
+    // 键盘操作所在的层：最深的有选中项的层
+    fn active_level(&self) -> usize {
+        self.levels
+            .iter()
+            .rposition(|level| level.selected.is_some())
+            .unwrap_or(0)
+    }
+
+    // 选中某一项，如果是子菜单则展开
+    fn select(&mut self, root: &Menu, level: usize, index: usize) {
+        self.levels.truncate(level + 1);
+        self.levels[level].selected = Some(index);
+
+        let menu = self.menu(root, level);
+        if let MenuEntry::Submenu(submenu) = &menu.entries[index] {
+            if submenu.enabled {
+                let parent = self.levels[level].rect;
+                let entry = menu.entry_rect(parent, index);
+                let (width, height) = submenu.popup_size();
+                // 子菜单在右侧（从右到左时在左侧）展开，放不下时翻转到另一侧
+                let anchor = Rect {
+                    x: parent.x + POPUP_BORDER,
+                    y: entry.y - POPUP_BORDER,
+                    width: parent.width - POPUP_BORDER * 2.0,
+                    height: entry.height + POPUP_BORDER * 2.0,
+                };
+                self.levels.push(PopupLevel {
+                    rect: place_popup(anchor, width, height, self.bounds, if self.rtl { Placement::Left } else { Placement::Right }),
+                    selected: None,
+                });
+            }
+        }
+    }
+
+    fn select_first(&mut self, root: &Menu, level: usize) {
+        if let Some(index) = self.menu(root, level).next_selectable(None, true) {
+            self.select(root, level, index);
+        }
+    }
+
+    fn hover(&mut self, root: &Menu, x: f32, y: f32) -> bool {
+        for level in (0..self.levels.len()).rev() {
+            let rect = self.levels[level].rect;
+            if !rect.contains(x, y) {
+                continue;
+            }
+
+            let menu = self.menu(root, level);
+            match menu.entry_at(rect, y) {
+                Some(index) if menu.entries[index].is_selectable() => {
+                    if self.levels[level].selected != Some(index) {
+                        self.select(root, level, index);
+                    }
+                }
+                _ => {
+                    self.levels.truncate(level + 1);
+                    self.levels[level].selected = None;
+                }
+            }
+            return true;
+        }
+        false
+    }
+
+    fn activate(&mut self, root: &mut Menu, level: usize, index: usize) -> PopupEvent {
+        let entry = &self.menu(root, level).entries[index];
+        if !entry.is_selectable() {
+            return PopupEvent::Consumed;
+        }
+        if let MenuEntry::Submenu(_) = entry {
+            self.select(root, level, index);
+            self.select_first(root, level + 1);
+            return PopupEvent::Consumed;
+        }
+
+        if let Some(item) = self.item_mut(root, level, index) {
+            item.activate();
+        }
+        PopupEvent::Closed
+    }
+
+    fn handle_key(&mut self, root: &mut Menu, key: VirtualKeyCode) -> PopupEvent {
+        let level = self.active_level();
+        let selected = self.levels[level].selected;
+        // 从右到左时左方向键展开子菜单，右方向键返回上一层
+        let key = match key {
+            VirtualKeyCode::Left if self.rtl => VirtualKeyCode::Right,
+            VirtualKeyCode::Right if self.rtl => VirtualKeyCode::Left,
+            key => key,
+        };
+
+        match key {
+            VirtualKeyCode::Up | VirtualKeyCode::Down => {
+                let forward = key == VirtualKeyCode::Down;
+                if let Some(index) = self.menu(root, level).next_selectable(selected, forward) {
+                    self.select(root, level, index);
+                }
+                PopupEvent::Consumed
+            }
+            VirtualKeyCode::Right => match selected {
+                Some(index) if matches!(self.menu(root, level).entries[index], MenuEntry::Submenu(_)) => {
+                    self.activate(root, level, index)
+                }
+                _ => PopupEvent::NavigateRight,
+            },
+            VirtualKeyCode::Left => {
+                if level > 0 {
+                    self.levels.truncate(level);
+                    PopupEvent::Consumed
+                } else {
+                    PopupEvent::NavigateLeft
+                }
+            }
+            VirtualKeyCode::Return | VirtualKeyCode::NumpadEnter | VirtualKeyCode::Space => match selected {
+                Some(index) => self.activate(root, level, index),
+                None => PopupEvent::Consumed,
+            },
+            VirtualKeyCode::Escape => {
+                if self.levels.len() > 1 {
+                    self.levels.pop();
+                    PopupEvent::Consumed
+                } else {
+                    PopupEvent::Closed
+                }
+            }
+            _ => PopupEvent::Consumed,
+        }
+    }
+
+    fn handle_mnemonic(&mut self, root: &mut Menu, c: char) -> PopupEvent {
+        let level = self.active_level();
+        let c = c.to_ascii_lowercase();
+        let found = self
+            .menu(root, level)
+            .entries
+            .iter()
+            .position(|entry| entry.is_selectable() && entry.mnemonic() == Some(c));
+
+        match found {
+            Some(index) => {
+                self.select(root, level, index);
+                self.activate(root, level, index)
+            }
+            None => PopupEvent::Consumed,
+        }
+    }
+
+    fn handle_event(&mut self, root: &mut Menu, event: &WindowEvent) -> PopupEvent {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+                if self.hover(root, self.cursor.0, self.cursor.1) {
+                    PopupEvent::Consumed
+                } else {
+                    PopupEvent::Ignored
+                }
+            }
+            WindowEvent::MouseInput { state: ElementState::Pressed, .. } => {
+                if self.contains(self.cursor.0, self.cursor.1) {
+                    PopupEvent::Consumed
+                } else {
+                    // 点击菜单外部时关闭菜单
+                    PopupEvent::Closed
+                }
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => {
+                let (x, y) = self.cursor;
+                let hit = (0..self.levels.len()).rev().find_map(|level| {
+                    let rect = self.levels[level].rect;
+                    if rect.contains(x, y) {
+                        Some((level, self.menu(root, level).entry_at(rect, y)))
+                    } else {
+                        None
+                    }
+                });
+                match hit {
+                    Some((level, Some(index))) => self.activate(root, level, index),
+                    Some((_, None)) => PopupEvent::Consumed,
+                    None => PopupEvent::Ignored,
+                }
+            }
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(key),
+                    ..
+                },
+                ..
+            } => self.handle_key(root, *key),
+            WindowEvent::ReceivedCharacter(c) if c.is_alphanumeric() => self.handle_mnemonic(root, *c),
+            WindowEvent::KeyboardInput { .. } | WindowEvent::ReceivedCharacter(_) => PopupEvent::Consumed,
+            _ => PopupEvent::Ignored,
+        }
+    }
+
+    fn draw(&self, root: &Menu, renderer: &mut Renderer, font: &Font) {
+        let theme = renderer.theme();
+        let (palette, item_style) = (theme.palette, theme.menu_item);
+        for (level, popup) in self.levels.iter().enumerate() {
+            let menu = self.menu(root, level);
+            let rect = popup.rect;
+
+            // 边框和背景
+            renderer.draw_rect(rect.x as i32, rect.y as i32, rect.width as u32, rect.height as u32, palette.border);
+            renderer.draw_rect(
+                rect.x as i32 + 1,
+                rect.y as i32 + 1,
+                rect.width as u32 - 2,
+                rect.height as u32 - 2,
+                palette.popup,
+            );
+
+            for (index, entry) in menu.entries.iter().enumerate() {
+                let entry_rect = menu.entry_rect(rect, index);
+                let x = entry_rect.x as i32;
+                let y = entry_rect.y as i32;
+                let width = entry_rect.width as i32;
+
+                let (text, mnemonic, enabled) = match entry {
+                    MenuEntry::Separator => {
+                        renderer.draw_rect(x + 4, y + 3, (width - 8) as u32, 1, palette.border);
+                        continue;
+                    }
+                    MenuEntry::Item(item) => (&item.text, item.mnemonic, item.enabled),
+                    MenuEntry::Submenu(submenu) => (&submenu.text, submenu.mnemonic, submenu.enabled),
+                };
+
+                let selected = enabled && popup.selected == Some(index);
+                let style = item_style.resolve(WidgetState { hovered: selected, disabled: !enabled, ..WidgetState::default() });
+                if selected {
+                    renderer.draw_rect(x + 2, y, (width - 4) as u32, ITEM_HEIGHT as u32, style.background);
+                }
+
+                // 各列按从左到右计算位置，从右到左时在菜单项中镜像
+                let column_x = |left: f32, column_width: f32| {
+                    if self.rtl {
+                        x + width - (left + column_width) as i32
+                    } else {
+                        x + left as i32
+                    }
+                };
+                let color = style.text;
+                let text_y = y + text_offset(font, ITEM_HEIGHT) as i32;
+                draw_label(renderer, font, column_x(CHECK_COLUMN, text_width(text)), text_y, text, mnemonic, color);
+
+                match entry {
+                    MenuEntry::Item(item) => {
+                        if item.checkable && item.checked {
+                            draw_check(renderer, column_x(7.0, CHECK_WIDTH), text_y, color);
+                        }
+                        if let Some(accelerator) = &item.accelerator {
+                            let accelerator_width = text_width(accelerator);
+                            let accelerator_x = column_x(width as f32 - ARROW_COLUMN - accelerator_width, accelerator_width);
+                            draw_label(renderer, font, accelerator_x, text_y, accelerator, None, color);
+                        }
+                    }
+                    MenuEntry::Submenu(_) => {
+                        let arrow = if self.rtl { '<' } else { '>' };
+                        let arrow_x = column_x(width as f32 - ARROW_COLUMN + 4.0, text_width(&arrow.to_string()));
+                        font.render_char(renderer, arrow_x, text_y, arrow, color);
+                    }
+                    MenuEntry::Separator => {}
+                }
+            }
+        }
+    }
+}
+
+pub struct MenuBar {
+    rect: Rect,
+    menus: Vec<Menu>,
+    hovered: Option<usize>,
+    open: Option<(usize, MenuPopup)>,
+    cursor: (f32, f32),
+    modifiers: ModifiersState,
+    viewport: Rect,
+    direction: LayoutDirection,
+}
+
+impl MenuBar {
+    pub fn new(x: f32, y: f32, width: f32) -> Self {
+        Self {
+            rect: Rect { x, y, width, height: BAR_HEIGHT },
+            menus: Vec::new(),
+            hovered: None,
+            open: None,
+            cursor: (0.0, 0.0),
+            modifiers: ModifiersState::empty(),
+            viewport: UNBOUNDED,
+            direction: LayoutDirection::Ltr,
+        }
+    }
+
+    pub fn with_menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    // 从右到左时第一个菜单在最右边
+    fn title_rect(&self, index: usize) -> Rect {
+        let x = self.rect.x
+            + self.menus[..index]
+                .iter()
+                .map(|menu| text_width(&menu.text) + TITLE_PADDING * 2.0)
+                .sum::<f32>();
+        let rect = Rect {
+            x,
+            y: self.rect.y,
+            width: text_width(&self.menus[index].text) + TITLE_PADDING * 2.0,
+            height: self.rect.height,
+        };
+        if self.direction.is_rtl() {
+            rect.mirror(self.rect)
+        } else {
+            rect
+        }
+    }
+
+    fn title_at(&self, x: f32, y: f32) -> Option<usize> {
+        (0..self.menus.len()).find(|&index| self.title_rect(index).contains(x, y))
+    }
+
+    fn open_menu(&mut self, index: usize, select_first: bool) {
+        if !self.menus[index].enabled {
+            return;
+        }
+        let title = self.title_rect(index);
+        let mut popup = MenuPopup::new(&self.menus[index], title, self.viewport, self.cursor, self.direction);
+        if select_first {
+            popup.select_first(&self.menus[index], 0);
+        }
+        self.open = Some((index, popup));
+    }
+
+    // 切换到相邻的可用菜单
+    fn open_adjacent(&mut self, current: usize, forward: bool) {
+        let count = self.menus.len();
+        let mut index = current;
+        for _ in 0..count {
+            index = if forward { (index + 1) % count } else { (index + count - 1) % count };
+            if self.menus[index].enabled {
+                self.open_menu(index, true);
+                return;
+            }
+        }
+    }
+}
+
+impl Widget for MenuBar {
+    fn draw(&self, renderer: &mut Renderer) {
+        renderer.draw_rect(
+            self.rect.x as i32,
+            self.rect.y as i32,
+            self.rect.width as u32,
+            self.rect.height as u32,
+            renderer.theme().palette.surface_variant,
+        );
+
+        let font = Font::default();
+        let item_style = renderer.theme().menu_item;
+        let open = self.open.as_ref().map(|(index, _)| *index);
+        for (index, menu) in self.menus.iter().enumerate() {
+            let title = self.title_rect(index);
+            let highlighted = menu.enabled && (open == Some(index) || (open.is_none() && self.hovered == Some(index)));
+            let style = item_style.resolve(WidgetState { hovered: highlighted, disabled: !menu.enabled, ..WidgetState::default() });
+            if highlighted {
+                renderer.draw_rect(title.x as i32, title.y as i32, title.width as u32, title.height as u32, style.background);
+            }
+
+            let color = style.text;
+            draw_label(
+                renderer,
+                &font,
+                (title.x + TITLE_PADDING) as i32,
+                (title.y + text_offset(&font, title.height)) as i32,
+                &menu.text,
+                menu.mnemonic,
+                color,
+            );
+        }
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        if let Some((index, popup)) = &self.open {
+            popup.draw(&self.menus[*index], renderer, &Font::default());
+        }
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        let Some((index, mut popup)) = self.open.take() else {
+            return false;
+        };
+
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            self.cursor = (position.x as f32, position.y as f32);
+            // 菜单打开时，悬停到其他标题上直接切换菜单
+            if let Some(title) = self.title_at(self.cursor.0, self.cursor.1) {
+                if title != index && self.menus[title].enabled {
+                    self.open_menu(title, false);
+                } else {
+                    self.open = Some((index, popup));
+                }
+                return true;
+            }
+        }
+
+        if let WindowEvent::MouseInput { state: ElementState::Pressed, .. } = event {
+            if let Some(title) = self.title_at(self.cursor.0, self.cursor.1) {
+                // 再次点击当前标题时关闭菜单
+                if title != index {
+                    self.open_menu(title, false);
+                }
+                return true;
+            }
+        }
+
+        match popup.handle_event(&mut self.menus[index], event) {
+            PopupEvent::Ignored => {
+                self.open = Some((index, popup));
+                false
+            }
+            PopupEvent::Consumed => {
+                self.open = Some((index, popup));
+                true
+            }
+            PopupEvent::Closed => true,
+            PopupEvent::NavigateLeft => {
+                self.open_adjacent(index, false);
+                true
+            }
+            PopupEvent::NavigateRight => {
+                self.open_adjacent(index, true);
+                true
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        if let WindowEvent::Resized(size) = event {
+            self.viewport = viewport(size.width, size.height);
+            self.open = None;
+        }
+
+        // 菜单打开时由弹出层处理事件
+        if self.open.is_some() {
+            return;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+                self.hovered = self.title_at(self.cursor.0, self.cursor.1);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Some(index) = self.title_at(self.cursor.0, self.cursor.1) {
+                    self.open_menu(index, false);
+                }
+            }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = *modifiers;
+            }
+            WindowEvent::KeyboardInput {
+                input: KeyboardInput {
+                    state: ElementState::Pressed,
+                    virtual_keycode: Some(key),
+                    ..
+                },
+                ..
+            } if self.modifiers.alt() => {
+                // Alt + 助记符打开对应菜单
+                if let Some(c) = key_to_char(*key) {
+                    let found = self
+                        .menus
+                        .iter()
+                        .position(|menu| mnemonic_char(&menu.text, menu.mnemonic) == Some(c));
+                    if let Some(index) = found {
+                        self.open_menu(index, true);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.open = None;
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        (self.rect.width, BAR_HEIGHT)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.direction = direction;
+        self.open = None;
+    }
+}
+
+// 为任意组件添加右键菜单
+pub struct ContextMenu {
+    child: Box<dyn Widget>,
+    menu: Menu,
+    popup: Option<MenuPopup>,
+    cursor: (f32, f32),
+    viewport: Rect,
+    direction: LayoutDirection,
+}
+
+impl ContextMenu {
+    pub fn new<W: Widget + 'static>(child: W, menu: Menu) -> Self {
+        Self {
+            child: Box::new(child),
+            menu,
+            popup: None,
+            cursor: (0.0, 0.0),
+            viewport: UNBOUNDED,
+            direction: LayoutDirection::Ltr,
+        }
+    }
+}
+
+impl Widget for ContextMenu {
+    fn draw(&self, renderer: &mut Renderer) {
+        self.child.draw(renderer);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        self.child.draw_overlay(renderer);
+        if let Some(popup) = &self.popup {
+            popup.draw(&self.menu, renderer, &Font::default());
+        }
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        let Some(mut popup) = self.popup.take() else {
+            return self.child.handle_overlay_event(event);
+        };
+
+        match popup.handle_event(&mut self.menu, event) {
+            PopupEvent::Ignored => {
+                self.popup = Some(popup);
+                false
+            }
+            PopupEvent::Closed => true,
+            _ => {
+                self.popup = Some(popup);
+                true
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        self.child.handle_event(event);
+
+        if let WindowEvent::Resized(size) = event {
+            self.viewport = viewport(size.width, size.height);
+            self.popup = None;
+        }
+
+        if self.popup.is_some() {
+            return;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = (position.x as f32, position.y as f32);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Right,
+                ..
+            } => {
+                let (x, y) = self.cursor;
+                if self.child.get_rect().contains(x, y) {
+                    let anchor = Rect { x, y, width: 0.0, height: 0.0 };
+                    self.popup = Some(MenuPopup::new(&self.menu, anchor, self.viewport, self.cursor, self.direction));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        self.child.next_wakeup()
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.child.get_rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.child.set_rect(rect);
+        self.popup = None;
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.child.preferred_size()
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        self.child.preferred_height(width)
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.child.measure(constraints)
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.child.needs_layout()
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.direction = direction;
+        self.popup = None;
+        self.child.set_layout_direction(direction);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.child.set_theme(theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.child.set_stylesheet(sheet);
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        self.child.cursor()
+    }
+}
@@ -1,9 +1,183 @@
+use std::cell::RefCell;
+use std::time::Instant;
+use winit::event::WindowEvent;
+use winit::dpi::PhysicalPosition;
+use winit::window::CursorIcon;
 use crate::renderer::Renderer;
-use crate::layout::Rect;
+use crate::theme::{Theme, WidgetState};
+use crate::ui::style::{StyleProperties, Stylesheet};
+use crate::layout::{Constraints, LayoutDirection, Rect};
 
 pub mod button;
 pub mod text;
 pub mod container;
+pub mod grid;
+pub mod stack;
+pub mod constraint_layout;
+pub mod splitter;
+pub mod menu;
+pub mod tooltip;
+pub mod combo_box;
+pub mod image;
+pub mod rich_text;
+mod children;
+
+// 每个容器最多缓存的测量结果数，常见的是不限制和固定宽度两种约束
+const MEASURE_CACHE_SIZE: usize = 4;
+
+// 容器的布局状态：是否需要重新排列子组件、按约束缓存的测量结果，以及布局方向
+#[derive(Default)]
+pub(crate) struct LayoutState {
+    dirty: bool,
+    measured: RefCell<Vec<(Constraints, (f32, f32))>>,
+    // 容器自身设置的方向，None 时使用从父组件继承的方向
+    direction: Option<LayoutDirection>,
+    inherited: LayoutDirection,
+}
+
+impl LayoutState {
+    // 子组件或布局参数发生变化
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+        self.measured.get_mut().clear();
+    }
+
+    // 排列完成
+    pub fn clean(&mut self) {
+        self.dirty = false;
+    }
+
+    pub fn direction(&self) -> LayoutDirection {
+        self.direction.unwrap_or(self.inherited)
+    }
+
+    // 设置容器所在子树的布局方向，并传给子组件
+    pub fn set_direction(&mut self, direction: LayoutDirection, children: &mut [Box<dyn Widget>]) {
+        self.direction = Some(direction);
+        self.invalidate();
+        children::set_layout_direction(children, direction);
+    }
+
+    // 父组件的方向发生变化，容器实际使用的方向随之改变时传给子组件
+    pub fn inherit_direction(&mut self, direction: LayoutDirection, children: &mut [Box<dyn Widget>]) {
+        let previous = self.direction();
+        self.inherited = direction;
+        if self.direction() != previous {
+            self.invalidate();
+            children::set_layout_direction(children, self.direction());
+        }
+    }
+
+    // 容器自身需要重新排列，或者子组件树中有组件需要重新布局
+    pub fn needs_layout(&self, children: &[Box<dyn Widget>]) -> bool {
+        self.dirty || children.iter().any(|child| child.needs_layout())
+    }
+
+    // 容器被放到 rect 上。位置和尺寸不变、子组件树也没有变化时保留上次的布局并返回 false，
+    // 否则更新 current 并返回 true，容器随后重新排列子组件并调用 clean
+    pub fn update_rect(&mut self, current: &mut Rect, rect: Rect, children: &[Box<dyn Widget>]) -> bool {
+        let stale = self.needs_layout(children);
+        if rect == *current && !stale {
+            return false;
+        }
+        if stale {
+            self.invalidate();
+        }
+        *current = rect;
+        true
+    }
+
+    // 把样式表传给子组件，内边距、间距可能变化，需要重新布局
+    pub fn set_stylesheet(&mut self, sheet: &Stylesheet, children: &mut [Box<dyn Widget>]) {
+        for child in children {
+            child.set_stylesheet(sheet);
+        }
+        self.invalidate();
+    }
+
+    // 返回缓存的测量结果，没有时调用 compute 计算。
+    // stale 表示子组件树中有变化，之前缓存的结果都已失效
+    pub fn measure(&self, constraints: Constraints, stale: bool, compute: impl FnOnce() -> (f32, f32)) -> (f32, f32) {
+        if stale {
+            self.measured.borrow_mut().clear();
+        }
+        let cached = self
+            .measured
+            .borrow()
+            .iter()
+            .find(|(key, _)| *key == constraints)
+            .map(|&(_, size)| size);
+        if let Some(size) = cached {
+            return size;
+        }
+
+        let size = compute();
+        let mut measured = self.measured.borrow_mut();
+        if measured.len() >= MEASURE_CACHE_SIZE {
+            measured.remove(0);
+        }
+        measured.push((constraints, size));
+        size
+    }
+}
+
+// 组件在样式表中的名字（类型名、id 和 class），以及样式表为它计算出的属性
+pub(crate) struct StyleState {
+    kind: &'static str,
+    id: Option<String>,
+    classes: Vec<String>,
+    // 按 state_index 排列的各状态组合下的属性，没有样式表时为空
+    computed: Vec<StyleProperties>,
+}
+
+impl StyleState {
+    pub fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            id: None,
+            classes: Vec::new(),
+            computed: Vec::new(),
+        }
+    }
+
+    pub fn set_id(&mut self, id: &str) {
+        self.id = Some(id.to_string());
+    }
+
+    pub fn add_class(&mut self, class: &str) {
+        if !self.classes.iter().any(|existing| existing == class) {
+            self.classes.push(class.to_string());
+        }
+    }
+
+    // 样式表变化时预先计算悬停、按下、焦点、禁用的所有 16 种组合，绘制时直接查表
+    pub fn apply(&mut self, sheet: &Stylesheet) {
+        self.computed = (0..16)
+            .map(|index| {
+                let state = WidgetState {
+                    hovered: index & 1 != 0,
+                    pressed: index & 2 != 0,
+                    focused: index & 4 != 0,
+                    disabled: index & 8 != 0,
+                };
+                sheet.resolve(self.kind, self.id.as_deref(), &self.classes, state)
+            })
+            .collect();
+    }
+
+    pub fn resolve(&self, state: WidgetState) -> StyleProperties {
+        let index = state.hovered as usize
+            | (state.pressed as usize) << 1
+            | (state.focused as usize) << 2
+            | (state.disabled as usize) << 3;
+        self.computed.get(index).copied().unwrap_or_default()
+    }
+
+    // 普通状态下的属性，用于内边距、间距等不随状态变化的布局参数
+    pub fn normal(&self) -> StyleProperties {
+        self.resolve(WidgetState::default())
+    }
+}
 
 pub trait Widget {
     fn draw(&self, renderer: &mut Renderer);
@@ -15,4 +189,77 @@ pub trait Widget {
     fn preferred_size(&self) -> (f32, f32) {
         (0.0, 0.0)  // 默认实现
     }
+
+    // 给定宽度下的首选高度，自动换行的文本等高度随宽度变化的组件需要重写
+    fn preferred_height(&self, _width: f32) -> f32 {
+        self.preferred_size().1
+    }
+
+    // 布局的第一步：在约束范围内测量组件的尺寸。
+    // 默认先按首选宽度确定宽度，再按该宽度计算高度
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        measure_preferred(self, constraints)
+    }
+
+    // 布局的第二步：把组件放到父组件分配的位置上，容器在这里排列子组件
+    fn arrange(&mut self, rect: Rect) {
+        self.set_rect(rect);
+    }
+
+    // 组件自身或子组件的尺寸发生了变化，需要重新布局。
+    // 容器汇总子组件的状态，窗口在绘制前重新排列需要布局的组件树
+    fn needs_layout(&self) -> bool {
+        false
+    }
+
+    // 父组件或窗口通知布局方向，容器把它传给没有单独设置方向的子组件，
+    // 有方向的组件（菜单、下拉框、图标等）在从右到左时镜像显示
+    fn set_layout_direction(&mut self, _direction: LayoutDirection) {}
+
+    // 窗口安装或切换主题时调用，颜色在绘制时从 Renderer 读取，
+    // 这里只处理影响布局的部分（例如字号），容器把它传给子组件
+    fn set_theme(&mut self, _theme: &Theme) {}
+
+    // 窗口安装或重新加载样式表时调用，组件按自己的类型名、id 和 class 计算样式，
+    // 容器把它传给子组件，并在内边距、间距变化后重新布局
+    fn set_stylesheet(&mut self, _sheet: &Stylesheet) {}
+
+    // 弹出层（菜单、下拉列表等）在所有组件绘制完成后绘制，保证位于最上层
+    fn draw_overlay(&self, _renderer: &mut Renderer) {}
+
+    // 弹出层优先于普通事件处理，返回 true 表示事件已被消费，不再分发给其他组件
+    fn handle_overlay_event(&mut self, _event: &WindowEvent) -> bool {
+        false
+    }
+
+    // 组件需要在某个时间点重绘时返回该时间（例如提示框的悬停延时），
+    // 窗口会在此之前一直休眠
+    fn next_wakeup(&self) -> Option<Instant> {
+        None
+    }
+
+    // 光标悬停在组件上时希望显示的鼠标指针，None 表示使用默认指针
+    fn cursor(&self) -> Option<CursorIcon> {
+        None
+    }
+}
+
+// Widget::measure 的默认实现，容器在缓存未命中时也用它计算
+pub(crate) fn measure_preferred<W: Widget + ?Sized>(widget: &W, constraints: Constraints) -> (f32, f32) {
+    let width = constraints.clamp_width(widget.preferred_size().0);
+    (width, constraints.clamp_height(widget.preferred_height(width)))
+}
+
+// 光标移动事件被弹出层消费后，向下层组件发送一个位于窗口外的光标位置，
+// 以清除它们的悬停状态
+#[allow(deprecated)]
+pub(crate) fn cursor_outside<'a>(event: &WindowEvent<'a>) -> Option<WindowEvent<'a>> {
+    match event {
+        WindowEvent::CursorMoved { device_id, modifiers, .. } => Some(WindowEvent::CursorMoved {
+            device_id: *device_id,
+            position: PhysicalPosition::new(f64::MIN, f64::MIN),
+            modifiers: *modifiers,
+        }),
+        _ => None,
+    }
 }
\ No newline at end of file
@@ -0,0 +1,359 @@
+use std::sync::Arc;
+use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::window::CursorIcon;
+use super::{StyleState, Widget};
+use crate::color::Color;
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::font::Font;
+use crate::layout::{Rect, Alignment};
+use crate::text::{break_lines, parse_markup, TextSpan};
+use crate::ui::style::Stylesheet;
+
+type LinkCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+// 一行中属于同一个片段的部分，start..end 为合并后文本中的字节范围
+struct Fragment {
+    span: usize,
+    start: usize,
+    end: usize,
+    x: f32,
+    width: f32,
+}
+
+struct RichLine {
+    y: f32,
+    height: f32,
+    // 基线到行顶部的距离，取行内各片段字体的最大值
+    ascent: f32,
+    width: f32,
+    fragments: Vec<Fragment>,
+}
+
+// 多种样式混排的文本，片段之间可以在行内任意位置换行
+pub struct RichText {
+    rect: Rect,
+    spans: Vec<TextSpan>,
+    // 所有片段合并后的文本，以及每个片段在其中的起始位置
+    plain: String,
+    offsets: Vec<usize>,
+    // 每个片段按样式派生的字体
+    fonts: Vec<Font>,
+    font: Font,
+    // 通过 with_font 指定字体后不再使用主题的字号
+    custom_font: bool,
+    // None 表示使用主题的文本颜色
+    color: Option<Color>,
+    wrap: bool,
+    align: Alignment,
+    lines: Vec<RichLine>,
+    hovered_link: Option<usize>,
+    pressed_link: Option<usize>,
+    on_link: Option<LinkCallback>,
+    // 片段变化后尺寸可能改变，需要父容器重新布局
+    dirty: bool,
+    style: StyleState,
+}
+
+impl RichText {
+    pub fn new(x: f32, y: f32, spans: Vec<TextSpan>) -> Self {
+        let mut text = Self {
+            rect: Rect { x, y, width: 0.0, height: 0.0 },
+            spans,
+            plain: String::new(),
+            offsets: Vec::new(),
+            fonts: Vec::new(),
+            font: Font::default(),
+            custom_font: false,
+            color: None,
+            wrap: false,
+            align: Alignment::Start,
+            lines: Vec::new(),
+            hovered_link: None,
+            pressed_link: None,
+            on_link: None,
+            dirty: false,
+            style: StyleState::new("RichText"),
+        };
+        text.rebuild();
+        text
+    }
+
+    // 从标记文本创建，语法见 parse_markup
+    pub fn from_markup(x: f32, y: f32, markup: &str) -> Self {
+        Self::new(x, y, parse_markup(markup))
+    }
+
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self.custom_font = true;
+        self.rebuild();
+        self
+    }
+
+    // 没有指定颜色的片段使用的颜色
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    // 样式表中用 #id 选择这个富文本
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.style.set_id(id);
+        self
+    }
+
+    // 样式表中用 .class 选择这个富文本，可以多次调用添加多个 class
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.style.add_class(class);
+        self
+    }
+
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self.relayout();
+        self
+    }
+
+    pub fn with_align(mut self, align: Alignment) -> Self {
+        self.align = align;
+        self.relayout();
+        self
+    }
+
+    // 点击链接时以链接地址调用
+    pub fn on_link<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        self.on_link = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn set_spans(&mut self, spans: Vec<TextSpan>) {
+        self.spans = spans;
+        self.hovered_link = None;
+        self.pressed_link = None;
+        self.dirty = true;
+        self.rebuild();
+    }
+
+    // 重新合并文本、派生字体，并按内容调整尺寸
+    fn rebuild(&mut self) {
+        self.plain.clear();
+        self.offsets.clear();
+        for span in &self.spans {
+            self.offsets.push(self.plain.len());
+            self.plain.push_str(&span.text);
+        }
+
+        self.fonts = self
+            .spans
+            .iter()
+            .map(|span| {
+                let font = self.font.clone().with_bold(span.style.bold).with_italic(span.style.italic);
+                match span.style.size {
+                    Some(size) => font.with_size(size),
+                    None => font,
+                }
+            })
+            .collect();
+
+        let (width, height) = self.text_size(None);
+        self.rect.width = width;
+        self.rect.height = height;
+        self.relayout();
+    }
+
+    fn relayout(&mut self) {
+        let max_width = self.wrap.then_some(self.rect.width);
+        self.lines = self.layout(max_width);
+    }
+
+    fn text_size(&self, max_width: Option<f32>) -> (f32, f32) {
+        let lines = self.layout(max_width);
+        let width = lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        let height = lines.last().map(|line| line.y + line.height).unwrap_or(0.0);
+        (width, height)
+    }
+
+    // 合并文本中 start..end 范围与各片段的交集
+    fn pieces(&self, start: usize, end: usize) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.offsets.iter().enumerate().filter_map(move |(index, &offset)| {
+            let span_end = offset + self.spans[index].text.len();
+            let from = start.max(offset);
+            let to = end.min(span_end);
+            (from < to).then_some((index, from, to))
+        })
+    }
+
+    fn width_of(&self, start: usize, end: usize) -> f32 {
+        self.pieces(start, end)
+            .map(|(index, from, to)| self.fonts[index].text_width(&self.plain[from..to]) as f32)
+            .sum()
+    }
+
+    fn layout(&self, max_width: Option<f32>) -> Vec<RichLine> {
+        let measure = |start: usize, end: usize| self.width_of(start, end);
+        let ranges = break_lines(&self.plain, max_width, &measure);
+
+        let mut lines = Vec::with_capacity(ranges.len());
+        let mut y = 0.0;
+        for (start, end) in ranges {
+            let end = start + self.plain[start..end].trim_end().len();
+            let mut x = 0.0;
+            let mut ascent = self.font.ascent() as f32;
+            let mut descent = self.font.descent() as f32;
+            let mut fragments = Vec::new();
+            for (span, from, to) in self.pieces(start, end) {
+                let font = &self.fonts[span];
+                let width = font.text_width(&self.plain[from..to]) as f32;
+                ascent = ascent.max(font.ascent() as f32);
+                descent = descent.max(font.descent() as f32);
+                fragments.push(Fragment { span, start: from, end: to, x, width });
+                x += width;
+            }
+
+            lines.push(RichLine {
+                y,
+                height: ascent + descent,
+                ascent,
+                width: x,
+                fragments,
+            });
+            y += ascent + descent;
+        }
+
+        let align_width = max_width.unwrap_or_else(|| lines.iter().map(|line| line.width).fold(0.0, f32::max));
+        for line in &mut lines {
+            let offset = match self.align {
+                Alignment::Start => 0.0,
+                Alignment::Center => ((align_width - line.width) / 2.0).max(0.0),
+                Alignment::End => (align_width - line.width).max(0.0),
+            };
+            for fragment in &mut line.fragments {
+                fragment.x += offset;
+            }
+        }
+        lines
+    }
+
+    // 光标下的链接片段
+    fn link_at(&self, x: f32, y: f32) -> Option<usize> {
+        let (x, y) = (x - self.rect.x, y - self.rect.y);
+        let line = self.lines.iter().find(|line| y >= line.y && y < line.y + line.height)?;
+        line.fragments
+            .iter()
+            .find(|fragment| x >= fragment.x && x < fragment.x + fragment.width)
+            .map(|fragment| fragment.span)
+            .filter(|&span| self.spans[span].style.link.is_some())
+    }
+}
+
+impl Widget for RichText {
+    fn draw(&self, renderer: &mut Renderer) {
+        let palette = renderer.theme().palette;
+        // 样式表中的颜色优先于 with_color 设置的颜色，片段自身的颜色不受影响
+        let text_color = self.style.normal().color.or(self.color).unwrap_or(palette.text);
+        for line in &self.lines {
+            let top = self.rect.y + line.y;
+            let baseline = top + line.ascent;
+            for fragment in &line.fragments {
+                let style = &self.spans[fragment.span].style;
+                let font = &self.fonts[fragment.span];
+                let x = self.rect.x + fragment.x;
+
+                if let Some(background) = style.background {
+                    renderer.blend_rect(x as i32, top as i32, fragment.width.ceil() as u32, line.height as u32, background);
+                }
+
+                let is_link = style.link.is_some();
+                let color = match style.color {
+                    _ if is_link && self.hovered_link == Some(fragment.span) => palette.link_hover,
+                    Some(color) => color,
+                    None if is_link => palette.link,
+                    None => text_color,
+                };
+                // 不同字号的片段按基线对齐
+                let text_y = baseline - font.ascent() as f32;
+                font.draw_text(renderer, x as i32, text_y as i32, &self.plain[fragment.start..fragment.end], color);
+
+                // 线的粗细随字号变化
+                let thickness = (font.size() / 14.0).round().max(1.0) as u32;
+                if style.underline || is_link {
+                    renderer.draw_rect(x as i32, baseline as i32 + 1, fragment.width as u32, thickness, color);
+                }
+                if style.strikethrough {
+                    let strike_y = baseline - font.ascent() as f32 * 0.35;
+                    renderer.draw_rect(x as i32, strike_y as i32, fragment.width as u32, thickness, color);
+                }
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.hovered_link = self.link_at(position.x as f32, position.y as f32);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.pressed_link = self.hovered_link;
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            } => {
+                // 在同一个链接上按下并松开才算点击
+                if let Some(span) = self.pressed_link.take().filter(|&span| self.hovered_link == Some(span)) {
+                    if let (Some(callback), Some(url)) = (&self.on_link, &self.spans[span].style.link) {
+                        callback(url);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.rect = rect;
+        self.dirty = false;
+        self.relayout();
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.text_size(None)
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        self.text_size(self.wrap.then_some(width)).1
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        self.hovered_link.map(|_| CursorIcon::Hand)
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        if !self.custom_font {
+            self.font = theme.body_font();
+            self.dirty = true;
+            self.rebuild();
+        }
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.style.apply(sheet);
+    }
+}
@@ -0,0 +1,501 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::event::{ElementState, MouseButton, WindowEvent};
+use winit::window::CursorIcon;
+use super::{children, measure_preferred, LayoutState, Widget};
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{resolve_flex, Constraints, Direction, FlexLine, LayoutDirection, Rect, SplitPane};
+
+const DIVIDER_SIZE: f32 = 6.0;
+// 两次按下分隔条的间隔小于这个时间视为双击
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+type ResizeCallback = Arc<dyn Fn(&[f32]) + Send + Sync>;
+
+// 可拖动调整大小的分割容器，窗格沿 direction 排列，相邻窗格之间是分隔条。
+// 拖动分隔条时捕获鼠标，光标移出分隔条也继续调整，松开后通知新的比例并保存到状态文件
+pub struct Splitter {
+    rect: Rect,
+    direction: Direction,
+    children: Vec<Box<dyn Widget>>,
+    // 与 children 一一对应的窗格参数
+    panes: Vec<SplitPane>,
+    // 各窗格展开时所占的比例，总和为 1，折叠的窗格保留折叠前的比例
+    ratios: Vec<f32>,
+    collapsed: Vec<bool>,
+    // 上次布局时各窗格在主轴上的尺寸
+    sizes: Vec<f32>,
+    cursor: (f32, f32),
+    hovered: Option<usize>,
+    // 正在拖动的分隔条，以及按下时光标到分隔条起始边的距离
+    dragging: Option<(usize, f32)>,
+    // 这次拖动是否改变过窗格尺寸，松开时只有改变过才通知
+    drag_moved: bool,
+    last_press: Option<(usize, Instant)>,
+    state_file: Option<PathBuf>,
+    // 从状态文件读取的比例和折叠状态，窗格数量一致时在布局时应用
+    saved: Option<(Vec<f32>, Vec<bool>)>,
+    on_resize: Option<ResizeCallback>,
+    layout_state: LayoutState,
+}
+
+impl Splitter {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            rect: Rect { x, y, width, height },
+            direction: Direction::Horizontal,
+            children: Vec::new(),
+            panes: Vec::new(),
+            ratios: Vec::new(),
+            collapsed: Vec::new(),
+            sizes: Vec::new(),
+            cursor: (0.0, 0.0),
+            hovered: None,
+            dragging: None,
+            drag_moved: false,
+            last_press: None,
+            state_file: None,
+            saved: None,
+            on_resize: None,
+            layout_state: LayoutState::default(),
+        }
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 启动时从文件恢复上次的比例和折叠状态，之后每次调整后写回。
+    // 文件不存在或窗格数量不一致时使用窗格自身的比例
+    pub fn with_state_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        self.saved = load_state(&path).ok();
+        self.state_file = Some(path);
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 用户拖动分隔条或折叠窗格后调用，参数是各窗格的比例
+    pub fn on_resize<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[f32]) + Send + Sync + 'static,
+    {
+        self.on_resize = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn add_child<W: Widget + 'static>(&mut self, widget: W, pane: SplitPane) {
+        self.add_boxed(Box::new(widget), pane);
+    }
+
+    pub(crate) fn add_boxed(&mut self, mut widget: Box<dyn Widget>, pane: SplitPane) {
+        widget.set_layout_direction(self.layout_state.direction());
+        self.children.push(widget);
+        self.panes.push(pane);
+        self.collapsed.push(false);
+        self.ratios = normalize(&self.panes.iter().map(|pane| pane.ratio).collect::<Vec<_>>());
+        self.layout_state.invalidate();
+    }
+
+    pub fn ratios(&self) -> &[f32] {
+        &self.ratios
+    }
+
+    // 数量与窗格数量不一致时忽略
+    pub fn set_ratios(&mut self, ratios: &[f32]) {
+        if ratios.len() == self.ratios.len() {
+            self.ratios = normalize(ratios);
+            self.layout_state.invalidate();
+        }
+    }
+
+    pub fn is_collapsed(&self, index: usize) -> bool {
+        self.collapsed.get(index).copied().unwrap_or(false)
+    }
+
+    pub fn set_collapsed(&mut self, index: usize, collapsed: bool) {
+        if let Some(slot) = self.collapsed.get_mut(index) {
+            *slot = collapsed;
+            self.layout_state.invalidate();
+        }
+    }
+
+    pub fn save_state(&self, path: &Path) -> io::Result<()> {
+        let ratios: Vec<String> = self.ratios.iter().map(|ratio| format!("{:.4}", ratio)).collect();
+        let collapsed: Vec<String> = (0..self.collapsed.len())
+            .filter(|&index| self.collapsed[index])
+            .map(|index| index.to_string())
+            .collect();
+        fs::write(path, format!("ratios: {}\ncollapsed: {}\n", ratios.join(" "), collapsed.join(" ")))
+    }
+
+    // 主轴上的总长度
+    fn main_length(&self) -> f32 {
+        match self.direction {
+            Direction::Horizontal => self.rect.width,
+            Direction::Vertical => self.rect.height,
+        }
+    }
+
+    // 去掉分隔条后可分配给窗格的长度
+    fn available(&self) -> f32 {
+        (self.main_length() - DIVIDER_SIZE * self.children.len().saturating_sub(1) as f32).max(0.0)
+    }
+
+    // 光标到起始边的距离，从右到左时水平方向从右边算起
+    fn main_offset(&self, x: f32, y: f32) -> f32 {
+        match self.direction {
+            Direction::Horizontal if self.layout_state.direction().is_rtl() => self.rect.x + self.rect.width - x,
+            Direction::Horizontal => x - self.rect.x,
+            Direction::Vertical => y - self.rect.y,
+        }
+    }
+
+    // 主轴上从 start 开始、长为 length 的区域
+    fn span_rect(&self, start: f32, length: f32) -> Rect {
+        let rect = match self.direction {
+            Direction::Horizontal => Rect { x: self.rect.x + start, width: length, ..self.rect },
+            Direction::Vertical => Rect { y: self.rect.y + start, height: length, ..self.rect },
+        };
+        match self.direction {
+            Direction::Horizontal if self.layout_state.direction().is_rtl() => rect.mirror(self.rect),
+            _ => rect,
+        }
+    }
+
+    // 第 index 个分隔条（第 index 和 index + 1 个窗格之间）到起始边的距离
+    fn divider_start(&self, index: usize) -> f32 {
+        self.sizes[..=index].iter().sum::<f32>() + DIVIDER_SIZE * index as f32
+    }
+
+    fn divider_at(&self, x: f32, y: f32) -> Option<usize> {
+        if self.sizes.len() != self.children.len() {
+            return None;
+        }
+        (0..self.children.len().saturating_sub(1))
+            .find(|&index| self.span_rect(self.divider_start(index), DIVIDER_SIZE).contains(x, y))
+    }
+
+    fn apply_saved(&mut self) {
+        if let Some((ratios, collapsed)) = self.saved.take() {
+            if ratios.len() == self.children.len() {
+                self.ratios = normalize(&ratios);
+                self.collapsed = collapsed;
+            } else {
+                self.saved = Some((ratios, collapsed));
+            }
+        }
+    }
+
+    fn layout(&mut self) {
+        self.apply_saved();
+        // 按比例分配可用长度，违反最小、最大尺寸的窗格固定在边界上，其余窗格按比例分担差额
+        let available = self.available();
+        let expanded: f32 = (0..self.ratios.len())
+            .filter(|&index| !self.collapsed[index])
+            .map(|index| self.ratios[index])
+            .sum();
+        let lines: Vec<FlexLine> = self
+            .panes
+            .iter()
+            .enumerate()
+            .map(|(index, pane)| {
+                if self.collapsed[index] || expanded <= 0.0 {
+                    return FlexLine { basis: 0.0, grow: 0.0, shrink: 0.0, min: 0.0, max: 0.0 };
+                }
+                let ratio = self.ratios[index] / expanded;
+                FlexLine {
+                    basis: available * ratio,
+                    grow: ratio,
+                    shrink: 1.0,
+                    min: pane.min_size,
+                    max: pane.max_size,
+                }
+            })
+            .collect();
+        self.sizes = resolve_flex(available, &lines);
+
+        let mut start = 0.0;
+        for index in 0..self.children.len() {
+            let rect = self.span_rect(start, self.sizes[index]);
+            self.children[index].arrange(rect);
+            start += self.sizes[index] + DIVIDER_SIZE;
+        }
+    }
+
+    // 拖动时只改变分隔条两侧的窗格，两者的尺寸之和不变。返回尺寸是否改变
+    fn drag_to(&mut self, index: usize, grab: f32) -> bool {
+        let pane_start = self.divider_start(index) - self.sizes[index];
+        let pair = self.sizes[index] + self.sizes[index + 1];
+        let (first, second) = (&self.panes[index], &self.panes[index + 1]);
+        let low = first.min_size.max(pair - second.max_size);
+        let high = first.max_size.min(pair - second.min_size);
+        if low > high {
+            return false;
+        }
+        let size = (self.main_offset(self.cursor.0, self.cursor.1) - grab - pane_start).clamp(low, high);
+        if size == self.sizes[index] {
+            return false;
+        }
+        self.sizes[index] = size;
+        self.sizes[index + 1] = pair - size;
+
+        // 展开的窗格按新的尺寸分配展开比例之和，折叠的窗格保留原来的比例
+        let share: f32 = (0..self.ratios.len())
+            .filter(|&index| !self.collapsed[index])
+            .map(|index| self.ratios[index])
+            .sum();
+        let total: f32 = (0..self.sizes.len())
+            .filter(|&index| !self.collapsed[index])
+            .map(|index| self.sizes[index])
+            .sum();
+        if total > 0.0 {
+            for pane in (0..self.ratios.len()).filter(|&pane| !self.collapsed[pane]) {
+                self.ratios[pane] = self.sizes[pane] / total * share;
+            }
+        }
+        self.layout_state.invalidate();
+        true
+    }
+
+    // 双击分隔条时展开相邻的折叠窗格，没有折叠的窗格时折叠前一个（不可折叠时是后一个）
+    fn toggle_collapse(&mut self, index: usize) {
+        let neighbors = [index, index + 1];
+        let target = neighbors
+            .iter()
+            .find(|&&pane| self.collapsed[pane])
+            .or_else(|| neighbors.iter().find(|&&pane| self.panes[pane].collapsible));
+        if let Some(&pane) = target {
+            self.collapsed[pane] = !self.collapsed[pane];
+            self.layout_state.invalidate();
+            self.changed();
+        }
+    }
+
+    fn press_divider(&mut self, index: usize) {
+        let now = Instant::now();
+        if let Some((last, time)) = self.last_press {
+            if last == index && now.duration_since(time) <= DOUBLE_CLICK_TIME {
+                self.last_press = None;
+                self.toggle_collapse(index);
+                return;
+            }
+        }
+        self.last_press = Some((index, now));
+        // 与折叠的窗格相邻的分隔条只能双击展开，不能拖动
+        if !self.collapsed[index] && !self.collapsed[index + 1] {
+            let grab = self.main_offset(self.cursor.0, self.cursor.1) - self.divider_start(index);
+            self.dragging = Some((index, grab));
+            self.drag_moved = false;
+        }
+    }
+
+    // 比例或折叠状态被用户改变
+    fn changed(&self) {
+        if let Some(callback) = &self.on_resize {
+            callback(&self.ratios);
+        }
+        if let Some(path) = &self.state_file {
+            if let Err(e) = self.save_state(path) {
+                println!("Failed to save splitter state {:?}: {:?}", path, e);
+            }
+        }
+    }
+
+    // 没有折叠的子组件
+    fn visible_children(&self) -> impl DoubleEndedIterator<Item = &Box<dyn Widget>> {
+        self.children.iter().zip(&self.collapsed).filter(|(_, &collapsed)| !collapsed).map(|(child, _)| child)
+    }
+
+    fn visible_children_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Box<dyn Widget>> {
+        self.children.iter_mut().zip(&self.collapsed).filter(|(_, &collapsed)| !collapsed).map(|(child, _)| child)
+    }
+}
+
+// 比例之和为 0 时平均分配
+fn normalize(ratios: &[f32]) -> Vec<f32> {
+    let sum: f32 = ratios.iter().map(|ratio| ratio.max(0.0)).sum();
+    if sum <= 0.0 {
+        return vec![1.0 / ratios.len() as f32; ratios.len()];
+    }
+    ratios.iter().map(|ratio| ratio.max(0.0) / sum).collect()
+}
+
+// 状态文件：
+//   ratios: 0.2500 0.7500
+//   collapsed: 0
+fn load_state(path: &Path) -> io::Result<(Vec<f32>, Vec<bool>)> {
+    let content = fs::read_to_string(path)?;
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid splitter state");
+    let mut ratios = Vec::new();
+    let mut collapsed_indices = Vec::new();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let values = value.split_whitespace();
+        match key.trim() {
+            "ratios" => ratios = values.map(|ratio| ratio.parse::<f32>().map_err(|_| invalid())).collect::<Result<_, _>>()?,
+            "collapsed" => {
+                collapsed_indices = values.map(|index| index.parse::<usize>().map_err(|_| invalid())).collect::<Result<_, _>>()?
+            }
+            _ => {}
+        }
+    }
+    let collapsed = (0..ratios.len()).map(|index| collapsed_indices.contains(&index)).collect();
+    Ok((ratios, collapsed))
+}
+
+impl Widget for Splitter {
+    fn draw(&self, renderer: &mut Renderer) {
+        children::draw(self.visible_children(), renderer);
+        if self.sizes.len() != self.children.len() {
+            return;
+        }
+        let palette = renderer.theme().palette;
+        let active = self.dragging.map(|(index, _)| index).or(self.hovered);
+        for index in 0..self.children.len().saturating_sub(1) {
+            let rect = self.span_rect(self.divider_start(index), DIVIDER_SIZE);
+            let color = if active == Some(index) { palette.accent } else { palette.surface };
+            renderer.draw_rect(rect.x as i32, rect.y as i32, rect.width as u32, rect.height as u32, color);
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        children::handle_event(self.visible_children_mut(), event);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        children::draw_overlay(self.visible_children(), renderer);
+    }
+
+    // 拖动分隔条时捕获鼠标事件，其他组件收不到光标移动和松开按键
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            self.cursor = (position.x as f32, position.y as f32);
+        }
+
+        if let Some((index, grab)) = self.dragging {
+            match event {
+                WindowEvent::CursorMoved { .. } => {
+                    if self.drag_to(index, grab) {
+                        self.drag_moved = true;
+                    }
+                    return true;
+                }
+                WindowEvent::MouseInput {
+                    state: ElementState::Released,
+                    button: MouseButton::Left,
+                    ..
+                } => {
+                    self.dragging = None;
+                    if self.drag_moved {
+                        self.changed();
+                    }
+                    return true;
+                }
+                WindowEvent::MouseInput { .. } => return true,
+                _ => {}
+            }
+        }
+
+        if children::handle_overlay_event(self.visible_children_mut(), event) {
+            return true;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { .. } => {
+                self.hovered = self.divider_at(self.cursor.0, self.cursor.1);
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => match self.divider_at(self.cursor.0, self.cursor.1) {
+                Some(index) => {
+                    self.press_divider(index);
+                    true
+                }
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        children::next_wakeup(self.visible_children())
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        if self.dragging.is_some() || self.hovered.is_some() {
+            return Some(match self.direction {
+                Direction::Horizontal => CursorIcon::ColResize,
+                Direction::Vertical => CursorIcon::RowResize,
+            });
+        }
+        children::cursor(self.visible_children())
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        if self.layout_state.update_rect(&mut self.rect, rect, &self.children) {
+            self.layout();
+            self.layout_state.clean();
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.layout_state.measure(constraints, self.needs_layout(), || measure_preferred(self, constraints))
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.layout_state.needs_layout(&self.children)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_state.inherit_direction(direction, &mut self.children);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        children::set_theme(&mut self.children, theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.layout_state.set_stylesheet(sheet, &mut self.children);
+    }
+
+    // 窗格按首选尺寸并排，不小于最小尺寸
+    fn preferred_size(&self) -> (f32, f32) {
+        let dividers = DIVIDER_SIZE * self.children.len().saturating_sub(1) as f32;
+        let sizes = self
+            .children
+            .iter()
+            .zip(&self.panes)
+            .zip(&self.collapsed)
+            .filter(|(_, &collapsed)| !collapsed)
+            .map(|((child, pane), _)| (child.preferred_size(), pane));
+        match self.direction {
+            Direction::Horizontal => {
+                let (width, height) = sizes.fold((0.0, 0.0f32), |(width, height), (size, pane)| {
+                    (width + pane.clamp(size.0), height.max(size.1))
+                });
+                (width + dividers, height)
+            }
+            Direction::Vertical => {
+                let (width, height) = sizes.fold((0.0f32, 0.0), |(width, height), (size, pane)| {
+                    (width.max(size.0), height + pane.clamp(size.1))
+                });
+                (width, height + dividers)
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::time::Instant;
+use winit::event::WindowEvent;
+use winit::window::CursorIcon;
+use super::{children, cursor_outside, measure_preferred, LayoutState, Widget};
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{Constraints, LayoutDirection, Padding, Rect, StackItem};
+
+// 层叠容器：子组件重叠在同一区域内，按锚点和偏移定位，
+// 适合图标上的角标、悬浮按钮和 HUD 叠加层
+pub struct Stack {
+    rect: Rect,
+    padding: Padding,
+    children: Vec<Box<dyn Widget>>,
+    // 与 children 一一对应的位置参数
+    items: Vec<StackItem>,
+    // 按 z_index 从下到上排列的子组件下标
+    order: Vec<usize>,
+    layout_state: LayoutState,
+}
+
+impl Stack {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            rect: Rect { x, y, width, height },
+            padding: Padding::all(0.0),
+            children: Vec::new(),
+            items: Vec::new(),
+            order: Vec::new(),
+            layout_state: LayoutState::default(),
+        }
+    }
+
+    pub fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self.layout_state.invalidate();
+        self
+    }
+
+    // 从右到左时左侧的锚点和水平偏移都换到右侧
+    pub fn with_layout_direction(mut self, direction: LayoutDirection) -> Self {
+        self.layout_state.set_direction(direction, &mut self.children);
+        self
+    }
+
+    pub fn add_child<W: Widget + 'static>(&mut self, widget: W, item: StackItem) {
+        self.add_boxed(Box::new(widget), item);
+    }
+
+    pub(crate) fn add_boxed(&mut self, mut widget: Box<dyn Widget>, item: StackItem) {
+        widget.set_layout_direction(self.layout_state.direction());
+        self.children.push(widget);
+        self.items.push(item);
+        self.sort();
+        self.layout_state.invalidate();
+    }
+
+    pub fn set_z_index(&mut self, index: usize, z_index: i32) {
+        if let Some(item) = self.items.get_mut(index) {
+            item.z_index = z_index;
+            self.sort();
+        }
+    }
+
+    // 稳定排序，z_index 相同时保持添加顺序
+    fn sort(&mut self) {
+        self.order = (0..self.children.len()).collect();
+        self.order.sort_by_key(|&index| self.items[index].z_index);
+    }
+
+    fn content_rect(&self) -> Rect {
+        Rect {
+            x: self.rect.x + self.padding.left,
+            y: self.rect.y + self.padding.top,
+            width: self.rect.width - (self.padding.left + self.padding.right),
+            height: self.rect.height - (self.padding.top + self.padding.bottom),
+        }
+    }
+
+    fn layout(&mut self) {
+        let bounds = self.content_rect();
+        let rtl = self.layout_state.direction().is_rtl();
+        for (child, item) in self.children.iter_mut().zip(&self.items) {
+            let rect = item.place(bounds, child.measure(Constraints::unbounded()));
+            child.arrange(if rtl { rect.mirror(self.rect) } else { rect });
+        }
+    }
+
+    // 光标下最上层的子组件
+    fn hit(&self, x: f32, y: f32) -> Option<usize> {
+        self.order
+            .iter()
+            .rev()
+            .copied()
+            .find(|&index| self.children[index].get_rect().contains(x, y))
+    }
+}
+
+impl Widget for Stack {
+    fn draw(&self, renderer: &mut Renderer) {
+        for &index in &self.order {
+            self.children[index].draw(renderer);
+        }
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        // 光标只属于最上层命中的子组件，下层的子组件收到窗口外的位置以清除悬停状态，
+        // 这样点击也只会落在最上层
+        if let WindowEvent::CursorMoved { position, .. } = event {
+            let hit = self.hit(position.x as f32, position.y as f32);
+            let outside = cursor_outside(event);
+            for &index in self.order.iter().rev() {
+                match (&outside, hit == Some(index)) {
+                    (Some(outside), false) => self.children[index].handle_event(outside),
+                    _ => self.children[index].handle_event(event),
+                }
+            }
+            return;
+        }
+        for &index in self.order.iter().rev() {
+            self.children[index].handle_event(event);
+        }
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        for &index in &self.order {
+            self.children[index].draw_overlay(renderer);
+        }
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        let children = &mut self.children;
+        self.order.iter().rev().any(|&index| children[index].handle_overlay_event(event))
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        children::next_wakeup(&self.children)
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        self.order.iter().rev().find_map(|&index| self.children[index].cursor())
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        if self.layout_state.update_rect(&mut self.rect, rect, &self.children) {
+            self.layout();
+            self.layout_state.clean();
+        }
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.layout_state.measure(constraints, self.needs_layout(), || measure_preferred(self, constraints))
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.layout_state.needs_layout(&self.children)
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_state.inherit_direction(direction, &mut self.children);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        children::set_theme(&mut self.children, theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.layout_state.set_stylesheet(sheet, &mut self.children);
+    }
+
+    // 能容纳所有按首选尺寸放置的子组件（含偏移和外边距）的尺寸，百分比尺寸的子组件不参与计算
+    fn preferred_size(&self) -> (f32, f32) {
+        let (width, height) = self
+            .children
+            .iter()
+            .zip(&self.items)
+            .map(|(child, item)| {
+                let (width, height) = child.measure(Constraints::unbounded());
+                (
+                    if item.width_percent.is_some() { 0.0 } else { width + item.offset_x.abs() + item.margin.horizontal() },
+                    if item.height_percent.is_some() { 0.0 } else { height + item.offset_y.abs() + item.margin.vertical() },
+                )
+            })
+            .fold((0.0f32, 0.0f32), |(w, h), (width, height)| (w.max(width), h.max(height)));
+        (
+            width + self.padding.left + self.padding.right,
+            height + self.padding.top + self.padding.bottom,
+        )
+    }
+}
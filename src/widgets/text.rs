@@ -1,50 +1,170 @@
 use winit::event::WindowEvent;
-use super::Widget;
+use super::{StyleState, Widget};
+use crate::color::Color;
 use crate::renderer::Renderer;
+use crate::theme::Theme;
 use crate::font::Font;
-use crate::layout::Rect;
+use crate::layout::{Rect, Alignment};
+use crate::text::{TextDirection, TextLayout, TextOptions};
+use crate::ui::style::Stylesheet;
 
 pub struct Text {
     rect: Rect,
     content: String,
-    color: [u8; 4],
+    // None 表示使用主题的文本颜色
+    color: Option<Color>,
     font: Font,
+    // 通过 with_font 指定字体后不再使用主题的字号
+    custom_font: bool,
+    options: TextOptions,
+    layout: TextLayout,
+    // 内容变化后尺寸可能改变，需要父容器重新布局
+    dirty: bool,
+    style: StyleState,
 }
 
 impl Text {
     pub fn new(x: f32, y: f32, content: &str) -> Self {
+        let font = Font::default();
+        let options = TextOptions::default();
+        let layout = TextLayout::new(&font, content, &options);
         Self {
             rect: Rect {
                 x,
                 y,
-                width: content.len() as f32 * 8.0,  // 假设每个字符宽度为8
-                height: 8.0,  // 字体高度为8
+                width: layout.width(),
+                height: layout.height(),
             },
             content: content.to_string(),
-            color: [255, 255, 255, 255], // 默认白色
-            font: Font::default(),
+            color: None,
+            font,
+            custom_font: false,
+            options,
+            layout,
+            dirty: false,
+            style: StyleState::new("Text"),
         }
     }
 
-    pub fn with_color(mut self, color: [u8; 4]) -> Self {
-        self.color = color;
+    // 更换字体后按新字体重新计算尺寸
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self.custom_font = true;
+        self.fit_content();
         self
-    }    
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    // 样式表中用 #id 选择这个文本
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.style.set_id(id);
+        self
+    }
+
+    // 样式表中用 .class 选择这个文本，可以多次调用添加多个 class
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.style.add_class(class);
+        self
+    }
+
+    // 超出组件宽度时自动换行
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.options.wrap = wrap;
+        self.relayout();
+        self
+    }
+
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.options.max_lines = Some(max_lines);
+        self.fit_content();
+        self
+    }
+
+    // 超出组件宽度或行数限制时以省略号结尾
+    pub fn with_ellipsis(mut self, ellipsis: bool) -> Self {
+        self.options.ellipsis = ellipsis;
+        self.relayout();
+        self
+    }
+
+    pub fn with_align(mut self, align: Alignment) -> Self {
+        self.options.align = align;
+        self.relayout();
+        self
+    }
+
+    pub fn with_line_spacing(mut self, line_spacing: f32) -> Self {
+        self.options.line_spacing = line_spacing;
+        self.fit_content();
+        self
+    }
+
+    // 段落的基本方向，默认由内容决定
+    pub fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.options.direction = direction;
+        self.relayout();
+        self
+    }
+
+    // 自动换行时保持当前宽度，否则尺寸随内容变化
+    pub fn set_content(&mut self, content: &str) {
+        self.content = content.to_string();
+        self.refresh();
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    // 不限制宽度时的排版参数
+    fn natural_options(&self) -> TextOptions {
+        TextOptions {
+            max_width: None,
+            ..self.options
+        }
+    }
+
+    // 内容或字体变化后重新排版，并通知父容器重新布局
+    fn refresh(&mut self) {
+        self.dirty = true;
+        if self.options.wrap {
+            self.relayout();
+        } else {
+            self.fit_content();
+        }
+    }
+
+    // 尺寸随内容变化
+    fn fit_content(&mut self) {
+        let natural = TextLayout::new(&self.font, &self.content, &self.natural_options());
+        self.rect.width = natural.width();
+        self.rect.height = natural.height();
+        self.relayout();
+    }
+
+    // 按组件宽度重新排版
+    fn relayout(&mut self) {
+        let options = TextOptions {
+            max_width: Some(self.rect.width),
+            ..self.options
+        };
+        self.layout = TextLayout::new(&self.font, &self.content, &options);
+    }
 }
 
 impl Widget for Text {
     fn draw(&self, renderer: &mut Renderer) {
-        let mut x_offset = 0;
-        for c in self.content.chars() {
-            self.font.render_char(
-                renderer,
-                self.rect.x as i32 + x_offset,
-                self.rect.y as i32,
-                c,
-                self.color
-            );
-            x_offset += 8;
+        // 样式表中的颜色优先于 with_color 设置的颜色
+        let properties = self.style.normal();
+        if let Some(background) = properties.background {
+            renderer.draw_rect(self.rect.x as i32, self.rect.y as i32, self.rect.width as u32, self.rect.height as u32, background);
         }
+        let color = properties.color.or(self.color).unwrap_or(renderer.theme().palette.text);
+        self.layout.draw(renderer, &self.font, self.rect.x, self.rect.y, color);
     }
 
     fn handle_event(&mut self, _event: &WindowEvent) {
@@ -57,9 +177,35 @@ impl Widget for Text {
 
     fn set_rect(&mut self, rect: Rect) {
         self.rect = rect;
+        self.dirty = false;
+        self.relayout();
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.dirty
     }
 
     fn preferred_size(&self) -> (f32, f32) {
-        (self.content.len() as f32 * 8.0, 8.0)
+        let natural = TextLayout::new(&self.font, &self.content, &self.natural_options());
+        (natural.width(), natural.height())
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        let options = TextOptions {
+            max_width: Some(width),
+            ..self.options
+        };
+        TextLayout::new(&self.font, &self.content, &options).height()
     }
-}
\ No newline at end of file
+
+    fn set_theme(&mut self, theme: &Theme) {
+        if !self.custom_font {
+            self.font = theme.body_font();
+            self.refresh();
+        }
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.style.apply(sheet);
+    }
+}
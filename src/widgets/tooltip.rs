@@ -0,0 +1,182 @@
+use std::time::{Duration, Instant};
+use winit::event::WindowEvent;
+use winit::window::CursorIcon;
+use super::Widget;
+use super::text::Text;
+use crate::renderer::Renderer;
+use crate::theme::Theme;
+use crate::ui::style::Stylesheet;
+use crate::layout::{place_popup, Constraints, LayoutDirection, Placement, Rect};
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(500);
+const BUBBLE_PADDING: f32 = 6.0;
+// 气泡显示在光标右下方，避开光标本身
+const CURSOR_SIZE: f32 = 16.0;
+
+// 为任意组件添加悬停提示：光标在组件上停留一段时间后，在光标附近显示提示气泡，
+// 移动或按下鼠标时隐藏
+pub struct Tooltip {
+    child: Box<dyn Widget>,
+    content: Box<dyn Widget>,
+    delay: Duration,
+    hover_start: Option<Instant>,
+    bubble: Rect,
+    viewport: Rect,
+}
+
+impl Tooltip {
+    pub fn new<W: Widget + 'static>(child: W, text: &str) -> Self {
+        Self {
+            child: Box::new(child),
+            content: Box::new(Text::new(0.0, 0.0, text)),
+            delay: DEFAULT_DELAY,
+            hover_start: None,
+            bubble: Rect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 },
+            viewport: Rect { x: 0.0, y: 0.0, width: f32::MAX, height: f32::MAX },
+        }
+    }
+
+    // 使用任意组件作为提示内容
+    pub fn with_content<W: Widget + 'static>(mut self, content: W) -> Self {
+        self.content = Box::new(content);
+        self
+    }
+
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn is_visible(&self, now: Instant) -> bool {
+        self.hover_start
+            .map(|start| now >= start + self.delay)
+            .unwrap_or(false)
+    }
+
+    // 根据光标位置计算气泡位置，保证气泡位于窗口内
+    fn place_bubble(&mut self, x: f32, y: f32) {
+        let (width, height) = self.content.preferred_size();
+        let anchor = Rect { x, y, width: CURSOR_SIZE, height: CURSOR_SIZE };
+        self.bubble = place_popup(
+            anchor,
+            width + BUBBLE_PADDING * 2.0,
+            height + BUBBLE_PADDING * 2.0,
+            self.viewport,
+            Placement::Below,
+        );
+        self.content.set_rect(Rect {
+            x: self.bubble.x + BUBBLE_PADDING,
+            y: self.bubble.y + BUBBLE_PADDING,
+            width,
+            height,
+        });
+    }
+}
+
+impl Widget for Tooltip {
+    fn draw(&self, renderer: &mut Renderer) {
+        self.child.draw(renderer);
+    }
+
+    fn draw_overlay(&self, renderer: &mut Renderer) {
+        self.child.draw_overlay(renderer);
+
+        if self.is_visible(Instant::now()) {
+            let bubble = self.bubble;
+            let palette = renderer.theme().palette;
+            renderer.draw_rect(bubble.x as i32, bubble.y as i32, bubble.width as u32, bubble.height as u32, palette.outline);
+            renderer.draw_rect(
+                bubble.x as i32 + 1,
+                bubble.y as i32 + 1,
+                bubble.width as u32 - 2,
+                bubble.height as u32 - 2,
+                palette.tooltip,
+            );
+            self.content.draw(renderer);
+        }
+    }
+
+    fn handle_overlay_event(&mut self, event: &WindowEvent) -> bool {
+        self.child.handle_overlay_event(event)
+    }
+
+    fn handle_event(&mut self, event: &WindowEvent) {
+        self.child.handle_event(event);
+
+        match event {
+            WindowEvent::Resized(size) => {
+                self.viewport = Rect { x: 0.0, y: 0.0, width: size.width as f32, height: size.height as f32 };
+                self.hover_start = None;
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let (x, y) = (position.x as f32, position.y as f32);
+                // 每次移动都重新开始计时
+                if self.child.get_rect().contains(x, y) {
+                    self.hover_start = Some(Instant::now());
+                    self.place_bubble(x, y);
+                } else {
+                    self.hover_start = None;
+                }
+            }
+            WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } | WindowEvent::KeyboardInput { .. } => {
+                self.hover_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        let own = self
+            .hover_start
+            .map(|start| start + self.delay)
+            .filter(|&time| time > Instant::now());
+        match (own, self.child.next_wakeup()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn get_rect(&self) -> Rect {
+        self.child.get_rect()
+    }
+
+    fn set_rect(&mut self, rect: Rect) {
+        self.child.set_rect(rect);
+        self.hover_start = None;
+    }
+
+    fn preferred_size(&self) -> (f32, f32) {
+        self.child.preferred_size()
+    }
+
+    fn preferred_height(&self, width: f32) -> f32 {
+        self.child.preferred_height(width)
+    }
+
+    fn measure(&self, constraints: Constraints) -> (f32, f32) {
+        self.child.measure(constraints)
+    }
+
+    fn needs_layout(&self) -> bool {
+        self.child.needs_layout()
+    }
+
+    fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.child.set_layout_direction(direction);
+        self.content.set_layout_direction(direction);
+    }
+
+    fn set_theme(&mut self, theme: &Theme) {
+        self.child.set_theme(theme);
+        self.content.set_theme(theme);
+    }
+
+    fn set_stylesheet(&mut self, sheet: &Stylesheet) {
+        self.child.set_stylesheet(sheet);
+        self.content.set_stylesheet(sheet);
+    }
+
+    fn cursor(&self) -> Option<CursorIcon> {
+        self.child.cursor()
+    }
+}
@@ -1,19 +1,27 @@
 use crate::renderer::Renderer;
-use crate::widgets::Widget;
+use crate::widgets::{Widget, cursor_outside};
+use crate::overlay::{Overlay, OverlayCommand, OverlayHandle, OverlayResponse};
+use crate::layout::{LayoutDirection, Rect};
+use crate::theme::{Theme, ThemeHandle};
 use crate::Container;
+use std::sync::{Arc, Mutex};
 use std::vec::Vec;
 use winit::{
-    event::{Event, WindowEvent},
-    event_loop::{ControlFlow, EventLoop, EventLoopProxy},
-    window::{Window as WinitWindow, WindowBuilder},
+    event::{Event, StartCause, WindowEvent},
+    event_loop::{ControlFlow, EventLoop, EventLoopBuilder, EventLoopProxy},
+    window::{CursorIcon, Window as WinitWindow, WindowBuilder},
 };
 use crate::ui::parser::parse_ui;
+use crate::ui::style::{parse_style, Stylesheet};
 use crate::debug_log;
 
 
 #[derive(Debug, Clone)]
 pub enum CustomEvent {
     Reload(String),  // 改为传递 UI 文件内容而不是 Container
+    OverlayChanged,  // 叠加层命令队列有新命令
+    SetTheme(Arc<Theme>),  // 运行时切换主题
+    ReloadStyle(String),  // 样式表文件内容
 }
 
 pub struct Window {
@@ -21,12 +29,18 @@ pub struct Window {
     window: WinitWindow,
     renderer: Renderer,
     widgets: Vec<Box<dyn Widget>>,
+    // 与 widgets 一一对应，是否铺满窗口并随窗口缩放
+    roots: Vec<bool>,
+    // 整个窗口的布局方向，设置了方向的容器在自己的子树中覆盖它
+    layout_direction: LayoutDirection,
     event_proxy: EventLoopProxy<CustomEvent>,
+    overlay_commands: Arc<Mutex<Vec<OverlayCommand>>>,
+    stylesheet: Stylesheet,
 }
 
 impl Window {
     pub fn new(title: &str, width: u32, height: u32) -> Self {
-        let event_loop = EventLoop::<CustomEvent>::with_user_event();
+        let event_loop = EventLoopBuilder::<CustomEvent>::with_user_event().build();
         let event_proxy = event_loop.create_proxy();
 
         let window = WindowBuilder::new()
@@ -42,7 +56,11 @@ impl Window {
             window,
             renderer,
             widgets: Vec::new(),
+            roots: Vec::new(),
+            layout_direction: LayoutDirection::Ltr,
             event_proxy,
+            overlay_commands: Arc::new(Mutex::new(Vec::new())),
+            stylesheet: Stylesheet::new(),
         }
     }
 
@@ -50,27 +68,82 @@ impl Window {
         self.event_proxy.clone()
     }
 
+    // 用于打开对话框和弹出层，可以在组件回调中使用
+    pub fn get_overlay_handle(&self) -> OverlayHandle {
+        OverlayHandle::new(self.overlay_commands.clone(), self.event_proxy.clone())
+    }
+
+    // 用于在组件回调中切换主题
+    pub fn get_theme_handle(&self) -> ThemeHandle {
+        ThemeHandle::new(self.event_proxy.clone())
+    }
+
+    // 安装主题，内置组件的颜色、状态样式和字号都从主题读取
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.renderer.set_theme(Arc::new(theme));
+        for widget in &mut self.widgets {
+            widget.set_theme(self.renderer.theme());
+        }
+    }
+
+    // 安装样式表，已添加和之后添加的组件都按它计算样式。
+    // 用 UiLoader::load_style 加载的样式表在文件修改后自动重新加载
+    pub fn set_stylesheet(&mut self, stylesheet: Stylesheet) {
+        self.stylesheet = stylesheet;
+        for widget in &mut self.widgets {
+            widget.set_stylesheet(&self.stylesheet);
+        }
+    }
+
     pub fn reload_ui(&mut self, container: Container) {
         self.widgets.clear();
-        self.add_widget(container);
+        self.roots.clear();
+        self.add_root(container);
+    }
+
+    // 从右到左的语言中整个界面镜像显示，已添加的组件也随之改变
+    pub fn set_layout_direction(&mut self, direction: LayoutDirection) {
+        self.layout_direction = direction;
+        for widget in &mut self.widgets {
+            widget.set_layout_direction(direction);
+        }
     }
 
     pub fn add_widget<W: Widget + 'static>(&mut self, widget: W) {
-        self.widgets.push(Box::new(widget));
+        self.push_widget(Box::new(widget), false);
+    }
+
+    // 添加铺满窗口的根组件，窗口缩放时重新布局，容器中的百分比尺寸和断点随之变化
+    pub fn add_root<W: Widget + 'static>(&mut self, widget: W) {
+        self.push_widget(Box::new(widget), true);
     }
 
-    pub fn run(mut self) {
+    fn push_widget(&mut self, mut widget: Box<dyn Widget>, root: bool) {
+        widget.set_layout_direction(self.layout_direction);
+        widget.set_theme(self.renderer.theme());
+        widget.set_stylesheet(&self.stylesheet);
+        self.widgets.push(widget);
+        self.roots.push(root);
+    }
+
+    pub fn run(self) {
         let window = self.window;
         let mut widgets = self.widgets;
+        let mut roots = self.roots;
+        let layout_direction = self.layout_direction;
+        // 添加子组件时只标记需要布局，在处理第一个事件之前完成排列
+        arrange_dirty(&mut widgets);
         let mut renderer = self.renderer;
         let event_loop = self.event_loop;
+        let overlay_commands = self.overlay_commands;
+        let mut stylesheet = self.stylesheet;
+        let mut overlays: Vec<Box<dyn Overlay>> = Vec::new();
+        let mut cursor = CursorIcon::Default;
 
         let mut frame_count = 0;
         let mut last_time = std::time::Instant::now();
 
         event_loop.run(move |event, _, control_flow| {
-            *control_flow = ControlFlow::Poll;
-
             match event {
                 Event::WindowEvent {
                     window_id,
@@ -78,11 +151,87 @@ impl Window {
                 } if window_id == window.id() => {
                     match event {
                         WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
-                        _ => {
-                            // 处理组件事件
+                        WindowEvent::Resized(size) => {
+                            renderer.resize(size.width, size.height);
+                            fill_window(&mut widgets, &roots, &renderer);
+                            // 对话框按新的窗口大小重新居中，遮罩覆盖整个窗口
+                            let bounds = window_bounds(&renderer);
+                            for overlay in &mut overlays {
+                                overlay.layout(bounds);
+                            }
+                            dispatch_event(&mut widgets, &mut overlays, event);
+                            window.request_redraw();
+                        }
+                        _ => dispatch_event(&mut widgets, &mut overlays, event),
+                    }
+
+                    // 根据悬停的组件切换鼠标指针
+                    let hovered = widgets
+                        .iter()
+                        .rev()
+                        .find_map(|widget| widget.cursor())
+                        .unwrap_or(CursorIcon::Default);
+                    if hovered != cursor {
+                        cursor = hovered;
+                        window.set_cursor_icon(cursor);
+                    }
+                }
+                Event::NewEvents(StartCause::Init) => {
+                    // 启动时通知组件窗口大小，弹出菜单据此避开窗口边缘
+                    fill_window(&mut widgets, &roots, &renderer);
+                    let size = winit::dpi::PhysicalSize::new(renderer.width(), renderer.height());
+                    for widget in &mut widgets {
+                        widget.handle_event(&WindowEvent::Resized(size));
+                    }
+                }
+                Event::NewEvents(StartCause::ResumeTimeReached { .. }) => {
+                    // 组件预约的唤醒时间到达，重绘
+                    window.request_redraw();
+                }
+                Event::UserEvent(CustomEvent::OverlayChanged) => {
+                    let bounds = window_bounds(&renderer);
+                    let commands: Vec<OverlayCommand> = overlay_commands.lock().unwrap().drain(..).collect();
+                    for command in commands {
+                        match command {
+                            OverlayCommand::Show(mut overlay) => {
+                                overlay.layout(bounds);
+                                overlays.push(overlay);
+                            }
+                            OverlayCommand::CloseTop => {
+                                if let Some(mut overlay) = overlays.pop() {
+                                    overlay.dismiss();
+                                }
+                            }
+                            OverlayCommand::CloseAll => {
+                                while let Some(mut overlay) = overlays.pop() {
+                                    overlay.dismiss();
+                                }
+                            }
+                        }
+                    }
+                    window.request_redraw();
+                }
+                Event::UserEvent(CustomEvent::SetTheme(theme)) => {
+                    renderer.set_theme(theme);
+                    for widget in &mut widgets {
+                        widget.set_theme(renderer.theme());
+                    }
+                    window.request_redraw();
+                }
+                Event::UserEvent(CustomEvent::ReloadStyle(content)) => {
+                    debug_log!("Reloading stylesheet with content length: {}", content.len());
+                    // 解析失败时保留原来的样式表
+                    match parse_style(&content) {
+                        Ok(sheet) => {
+                            stylesheet = sheet;
                             for widget in &mut widgets {
-                                widget.handle_event(event);
+                                widget.set_stylesheet(&stylesheet);
                             }
+                            arrange_dirty(&mut widgets);
+                            window.request_redraw();
+                        }
+                        Err(e) => {
+                            println!("Failed to parse stylesheet: {:?}", e);
                         }
                     }
                 }
@@ -90,9 +239,17 @@ impl Window {
                     debug_log!("Reloading UI with content length: {}", content.len());
                     // 解析新的UI内容
                     match parse_ui(&content) {
-                        Ok(container) => {
+                        Ok(mut container) => {
+                            container.set_layout_direction(layout_direction);
+                            container.set_theme(renderer.theme());
+                            container.set_stylesheet(&stylesheet);
+                            let size = winit::dpi::PhysicalSize::new(renderer.width(), renderer.height());
+                            container.handle_event(&WindowEvent::Resized(size));
                             widgets.clear();
                             widgets.push(Box::new(container));
+                            roots = vec![true];
+                            fill_window(&mut widgets, &roots, &renderer);
+                            arrange_dirty(&mut widgets);
                             window.request_redraw();
                         }
                         Err(e) => {
@@ -109,20 +266,110 @@ impl Window {
                         last_time = now;
                     }
 
-                    renderer.clear([64, 64, 64, 255]);
+                    arrange_dirty(&mut widgets);
+
+                    renderer.clear(renderer.theme().palette.background);
                     
                     // 绘制所有组件
                     for widget in &widgets {
                         widget.draw(&mut renderer);
                     }
+
+                    // 绘制弹出层
+                    for widget in &widgets {
+                        widget.draw_overlay(&mut renderer);
+                    }
+
+                    // 绘制窗口叠加层
+                    for overlay in &overlays {
+                        overlay.draw(&mut renderer);
+                    }
                     
                     renderer.render().unwrap();
                 }
                 Event::MainEventsCleared => {
+                    // 每批事件处理完后重绘，没有事件时休眠到最近的唤醒时间
                     window.request_redraw();
+                    if *control_flow != ControlFlow::Exit {
+                        let wakeup = widgets
+                            .iter()
+                            .filter_map(|widget| widget.next_wakeup())
+                            .chain(overlays.iter().filter_map(|overlay| overlay.next_wakeup()))
+                            .min();
+                        *control_flow = match wakeup {
+                            Some(time) => ControlFlow::WaitUntil(time),
+                            None => ControlFlow::Wait,
+                        };
+                    }
                 }
                 _ => (),
             }
         });
     }
-}
\ No newline at end of file
+}
+
+fn window_bounds(renderer: &Renderer) -> Rect {
+    Rect {
+        x: 0.0,
+        y: 0.0,
+        width: renderer.width() as f32,
+        height: renderer.height() as f32,
+    }
+}
+
+// 根组件铺满整个窗口
+fn fill_window(widgets: &mut [Box<dyn Widget>], roots: &[bool], renderer: &Renderer) {
+    let rect = window_bounds(renderer);
+    for (widget, _) in widgets.iter_mut().zip(roots).filter(|(_, &root)| root) {
+        widget.arrange(rect);
+    }
+}
+
+// 只重新排列有变化的组件树，其余组件保留上次的布局
+fn arrange_dirty(widgets: &mut [Box<dyn Widget>]) {
+    for widget in widgets {
+        if widget.needs_layout() {
+            let rect = widget.get_rect();
+            widget.arrange(rect);
+        }
+    }
+}
+
+// 事件先交给叠加层（从上到下），模态叠加层会阻止事件继续向下传递；
+// 之后由组件的弹出层处理，最后分发给所有组件
+fn dispatch_event(widgets: &mut [Box<dyn Widget>], overlays: &mut Vec<Box<dyn Overlay>>, event: &WindowEvent) {
+    let mut consumed = false;
+    for index in (0..overlays.len()).rev() {
+        match overlays[index].handle_event(event) {
+            OverlayResponse::Consumed => consumed = true,
+            OverlayResponse::Close => {
+                overlays.remove(index);
+                consumed = true;
+            }
+            OverlayResponse::Ignored => {
+                consumed = overlays[index].is_modal();
+            }
+        }
+        if consumed {
+            break;
+        }
+    }
+
+    if !consumed {
+        consumed = widgets
+            .iter_mut()
+            .rev()
+            .any(|widget| widget.handle_overlay_event(event));
+    }
+
+    // 处理组件事件
+    if !consumed {
+        for widget in widgets.iter_mut() {
+            widget.handle_event(event);
+        }
+    } else if let Some(outside) = cursor_outside(event) {
+        for widget in widgets.iter_mut() {
+            widget.handle_event(&outside);
+        }
+    }
+}